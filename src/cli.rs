@@ -1,4 +1,6 @@
-use crate::internal::config::_config::{AppConfig, ServerMode};
+use crate::internal::config::_config::{AppConfig, ParserConfig, ServerMode};
+use crate::internal::gemini::profile::{profile_by_name, profile_names, ValidationOptions};
+use crate::internal::parser::{diff_tools, generate_tools};
 use clap::{Arg, Command};
 
 pub fn build_cli() -> Command {
@@ -9,6 +11,74 @@ pub fn build_cli() -> Command {
     Command::new("yas-mcp")
         .version(version)
         .about("OpenAPI to MCP Server Generator")
+        .subcommand_negates_reqs(true)
+        .subcommand(
+            Command::new("diff")
+                .about("Compare the tools generated from two versions of a spec")
+                .arg(
+                    Arg::new("old")
+                        .long("old")
+                        .required(true)
+                        .help("Path or URL of the old spec"),
+                )
+                .arg(
+                    Arg::new("new")
+                        .long("new")
+                        .required(true)
+                        .help("Path or URL of the new spec"),
+                )
+                .arg(
+                    Arg::new("adjustments-file")
+                        .long("adjustments-file")
+                        .help("Adjustments applied to both specs"),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the report as JSON"),
+                ),
+        )
+        .subcommand(
+            Command::new("validate")
+                .about("Check the tools generated from a spec against a client's compatibility profile")
+                .arg(
+                    Arg::new("swagger-file")
+                        .long("swagger-file")
+                        .required(true)
+                        .help("Path or URL of the spec"),
+                )
+                .arg(
+                    Arg::new("adjustments-file")
+                        .long("adjustments-file")
+                        .help("Adjustments applied to the spec"),
+                )
+                .arg(
+                    Arg::new("profile")
+                        .long("profile")
+                        .value_parser(profile_names())
+                        .default_value("gemini")
+                        .help("Compatibility profile to validate against"),
+                )
+                .arg(
+                    Arg::new("strict")
+                        .long("strict")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Treat over-long descriptions as errors instead of warnings"),
+                )
+                .arg(
+                    Arg::new("max-description-length")
+                        .long("max-description-length")
+                        .value_parser(clap::value_parser!(usize))
+                        .help("Description length in characters above which a tool is reported (default: the profile's limit)"),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the report as JSON"),
+                ),
+        )
         .arg(
             Arg::new("mode")
                 .long("mode")
@@ -20,13 +90,25 @@ pub fn build_cli() -> Command {
             Arg::new("swagger-file")
                 .long("swagger-file")
                 .required(true)
-                .help("Path to the OpenAPI/Swagger file"),
+                .action(clap::ArgAction::Append)
+                .value_delimiter(',')
+                .help("Path to the OpenAPI/Swagger file; repeat (or comma separate) to merge several, optionally as prefix=path"),
         )
         .arg(
             Arg::new("adjustments-file")
                 .long("adjustments-file")
                 .help("Path to the adjustments file"),
         )
+        .arg(
+            Arg::new("cache-dir")
+                .long("cache-dir")
+                .help("Cache parsed tools in this directory to skip parsing unchanged specs"),
+        )
+        .arg(
+            Arg::new("audit-log")
+                .long("audit-log")
+                .help("Append a JSON audit record for every tool call to this file"),
+        )
         .arg(
             Arg::new("config")
                 .long("config")
@@ -46,23 +128,94 @@ pub fn build_cli() -> Command {
                 .default_value("3000")
                 .help("Server port (for http and sse modes)"),
         )
+        .arg(
+            Arg::new("uds")
+                .long("uds")
+                .help("Serve on this Unix domain socket instead of host:port (for http mode)"),
+        )
         .arg(
             Arg::new("endpoint")
                 .long("endpoint")
                 .short('e')
                 .help("API endpoint base URL for making requests (e.g., http://localhost:8080)"),
         )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(clap::ArgAction::SetTrue)
+                .help("Return the HTTP request each tool call would make instead of sending it"),
+        )
+        .arg(
+            Arg::new("read-only")
+                .long("read-only")
+                .action(clap::ArgAction::SetTrue)
+                .help("Only expose safe (GET/HEAD/OPTIONS) operations as tools"),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_parser(profile_names())
+                .help("Sanitize tool schemas and descriptions for this client's compatibility profile"),
+        )
+        .arg(
+            Arg::new("print-config")
+                .long("print-config")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print the effective configuration as JSON (secrets redacted) and exit"),
+        )
+        .arg(
+            Arg::new("self-test")
+                .long("self-test")
+                .action(clap::ArgAction::SetTrue)
+                .help("Dry-run every tool with an example built from its schema, report malformed requests and exit"),
+        )
+        .arg(
+            Arg::new("spec-fetch-retries")
+                .long("spec-fetch-retries")
+                .value_parser(clap::value_parser!(u32))
+                .help("Retries (with backoff) when fetching an http(s) spec fails (default: 3)"),
+        )
+        .arg(
+            Arg::new("fail-on-empty")
+                .long("fail-on-empty")
+                .value_parser(clap::value_parser!(bool))
+                .num_args(0..=1)
+                .default_missing_value("true")
+                .help("Exit with an error when the spec yields no tools (default: true for http/sse, false for stdio)"),
+        )
+        .arg(
+            Arg::new("tools")
+                .long("tools")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("Only expose these tools, by final tool name (comma separated, e.g. get__projects,post__projects)"),
+        )
+        .arg(
+            Arg::new("path-prefix")
+                .long("path-prefix")
+                .action(clap::ArgAction::Append)
+                .help("Only expose operations under this path prefix (repeatable)"),
+        )
 }
 
 pub fn parse_config(matches: &clap::ArgMatches) -> anyhow::Result<AppConfig> {
+    // Several files are kept comma separated, see AppConfig::swagger_sources
     let swagger_file = matches
-        .get_one::<String>("swagger-file")
+        .get_many::<String>("swagger-file")
         .expect("swagger-file is required")
-        .to_string();
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(",");
 
     let adjustments_file = matches
         .get_one::<String>("adjustments-file")
         .map(|s| s.to_string());
+    let cache_dir = matches
+        .get_one::<String>("cache-dir")
+        .map(|s| s.to_string());
+    let audit_log = matches
+        .get_one::<String>("audit-log")
+        .map(|s| s.to_string());
 
     let mode = match matches.get_one::<String>("mode").map(|s| s.as_str()) {
         Some("sse") => ServerMode::Sse,
@@ -78,7 +231,21 @@ pub fn parse_config(matches: &clap::ArgMatches) -> anyhow::Result<AppConfig> {
 
     let port = matches.get_one::<u16>("port").copied().unwrap_or(3000);
 
+    let uds = matches.get_one::<String>("uds").map(|s| s.to_string());
     let endpoint_url = matches.get_one::<String>("endpoint").map(|s| s.to_string());
+    let dry_run = matches.get_flag("dry-run");
+    let read_only = matches.get_flag("read-only");
+    let profile = matches.get_one::<String>("profile").cloned();
+    let spec_fetch_retries = matches.get_one::<u32>("spec-fetch-retries").copied();
+    let fail_on_empty = matches.get_one::<bool>("fail-on-empty").copied();
+    let tools: Vec<String> = matches
+        .get_many::<String>("tools")
+        .map(|values| values.map(|v| v.trim().to_string()).collect())
+        .unwrap_or_default();
+    let path_prefixes: Vec<String> = matches
+        .get_many::<String>("path-prefix")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
 
     // Try to load from config file first, fall back to CLI args
     match AppConfig::load() {
@@ -86,29 +253,122 @@ pub fn parse_config(matches: &clap::ArgMatches) -> anyhow::Result<AppConfig> {
             // Override with CLI values
             config.swagger_file = swagger_file;
             config.adjustments_file = adjustments_file;
+            if cache_dir.is_some() {
+                config.cache_dir = cache_dir;
+            }
+            if audit_log.is_some() {
+                config.server.audit_log = audit_log;
+            }
             config.server.mode = mode;
             config.server.host = host;
             config.server.port = port;
+            if uds.is_some() {
+                config.server.uds = uds;
+            }
+            if fail_on_empty.is_some() {
+                config.server.fail_on_empty = fail_on_empty;
+            }
+            if !tools.is_empty() {
+                config.server.tools = tools;
+            }
 
             // Override endpoint base_url if provided via CLI
             if let Some(url) = endpoint_url {
                 config.endpoint.base_url = url;
             }
+            if dry_run {
+                config.endpoint.dry_run = true;
+            }
+            if read_only {
+                config.parser.read_only = true;
+            }
+            if profile.is_some() {
+                config.parser.profile = profile;
+            }
+            if !path_prefixes.is_empty() {
+                config.parser.path_prefixes = path_prefixes;
+            }
+            if let Some(retries) = spec_fetch_retries {
+                config.parser.spec_fetch_retries = retries;
+            }
 
             Ok(config)
         }
         Err(_) => {
             // If config file loading fails, use CLI args only
             let mut config = AppConfig::from_args(swagger_file, adjustments_file, Some(mode));
+            config.cache_dir = cache_dir;
+            config.server.audit_log = audit_log;
             config.server.host = host;
             config.server.port = port;
+            config.server.uds = uds;
+            config.server.fail_on_empty = fail_on_empty;
+            config.server.tools = tools;
 
             // Set endpoint base_url if provided
             if let Some(url) = endpoint_url {
                 config.endpoint.base_url = url;
             }
+            config.endpoint.dry_run = dry_run;
+            config.parser.read_only = read_only;
+            config.parser.profile = profile;
+            config.parser.path_prefixes = path_prefixes;
+            if let Some(retries) = spec_fetch_retries {
+                config.parser.spec_fetch_retries = retries;
+            }
 
             Ok(config)
         }
     }
 }
+
+/// Run the `diff` subcommand and return the report to print
+pub fn run_diff(matches: &clap::ArgMatches) -> anyhow::Result<String> {
+    let old = matches.get_one::<String>("old").expect("old is required");
+    let new = matches.get_one::<String>("new").expect("new is required");
+    let adjustments = matches
+        .get_one::<String>("adjustments-file")
+        .map(String::as_str);
+
+    let old_tools = generate_tools(old, adjustments, ParserConfig::default())?;
+    let new_tools = generate_tools(new, adjustments, ParserConfig::default())?;
+    let diff = diff_tools(&old_tools, &new_tools);
+
+    if matches.get_flag("json") {
+        Ok(serde_json::to_string_pretty(&diff)?)
+    } else {
+        Ok(diff.report().trim_end().to_string())
+    }
+}
+
+/// Run the `validate` subcommand: the report to print, and whether every tool is compatible
+/// with the selected profile
+pub fn run_validate(matches: &clap::ArgMatches) -> anyhow::Result<(String, bool)> {
+    let spec = matches
+        .get_one::<String>("swagger-file")
+        .expect("swagger-file is required");
+    let adjustments = matches
+        .get_one::<String>("adjustments-file")
+        .map(String::as_str);
+
+    let tools: Vec<_> = generate_tools(spec, adjustments, ParserConfig::default())?
+        .into_iter()
+        .map(|route_tool| route_tool.tool)
+        .collect();
+    let profile = matches
+        .get_one::<String>("profile")
+        .and_then(|name| profile_by_name(name))
+        .expect("profile has a default and is checked by clap");
+    let options = ValidationOptions {
+        strict: matches.get_flag("strict"),
+        max_description_length: matches.get_one::<usize>("max-description-length").copied(),
+    };
+    let report = profile.validate_all(&tools, &options);
+
+    let output = if matches.get_flag("json") {
+        report.to_json()?
+    } else {
+        report.report().trim_end().to_string()
+    };
+    Ok((output, report.is_ok()))
+}
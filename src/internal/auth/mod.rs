@@ -1,9 +1,25 @@
 pub mod oauth2;
+pub mod token_manager;
+pub mod token_store;
 // pub mod providers;  // Comment out for now if not implemented
 
 use crate::internal::config::_config::OAuthConfig;
 use anyhow::{anyhow, Result};
 
+/// Create the OAuth2 client for the config, with its token cache when one is configured
+pub fn create_client(config: &OAuthConfig) -> Result<oauth2::OAuth2Client> {
+    let client = oauth2::OAuth2Client::new(create_provider_config(config)?)?;
+    match (&config.token_cache_path, &config.token_cache_passphrase) {
+        (None, _) => Ok(client),
+        (Some(path), Some(passphrase)) if !passphrase.is_empty() => {
+            Ok(client.with_token_store(token_store::TokenStore::new(path, passphrase.clone())))
+        }
+        (Some(_), _) => Err(anyhow!(
+            "oauth.token_cache_path requires oauth.token_cache_passphrase"
+        )),
+    }
+}
+
 /// Create provider-specific OAuth2 configuration
 pub fn create_provider_config(config: &OAuthConfig) -> Result<oauth2::OAuth2ProviderConfig> {
     match config.provider.to_lowercase().as_str() {
@@ -12,6 +28,7 @@ pub fn create_provider_config(config: &OAuthConfig) -> Result<oauth2::OAuth2Prov
             auth_url: "https://github.com/oauth/authorize".to_string(),
             token_url: "https://github.com/oauth/access_token".to_string(),
             user_info_url: Some("https://api.github.com/user".to_string()),
+            device_authorization_url: Some("https://github.com/login/device/code".to_string()),
             scopes: config.scopes.clone(),
             redirect_uri: config.redirect_uri.clone(),
             client_id: config.client_id.clone(),
@@ -27,6 +44,7 @@ pub fn create_provider_config(config: &OAuthConfig) -> Result<oauth2::OAuth2Prov
             auth_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
             token_url: "https://oauth2.googleapis.com/token".to_string(),
             user_info_url: Some("https://www.googleapis.com/oauth2/v3/userinfo".to_string()),
+            device_authorization_url: Some("https://oauth2.googleapis.com/device/code".to_string()),
             scopes: config.scopes.clone(),
             redirect_uri: config.redirect_uri.clone(),
             client_id: config.client_id.clone(),
@@ -43,6 +61,9 @@ pub fn create_provider_config(config: &OAuthConfig) -> Result<oauth2::OAuth2Prov
             auth_url: "https://login.microsoftonline.com/common/oauth2/v2.0/authorize".to_string(),
             token_url: "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
             user_info_url: Some("https://graph.microsoft.com/v1.0/me".to_string()),
+            device_authorization_url: Some(
+                "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode".to_string(),
+            ),
             scopes: config.scopes.clone(),
             redirect_uri: config.redirect_uri.clone(),
             client_id: config.client_id.clone(),
@@ -54,6 +75,7 @@ pub fn create_provider_config(config: &OAuthConfig) -> Result<oauth2::OAuth2Prov
             auth_url: config.auth_url.clone().unwrap_or_default(),
             token_url: config.token_url.clone().unwrap_or_default(),
             user_info_url: config.user_info_url.clone(),
+            device_authorization_url: config.device_authorization_url.clone(),
             scopes: config.scopes.clone(),
             redirect_uri: config.redirect_uri.clone(),
             client_id: config.client_id.clone(),
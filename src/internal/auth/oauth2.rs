@@ -2,7 +2,15 @@ use anyhow::{anyhow, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{debug, error, info};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use crate::internal::auth::token_store::{CachedToken, TokenStore};
+
+/// Grant type of device access token requests (RFC 8628 section 3.4)
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Polling interval when the provider does not set one (RFC 8628 section 3.2)
+pub const DEFAULT_DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuth2ProviderConfig {
@@ -12,6 +20,8 @@ pub struct OAuth2ProviderConfig {
     pub auth_url: String,
     pub token_url: String,
     pub user_info_url: Option<String>,
+    /// Device authorization endpoint for the device-code flow (RFC 8628)
+    pub device_authorization_url: Option<String>,
     pub scopes: Vec<String>,
     pub redirect_uri: Option<String>,
     /// Provider-specific additional parameters
@@ -28,6 +38,29 @@ pub struct OAuth2Token {
     pub id_token: Option<String>, // For OpenID Connect
 }
 
+/// Device authorization response: what the user needs to approve this device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    /// Google calls it `verification_url`
+    #[serde(alias = "verification_url")]
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until `device_code` expires
+    pub expires_in: u64,
+    /// Minimum seconds between token polls
+    pub interval: Option<u64>,
+}
+
+impl DeviceAuthorization {
+    /// Interval the provider asks polls to keep, for `with_device_poll_interval`
+    pub fn poll_interval(&self) -> Duration {
+        self.interval
+            .map_or(DEFAULT_DEVICE_POLL_INTERVAL, Duration::from_secs)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
     pub id: String,
@@ -40,12 +73,71 @@ pub struct UserInfo {
 pub struct OAuth2Client {
     config: OAuth2ProviderConfig,
     client: Client,
+    device_poll_interval: Duration,
+    token_store: Option<TokenStore>,
 }
 
 impl OAuth2Client {
     pub fn new(config: OAuth2ProviderConfig) -> Result<Self> {
         let client = Client::new();
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            client,
+            device_poll_interval: DEFAULT_DEVICE_POLL_INTERVAL,
+            token_store: None,
+        })
+    }
+
+    /// Persist every token obtained to `store`, and read it back with `cached_token`
+    pub fn with_token_store(mut self, store: TokenStore) -> Self {
+        self.token_store = Some(store);
+        self
+    }
+
+    /// Token kept by the token store from an earlier run. An expired token is refreshed
+    /// when it has a refresh token, and dropped otherwise.
+    pub async fn cached_token(&self) -> Result<Option<OAuth2Token>> {
+        let Some(store) = &self.token_store else {
+            return Ok(None);
+        };
+        let Some(cached) = store.load()? else {
+            return Ok(None);
+        };
+        if !cached.is_expired() {
+            return Ok(Some(cached.token));
+        }
+
+        match &cached.token.refresh_token {
+            Some(refresh_token) => {
+                info!(
+                    "Cached OAuth2 token for {} expired, refreshing",
+                    self.config.provider
+                );
+                self.refresh_token(refresh_token).await.map(Some)
+            }
+            None => {
+                info!(
+                    "Cached OAuth2 token for {} expired and cannot be refreshed",
+                    self.config.provider
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Write a newly obtained token to the token store; a failure only costs a re-auth later
+    fn persist_token(&self, token: &OAuth2Token) {
+        if let Some(store) = &self.token_store {
+            if let Err(e) = store.save(&CachedToken::new(token.clone())) {
+                warn!("Failed to cache OAuth2 token: {:#}", e);
+            }
+        }
+    }
+
+    /// Wait this long between device token polls; a `slow_down` answer adds 5 seconds
+    pub fn with_device_poll_interval(mut self, interval: Duration) -> Self {
+        self.device_poll_interval = interval;
+        self
     }
 
     /// Generate provider-specific authorization URL
@@ -97,6 +189,7 @@ impl OAuth2Client {
         let response = self
             .client
             .post(&self.config.token_url)
+            .header("Accept", "application/json")
             .form(&params)
             .send()
             .await
@@ -108,28 +201,34 @@ impl OAuth2Client {
                 )
             })?;
 
-        self.handle_token_response(response).await
+        let token = self.handle_token_response(response).await?;
+        self.persist_token(&token);
+        Ok(token)
     }
 
     /// Handle token response (provider-specific parsing)
     async fn handle_token_response(&self, response: reqwest::Response) -> Result<OAuth2Token> {
         let status = response.status();
+        let content_type = content_type(&response);
+        let body = response.text().await.unwrap_or_default();
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
             error!(
                 "OAuth2 token exchange failed for {}: {}",
-                self.config.provider, error_text
+                self.config.provider, body
             );
             return Err(anyhow!("OAuth2 token exchange failed: {}", status));
         }
 
-        let token: OAuth2Token = response.json().await.map_err(|e| {
-            anyhow!(
-                "Failed to parse OAuth2 token response from {}: {}",
-                self.config.provider,
-                e
-            )
-        })?;
+        let fields = token_fields(&content_type, &body);
+        // GitHub reports a rejected code with status 200
+        if let Some(code) = fields.get("error").and_then(serde_json::Value::as_str) {
+            error!(
+                "OAuth2 token exchange failed for {}: {}",
+                self.config.provider, body
+            );
+            return Err(anyhow!("OAuth2 token exchange failed: {}", code));
+        }
+        let token = self.parse_token(fields)?;
 
         info!(
             "Successfully obtained OAuth2 access token for {}",
@@ -138,6 +237,19 @@ impl OAuth2Client {
         Ok(token)
     }
 
+    fn parse_token(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<OAuth2Token> {
+        serde_json::from_value(serde_json::Value::Object(fields)).map_err(|e| {
+            anyhow!(
+                "Failed to parse OAuth2 token response from {}: {}",
+                self.config.provider,
+                e
+            )
+        })
+    }
+
     /// Get user information (provider-specific)
     pub async fn get_user_info(&self, access_token: &str) -> Result<UserInfo> {
         let user_info_url = match &self.config.user_info_url {
@@ -275,6 +387,7 @@ impl OAuth2Client {
         let response = self
             .client
             .post(&self.config.token_url)
+            .header("Accept", "application/json")
             .form(&params)
             .send()
             .await
@@ -286,6 +399,178 @@ impl OAuth2Client {
                 )
             })?;
 
-        self.handle_token_response(response).await
+        let mut token = self.handle_token_response(response).await?;
+        // Providers may omit the refresh token when it stays valid
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token.to_string());
+        }
+        self.persist_token(&token);
+        Ok(token)
+    }
+
+    /// Start the device-code flow (RFC 8628): request a device code and log the code the
+    /// user enters at the verification URI. Follow with `poll_device_token`, polling at
+    /// the returned `poll_interval()`.
+    pub async fn device_authorization(&self) -> Result<DeviceAuthorization> {
+        let device_url = self
+            .config
+            .device_authorization_url
+            .as_ref()
+            .ok_or_else(|| {
+                anyhow!(
+                    "No device authorization URL configured for {}",
+                    self.config.provider
+                )
+            })?;
+        let params = [
+            ("client_id", self.config.client_id.clone()),
+            ("scope", self.config.scopes.join(" ")),
+        ];
+
+        let response = self
+            .client
+            .post(device_url)
+            .header("Accept", "application/json")
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to request OAuth2 device code for {}: {}",
+                    self.config.provider,
+                    e
+                )
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!(
+                "OAuth2 device authorization failed for {}: {}",
+                self.config.provider, error_text
+            );
+            return Err(anyhow!("OAuth2 device authorization failed: {}", status));
+        }
+
+        let authorization: DeviceAuthorization = response.json().await.map_err(|e| {
+            anyhow!(
+                "Failed to parse OAuth2 device authorization from {}: {}",
+                self.config.provider,
+                e
+            )
+        })?;
+
+        info!(
+            "To authorize yas-mcp with {}, visit {} and enter the code {}",
+            self.config.provider, authorization.verification_uri, authorization.user_code
+        );
+        Ok(authorization)
+    }
+
+    /// Poll the token endpoint with a device code until the user approves the device.
+    /// Fails when the user denies access or the device code expires.
+    pub async fn poll_device_token(&self, device_code: &str) -> Result<OAuth2Token> {
+        let mut params = vec![
+            ("grant_type", DEVICE_CODE_GRANT_TYPE),
+            ("device_code", device_code),
+            ("client_id", self.config.client_id.as_str()),
+        ];
+        // Public clients have no secret
+        if !self.config.client_secret.is_empty() {
+            params.push(("client_secret", self.config.client_secret.as_str()));
+        }
+
+        let mut interval = self.device_poll_interval;
+        loop {
+            let response = self
+                .client
+                .post(&self.config.token_url)
+                .header("Accept", "application/json")
+                .form(&params)
+                .send()
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "Failed to poll OAuth2 device token for {}: {}",
+                        self.config.provider,
+                        e
+                    )
+                })?;
+
+            let status = response.status();
+            let content_type = content_type(&response);
+            let body = response.text().await.unwrap_or_default();
+            let fields = token_fields(&content_type, &body);
+            // Pending answers are errors; GitHub sends them with status 200
+            let error_code = fields
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string);
+
+            match error_code.as_deref() {
+                None if status.is_success() => {
+                    let token = self.parse_token(fields)?;
+                    info!(
+                        "Successfully obtained OAuth2 access token for {} via device authorization",
+                        self.config.provider
+                    );
+                    self.persist_token(&token);
+                    return Ok(token);
+                }
+                Some("authorization_pending") => {}
+                Some("slow_down") => interval += Duration::from_secs(5),
+                Some(code) => {
+                    error!(
+                        "OAuth2 device authorization failed for {}: {}",
+                        self.config.provider, body
+                    );
+                    return Err(anyhow!("OAuth2 device authorization failed: {}", code));
+                }
+                None => {
+                    error!(
+                        "OAuth2 device token request failed for {}: {}",
+                        self.config.provider, body
+                    );
+                    return Err(anyhow!("OAuth2 device token request failed: {}", status));
+                }
+            }
+
+            debug!(
+                "OAuth2 device authorization pending for {}, polling again in {:?}",
+                self.config.provider, interval
+            );
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+/// Media type of a response without parameters, lowercased
+fn content_type(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Fields of a token endpoint body. Form-encoded bodies (GitHub's default) become the
+/// JSON object a JSON provider would have sent, with `expires_in` as a number.
+fn token_fields(content_type: &str, body: &str) -> serde_json::Map<String, serde_json::Value> {
+    if content_type == "application/x-www-form-urlencoded" {
+        return url::form_urlencoded::parse(body.as_bytes())
+            .map(|(key, value)| {
+                let value = match (key.as_ref(), value.parse::<u64>()) {
+                    ("expires_in", Ok(secs)) => serde_json::Value::from(secs),
+                    _ => serde_json::Value::String(value.into_owned()),
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+    }
+    match serde_json::from_str(body) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
     }
 }
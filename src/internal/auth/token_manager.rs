@@ -0,0 +1,176 @@
+// src/internal/auth/token_manager.rs
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::internal::auth::oauth2::{OAuth2Client, OAuth2ProviderConfig, OAuth2Token};
+
+/// How long before expiry a token is refreshed when no skew is configured
+pub const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+/// Keeps an OAuth2 token valid: hands out its access token and refreshes it through the
+/// refresh token once it is within the skew of expiring. Concurrent callers share one refresh.
+pub struct OAuth2TokenManager {
+    client: OAuth2Client,
+    skew: Duration,
+    current: Mutex<ManagedToken>,
+}
+
+struct ManagedToken {
+    token: OAuth2Token,
+    /// `None` when the provider did not say when the token expires
+    expires_at: Option<Instant>,
+}
+
+impl ManagedToken {
+    fn new(token: OAuth2Token) -> Self {
+        let expires_at = token
+            .expires_in
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+        Self { token, expires_at }
+    }
+
+    fn expires_within(&self, skew: Duration) -> bool {
+        self.expires_at
+            .is_some_and(|at| at.saturating_duration_since(Instant::now()) <= skew)
+    }
+}
+
+impl OAuth2TokenManager {
+    pub fn new(client: OAuth2Client, token: OAuth2Token) -> Self {
+        Self {
+            client,
+            skew: DEFAULT_REFRESH_SKEW,
+            current: Mutex::new(ManagedToken::new(token)),
+        }
+    }
+
+    /// Manager for the refreshable upstream token in an endpoint's `auth_config`
+    /// (`token_url` and `refresh_token`, optionally `client_id`, `client_secret`, the
+    /// current `token` and its RFC 3339 `token_expires_at`); `None` when the config has no
+    /// refresh token to work with. Without a current token, the first request refreshes.
+    pub fn from_auth_config(
+        auth_config: &HashMap<String, String>,
+        skew: Option<Duration>,
+    ) -> Option<Result<Self>> {
+        let refresh_token = auth_config.get("refresh_token")?;
+        let token_url = auth_config.get("token_url")?;
+        let setting = |key: &str| auth_config.get(key).cloned().unwrap_or_default();
+
+        let client = OAuth2Client::new(OAuth2ProviderConfig {
+            provider: "upstream".to_string(),
+            client_id: setting("client_id"),
+            client_secret: setting("client_secret"),
+            auth_url: String::new(),
+            token_url: token_url.clone(),
+            user_info_url: None,
+            device_authorization_url: None,
+            scopes: Vec::new(),
+            redirect_uri: None,
+            extra_params: None,
+        });
+        // A missing or empty token is treated as already expired
+        let expires_in = match (
+            auth_config.get("token").filter(|token| !token.is_empty()),
+            auth_config.get("token_expires_at"),
+        ) {
+            (None, _) => Some(0),
+            (Some(_), None) => None,
+            (Some(_), Some(expires_at)) => match chrono::DateTime::parse_from_rfc3339(expires_at) {
+                Ok(at) => Some((at.timestamp() - chrono::Utc::now().timestamp()).max(0) as u64),
+                Err(e) => {
+                    return Some(Err(anyhow!(
+                        "Invalid token_expires_at '{}': {}",
+                        expires_at,
+                        e
+                    )))
+                }
+            },
+        };
+        let token = OAuth2Token {
+            access_token: setting("token"),
+            token_type: "Bearer".to_string(),
+            expires_in,
+            refresh_token: Some(refresh_token.clone()),
+            scope: auth_config.get("scope").cloned(),
+            id_token: None,
+        };
+        Some(client.map(|client| {
+            Self::new(client, token).with_refresh_skew(skew.unwrap_or(DEFAULT_REFRESH_SKEW))
+        }))
+    }
+
+    /// Refresh tokens this long before they expire
+    pub fn with_refresh_skew(mut self, skew: Duration) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// A valid access token, refreshed first if it is about to expire
+    pub async fn access_token(&self) -> Result<String> {
+        let mut current = self.current.lock().await;
+        if !current.expires_within(self.skew) {
+            return Ok(current.token.access_token.clone());
+        }
+
+        let Some(refresh_token) = current.token.refresh_token.clone() else {
+            warn!("OAuth2 token is expiring and has no refresh token");
+            return Ok(current.token.access_token.clone());
+        };
+        match self.client.refresh_token(&refresh_token).await {
+            Ok(token) => {
+                info!("Refreshed upstream OAuth2 token");
+                *current = ManagedToken::new(token);
+                Ok(current.token.access_token.clone())
+            }
+            // Within the skew the old token still works for a while
+            Err(e) if !current.expires_within(Duration::ZERO) => {
+                warn!(
+                    "Failed to refresh OAuth2 token, using the current one: {:#}",
+                    e
+                );
+                Ok(current.token.access_token.clone())
+            }
+            Err(e) => Err(anyhow!(
+                "OAuth2 token expired and could not be refreshed: {:#}",
+                e
+            )),
+        }
+    }
+
+    /// Refresh after the upstream rejected `rejected` (e.g. it was revoked before its
+    /// expiry), unless another caller has already replaced it
+    pub async fn refresh_rejected(&self, rejected: &str) -> Result<String> {
+        let mut current = self.current.lock().await;
+        if current.token.access_token != rejected {
+            return Ok(current.token.access_token.clone());
+        }
+
+        let Some(refresh_token) = current.token.refresh_token.clone() else {
+            return Err(anyhow!(
+                "OAuth2 token was rejected and has no refresh token"
+            ));
+        };
+        let token = self
+            .client
+            .refresh_token(&refresh_token)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "OAuth2 token was rejected and could not be refreshed: {:#}",
+                    e
+                )
+            })?;
+        info!("Refreshed upstream OAuth2 token after it was rejected");
+        *current = ManagedToken::new(token);
+        Ok(current.token.access_token.clone())
+    }
+
+    /// The token currently held
+    pub async fn token(&self) -> OAuth2Token {
+        self.current.lock().await.token.clone()
+    }
+}
@@ -0,0 +1,159 @@
+// src/internal/auth/token_store.rs
+
+use anyhow::{anyhow, Context, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::internal::auth::oauth2::OAuth2Token;
+
+/// Marks the file format; bump when the layout or key derivation changes
+const MAGIC: &[u8] = b"YASTOK1";
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// A token as persisted, with the absolute time it expires at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedToken {
+    pub token: OAuth2Token,
+    /// Unix seconds; `None` when the provider did not say
+    pub expires_at: Option<u64>,
+}
+
+impl CachedToken {
+    /// Stamp a freshly obtained token with its expiry
+    pub fn new(token: OAuth2Token) -> Self {
+        let expires_at = token.expires_in.map(|secs| unix_now() + secs);
+        Self { token, expires_at }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= unix_now())
+    }
+}
+
+/// OAuth token file encrypted with ChaCha20-Poly1305 under a key derived from a
+/// passphrase (PBKDF2-HMAC-SHA256). Layout: magic, salt, nonce, ciphertext.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    path: PathBuf,
+    passphrase: String,
+}
+
+impl TokenStore {
+    pub fn new(path: impl Into<PathBuf>, passphrase: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            passphrase: passphrase.into(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the stored token; `None` when nothing has been stored yet
+    pub fn load(&self) -> Result<Option<CachedToken>> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read token cache {}", self.path.display()))
+            }
+        };
+
+        let header_len = MAGIC.len() + SALT_LEN + NONCE_LEN;
+        if data.len() < header_len || !data.starts_with(MAGIC) {
+            return Err(anyhow!(
+                "Token cache {} is not a yas-mcp token file",
+                self.path.display()
+            ));
+        }
+        let (salt, rest) = data[MAGIC.len()..].split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| anyhow!("Token cache {} is corrupt", self.path.display()))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key(salt)?
+            .open_in_place(nonce, Aad::from(MAGIC), &mut in_out)
+            .map_err(|_| {
+                anyhow!(
+                    "Failed to decrypt token cache {}: wrong passphrase or corrupt file",
+                    self.path.display()
+                )
+            })?;
+        let cached = serde_json::from_slice(plaintext)
+            .with_context(|| format!("Failed to parse token cache {}", self.path.display()))?;
+        Ok(Some(cached))
+    }
+
+    /// Encrypt and write the token, replacing any stored one
+    pub fn save(&self, cached: &CachedToken) -> Result<()> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| anyhow!("Failed to generate token cache salt"))?;
+
+        let mut in_out = serde_json::to_vec(cached)?;
+        self.key(&salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut in_out,
+            )
+            .map_err(|_| anyhow!("Failed to encrypt token cache"))?;
+
+        let mut data = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + in_out.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&in_out);
+        write_private(&self.path, &data)
+            .with_context(|| format!("Failed to write token cache {}", self.path.display()))
+    }
+
+    fn key(&self, salt: &[u8]) -> Result<LessSafeKey> {
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+            salt,
+            self.passphrase.as_bytes(),
+            &mut key,
+        );
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+            .map_err(|_| anyhow!("Failed to derive token cache key"))?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+/// Write a file only the current user can read
+fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(path)?, data)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
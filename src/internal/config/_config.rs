@@ -2,6 +2,8 @@ use config::{Config, ConfigError, File};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::internal::parser::refs::remote_spec_url;
+
 // Version information from build script - using option_env! for safety
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -59,6 +61,73 @@ pub struct EndpointConfig {
     pub auth_config: HashMap<String, String>,
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    /// Return the composed request instead of sending it upstream
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Prefix removed from spec paths before calling the endpoint (e.g. "/v1")
+    #[serde(default)]
+    pub path_prefix_strip: Option<String>,
+    /// Prefix prepended to spec paths before calling the endpoint (e.g. "/api")
+    #[serde(default)]
+    pub path_prefix_add: Option<String>,
+    /// Template for the request path, applied after the prefix rewrites: `${path}` is the
+    /// spec path and any other `${NAME}` an environment variable (e.g. "/${API_VERSION}${path}")
+    #[serde(default)]
+    pub path_template: Option<String>,
+    /// Idle connections kept open per upstream host (reqwest default: unlimited)
+    #[serde(default)]
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle pooled connection is kept before closing (reqwest default: 90)
+    #[serde(default)]
+    pub pool_idle_timeout: Option<u64>,
+    /// Speak HTTP/2 to the upstream without negotiating (h2c or TLS); off means auto-negotiate
+    #[serde(default)]
+    pub http2_prior_knowledge: bool,
+    /// Seconds between HTTP/2 keep-alive pings; unset disables them
+    #[serde(default)]
+    pub http2_keep_alive_interval: Option<u64>,
+    /// Query parameters added to every request unless the call already sets them
+    #[serde(default)]
+    pub default_query: HashMap<String, String>,
+    /// Simultaneous requests allowed per upstream host; more wait for a slot (unset: unlimited)
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+    /// Seconds before expiry an oauth2 token with a refresh token is refreshed (default: 60)
+    #[serde(default)]
+    pub token_refresh_skew: Option<u64>,
+    /// User-Agent sent upstream (default: yas-mcp/<version>)
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Secret for HMAC-SHA256 request signatures; signing is off when unset
+    #[serde(default)]
+    pub hmac_secret: Option<String>,
+    /// Header carrying the hex signature (default: X-Signature)
+    #[serde(default)]
+    pub hmac_header: Option<String>,
+    /// Header carrying the Unix timestamp the signature covers (default: X-Timestamp)
+    #[serde(default)]
+    pub hmac_timestamp_header: Option<String>,
+    /// Request parts signed, in order, joined by newlines (empty: method, path, timestamp, body)
+    #[serde(default = "SignedComponent::defaults")]
+    pub signed_components: Vec<SignedComponent>,
+}
+
+/// Part of a request covered by the HMAC signature
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedComponent {
+    Method,
+    /// URL path without the query string
+    Path,
+    Query,
+    Timestamp,
+    Body,
+}
+
+impl SignedComponent {
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::Method, Self::Path, Self::Timestamp, Self::Body]
+    }
 }
 
 /// ServerMode represents the server operation mode
@@ -87,6 +156,78 @@ pub struct ServerConfig {
     pub name: String,
     #[serde(default = "default_version")]
     pub version: String,
+    /// Validate tool-call arguments against the tool's input schema before dispatch
+    #[serde(default)]
+    pub validate_arguments: bool,
+    /// Register the `__openapi_info` tool describing the API and its operations
+    #[serde(default)]
+    pub info_tool: bool,
+    /// Register the `__example_for` tool returning a request body example per tool
+    #[serde(default)]
+    pub example_tool: bool,
+    /// Append one JSON audit record per tool call to this file
+    #[serde(default)]
+    pub audit_log: Option<String>,
+    /// Cut the server instructions taken from the spec's `info.description` to this many
+    /// characters (0 = unlimited)
+    #[serde(default = "default_max_instructions_length")]
+    pub max_instructions_length: usize,
+    /// Route of the JSON-RPC endpoint in HTTP mode
+    #[serde(default = "default_mcp_path")]
+    pub mcp_path: String,
+    /// Serve HTTP mode on this Unix domain socket instead of host:port
+    #[serde(default)]
+    pub uds: Option<String>,
+    /// PEM certificate chain; with `tls_key_path`, HTTP mode serves HTTPS
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    /// PEM private key matching `tls_cert_path`
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+    /// Refuse to start when the spec yields no tools (default: on for http/sse, off for stdio)
+    #[serde(default)]
+    pub fail_on_empty: Option<bool>,
+    /// Send a W3C `traceparent` header upstream on every tool call, continuing the
+    /// trace of a `__traceparent` argument or starting a new one
+    #[serde(default)]
+    pub trace_context: bool,
+    /// Only expose these tools, by final tool name; empty exposes every tool
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl ServerConfig {
+    /// Certificate and key paths when TLS is configured; setting only one of them is an error
+    pub fn tls_paths(&self) -> Result<Option<(&str, &str)>, ConfigError> {
+        match (self.tls_cert_path.as_deref(), self.tls_key_path.as_deref()) {
+            (Some(cert), Some(key)) => Ok(Some((cert, key))),
+            (None, None) => Ok(None),
+            (Some(_), None) => Err(ConfigError::Message(
+                "tls_cert_path is set but tls_key_path is missing".to_string(),
+            )),
+            (None, Some(_)) => Err(ConfigError::Message(
+                "tls_key_path is set but tls_cert_path is missing".to_string(),
+            )),
+        }
+    }
+
+    /// Whether an empty tool set is an error; network modes serving nothing are almost
+    /// always misconfigured, while stdio is often launched speculatively by clients
+    pub fn fail_on_empty_tools(&self) -> bool {
+        self.fail_on_empty.unwrap_or(self.mode != ServerMode::Stdio)
+    }
+
+    /// Effective JSON-RPC route: always starts with a slash, `/mcp` when unset
+    pub fn mcp_route(&self) -> String {
+        let path = self.mcp_path.trim();
+        if path.is_empty() {
+            default_mcp_path()
+        } else if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        }
+    }
 }
 
 fn default_port() -> u16 {
@@ -104,6 +245,12 @@ fn default_name() -> String {
 fn default_version() -> String {
     VERSION.to_string()
 }
+fn default_max_instructions_length() -> usize {
+    2000
+}
+fn default_mcp_path() -> String {
+    "/mcp".to_string()
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LoggingConfig {
@@ -133,17 +280,165 @@ fn default_true() -> bool {
     true
 }
 
+/// ParserConfig controls how OpenAPI operations are turned into tools
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserConfig {
+    /// Operation `x-` extensions whose values are appended to the tool description
+    #[serde(default = "default_description_extensions")]
+    pub description_extensions: Vec<String>,
+    /// Skip operations whose parameters cannot all be exposed instead of registering a lossy tool
+    #[serde(default)]
+    pub strict: bool,
+    /// Strip schema keywords Gemini rejects (e.g. additionalProperties) from input schemas
+    #[serde(default)]
+    pub gemini_compatible: bool,
+    /// Compatibility profile (`gemini`, `default`) whose rules input schemas and descriptions
+    /// are sanitized against; `gemini_compatible` selects `gemini` when unset
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Only expose safe (GET/HEAD/OPTIONS) operations, regardless of adjustments
+    #[serde(default)]
+    pub read_only: bool,
+    /// Truncate tool descriptions to this many characters (0 disables truncation)
+    #[serde(default = "default_max_description_length")]
+    pub max_description_length: usize,
+    /// Strip HTML and Markdown markup from operation descriptions, keeping code spans and URLs
+    #[serde(default)]
+    pub sanitize_descriptions: bool,
+    /// Append a request body example to the `body` argument's description
+    #[serde(default)]
+    pub body_examples: bool,
+    /// Only expose operations under one of these path prefixes (empty keeps every path)
+    #[serde(default)]
+    pub path_prefixes: Vec<String>,
+    /// Fetch `$ref`s that point to http(s) URLs; file refs next to the spec always resolve
+    #[serde(default)]
+    pub remote_refs: bool,
+    /// Retries after a failed fetch of an http(s) spec, with exponential backoff
+    #[serde(default = "default_spec_fetch_retries")]
+    pub spec_fetch_retries: u32,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            description_extensions: default_description_extensions(),
+            strict: false,
+            gemini_compatible: false,
+            profile: None,
+            read_only: false,
+            max_description_length: default_max_description_length(),
+            sanitize_descriptions: false,
+            body_examples: false,
+            path_prefixes: Vec::new(),
+            remote_refs: false,
+            spec_fetch_retries: default_spec_fetch_retries(),
+        }
+    }
+}
+
+fn default_description_extensions() -> Vec<String> {
+    vec!["x-mcp-hint".to_string()]
+}
+fn default_spec_fetch_retries() -> u32 {
+    3
+}
+/// Default length limit of tool, schema and parameter descriptions
+pub const DEFAULT_MAX_DESCRIPTION_LENGTH: usize = 700;
+
+fn default_max_description_length() -> usize {
+    DEFAULT_MAX_DESCRIPTION_LENGTH
+}
+
 // Add to AppConfig struct:
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub logging: LoggingConfig,
     pub endpoint: EndpointConfig,
+    #[serde(default)]
+    pub parser: ParserConfig,
     pub swagger_file: String,
     pub adjustments_file: Option<String>,
+    /// Directory for cached parse results; parsing is skipped when the spec is unchanged
+    #[serde(default)]
+    pub cache_dir: Option<String>,
     pub oauth: Option<OAuthConfig>,
 }
 
+/// Placeholder shown instead of secret values
+pub const REDACTED: &str = "<redacted>";
+
+/// Headers that usually carry credentials
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name == "authorization"
+        || name == "cookie"
+        || name.contains("api-key")
+        || name.contains("apikey")
+        || name.contains("token")
+        || name.contains("secret")
+}
+
+/// `swagger_file` entry that reads the spec from stdin
+pub const STDIN_SPEC: &str = "-";
+
+/// One OpenAPI file to load, with the prefix applied to its tool names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwaggerSource {
+    pub path: String,
+    pub tool_prefix: Option<String>,
+}
+
+impl SwaggerSource {
+    /// Parse a `path` or `prefix=path` entry. http(s) URLs are taken whole, so `=` in
+    /// their query string is not mistaken for a prefix; a prefix must be a plain name
+    /// (letters, digits, `_` and `-`).
+    pub fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        let prefixed = match entry.split_once('=') {
+            _ if remote_spec_url(entry).is_some() => None,
+            Some((prefix, path)) if is_plain_prefix(prefix.trim()) => Some((prefix, path)),
+            _ => None,
+        };
+        match prefixed {
+            Some((prefix, path)) => Self {
+                path: path.trim().to_string(),
+                tool_prefix: Some(prefix.trim().to_string()),
+            },
+            None => Self {
+                path: entry.to_string(),
+                tool_prefix: None,
+            },
+        }
+    }
+
+    /// Whether the spec is read from stdin (`-`)
+    pub fn is_stdin(&self) -> bool {
+        self.path == STDIN_SPEC
+    }
+
+    /// File stem used as the tool prefix when several files are loaded; for URLs, the
+    /// stem of the last path segment
+    fn default_prefix(&self) -> String {
+        let path = match remote_spec_url(&self.path) {
+            Some(url) => url.path().to_string(),
+            None => self.path.clone(),
+        };
+        std::path::Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.clone())
+    }
+}
+
+fn is_plain_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuthConfig {
     pub enabled: bool,
@@ -159,6 +454,15 @@ pub struct OAuthConfig {
     pub user_info_url: Option<String>,
     pub redirect_uri: Option<String>,
     pub extra_params: Option<HashMap<String, String>>,
+    /// Device authorization endpoint (RFC 8628) of a generic provider
+    #[serde(default)]
+    pub device_authorization_url: Option<String>,
+    /// File where obtained tokens are kept, encrypted, across restarts (unset: memory only)
+    #[serde(default)]
+    pub token_cache_path: Option<String>,
+    /// Passphrase the token cache key is derived from; required with `token_cache_path`
+    #[serde(default)]
+    pub token_cache_passphrase: Option<String>,
 }
 
 impl AppConfig {
@@ -211,6 +515,57 @@ impl AppConfig {
         Ok(app_config)
     }
 
+    /// The OpenAPI files listed in `swagger_file` (comma separated).
+    /// With more than one file, every file's tools get a prefix (its stem unless set via `prefix=path`).
+    pub fn swagger_sources(&self) -> Vec<SwaggerSource> {
+        let mut sources: Vec<SwaggerSource> = self
+            .swagger_file
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(SwaggerSource::parse)
+            .collect();
+
+        if sources.len() > 1 {
+            for source in &mut sources {
+                if source.tool_prefix.is_none() {
+                    source.tool_prefix = Some(source.default_prefix());
+                }
+            }
+        }
+
+        sources
+    }
+
+    /// Copy of the configuration with secrets replaced, safe to print or log
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+
+        for value in config.endpoint.auth_config.values_mut() {
+            *value = REDACTED.to_string();
+        }
+        if let Some(secret) = &mut config.endpoint.hmac_secret {
+            *secret = REDACTED.to_string();
+        }
+        for (name, value) in config.endpoint.headers.iter_mut() {
+            if is_sensitive_header(name) {
+                *value = REDACTED.to_string();
+            }
+        }
+        if let Some(oauth) = &mut config.oauth {
+            oauth.client_secret = REDACTED.to_string();
+            if let Some(passphrase) = oauth.token_cache_passphrase.as_mut() {
+                *passphrase = REDACTED.to_string();
+            }
+            if let Some(extra) = &mut oauth.extra_params {
+                for value in extra.values_mut() {
+                    *value = REDACTED.to_string();
+                }
+            }
+        }
+
+        config
+    }
+
     pub fn from_args(
         swagger_file: String,
         adjustments_file: Option<String>,
@@ -231,8 +586,8 @@ impl AppConfig {
         let mut config = Self::load()?;
 
         // Override with CLI args if provided
-        if let Some(swagger_file) = matches.get_one::<String>("swagger-file") {
-            config.swagger_file = swagger_file.clone();
+        if let Some(swagger_files) = matches.get_many::<String>("swagger-file") {
+            config.swagger_file = swagger_files.cloned().collect::<Vec<_>>().join(",");
         }
 
         if let Some(adjustments_file) = matches.get_one::<String>("adjustments-file") {
@@ -1,2 +1,4 @@
+pub mod profile;
+pub mod sanitizer;
 pub mod transcript;
 pub mod validator;
@@ -0,0 +1,308 @@
+// src/internal/gemini/profile.rs
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::internal::gemini::sanitizer::GeminiSanitizer;
+use crate::internal::gemini::validator::{GeminiCompatibilityReport, ValidationResult};
+
+/// How a client accepts a schema saying a value may be null
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullableEncoding {
+    /// Any JSON Schema form, including `"type": ["string", "null"]`
+    Any,
+    /// A single `type` plus OpenAPI's `"nullable": true`; type arrays are rejected
+    Keyword,
+}
+
+/// Schema restrictions of one MCP client. Every rule is optional, so a profile only
+/// lists what its client actually enforces.
+#[derive(Debug, Clone)]
+pub struct ProfileRules {
+    pub max_name_length: Option<usize>,
+    /// Regex tool names must match
+    pub name_pattern: Option<&'static str>,
+    /// Schema keywords the client rejects anywhere in an input schema
+    pub unsupported_keywords: &'static [&'static str],
+    /// Unsupported keywords the sanitizer can drop without losing declared fields
+    pub strippable_keywords: &'static [&'static str],
+    pub nullable: NullableEncoding,
+    /// Output schemas, when present, must have `"type": "object"`
+    pub object_output_schema: bool,
+    /// Descriptions longer than this many characters are reported and truncated
+    pub max_description_length: Option<usize>,
+}
+
+/// Options of one validation run
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Report over-long descriptions as errors instead of warnings
+    pub strict: bool,
+    /// Replaces the profile's description length limit
+    pub max_description_length: Option<usize>,
+}
+
+/// Rules a client imposes on tools, with validation and sanitization derived from them
+pub trait CompatibilityProfile: Send + Sync {
+    /// Name the profile is selected by, e.g. with `--profile`
+    fn name(&self) -> &str;
+
+    fn rules(&self) -> &ProfileRules;
+
+    /// Check a tool against the profile's rules
+    fn validate_tool(
+        &self,
+        tool: &rmcp::model::Tool,
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        let rules = self.rules();
+        let mut result = ValidationResult {
+            tool_name: tool.name.to_string(),
+            is_valid: true,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        if let Some(max) = rules.max_name_length {
+            if tool.name.len() > max {
+                result.errors.push(format!(
+                    "Tool name exceeds {} characters ({} chars): '{}'",
+                    max,
+                    tool.name.len(),
+                    tool.name
+                ));
+            }
+        }
+        if let Some(pattern) = rules.name_pattern {
+            if !Regex::new(pattern).unwrap().is_match(&tool.name) {
+                result.errors.push(format!(
+                    "Tool name contains invalid characters: '{}'",
+                    tool.name
+                ));
+            }
+        }
+
+        validate_schema_at(rules, &tool.input_schema, "", &mut result.errors);
+
+        if rules.object_output_schema {
+            if let Some(type_val) = tool.output_schema.as_ref().and_then(|s| s.get("type")) {
+                if type_val != "object" {
+                    result.errors.push(format!(
+                        "Output schema type must be 'object', found '{}'",
+                        type_val
+                    ));
+                }
+            }
+        }
+
+        let max_description_length = options
+            .max_description_length
+            .or(rules.max_description_length);
+        let description_length = tool.description.as_deref().map_or(0, |d| d.chars().count());
+        if let Some(max) = max_description_length.filter(|max| description_length > *max) {
+            let message = format!(
+                "Tool description exceeds {} characters ({} chars)",
+                max, description_length
+            );
+            if options.strict {
+                result.errors.push(message);
+            } else {
+                result.warnings.push(message);
+            }
+        }
+
+        result.is_valid = result.errors.is_empty();
+        result
+    }
+
+    /// Check every tool and summarize
+    fn validate_all(
+        &self,
+        tools: &[rmcp::model::Tool],
+        options: &ValidationOptions,
+    ) -> GeminiCompatibilityReport {
+        let results: Vec<_> = tools
+            .iter()
+            .map(|tool| self.validate_tool(tool, options))
+            .collect();
+        let valid_tools = results.iter().filter(|r| r.is_valid).count();
+
+        GeminiCompatibilityReport {
+            profile: self.name().to_string(),
+            total_tools: results.len(),
+            valid_tools,
+            invalid_tools: results.len() - valid_tools,
+            results,
+        }
+    }
+
+    /// Rewrite a schema and its nested schemas to what the client accepts, where that
+    /// loses nothing: strippable keywords are dropped and nullable types re-encoded
+    fn sanitize_schema(&self, schema: &mut Map<String, Value>) {
+        let rules = self.rules();
+        for keyword in rules.strippable_keywords {
+            schema.remove(*keyword);
+        }
+        if rules.nullable == NullableEncoding::Keyword {
+            encode_nullable_keyword(schema);
+        }
+
+        if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+            for prop_schema in properties.values_mut() {
+                if let Value::Object(prop_obj) = prop_schema {
+                    self.sanitize_schema(prop_obj);
+                }
+            }
+        }
+        match schema.get_mut("items") {
+            Some(Value::Object(items)) => self.sanitize_schema(items),
+            // Tuple form: one schema per position
+            Some(Value::Array(items)) => {
+                for item in items {
+                    if let Value::Object(item) = item {
+                        self.sanitize_schema(item);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Shorten a description to the profile's length limit, if it has one
+    fn sanitize_description(&self, description: &str) -> String {
+        match self.rules().max_description_length {
+            Some(max) => GeminiSanitizer::truncate_description(description, max),
+            None => description.to_string(),
+        }
+    }
+}
+
+/// A profile defined entirely by its rules
+#[derive(Debug, Clone)]
+pub struct RuleProfile {
+    pub name: &'static str,
+    pub rules: ProfileRules,
+}
+
+impl CompatibilityProfile for RuleProfile {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn rules(&self) -> &ProfileRules {
+        &self.rules
+    }
+}
+
+/// No restrictions: tools are served exactly as generated
+pub const PERMISSIVE: RuleProfile = RuleProfile {
+    name: "default",
+    rules: ProfileRules {
+        max_name_length: None,
+        name_pattern: None,
+        unsupported_keywords: &[],
+        strippable_keywords: &[],
+        nullable: NullableEncoding::Any,
+        object_output_schema: false,
+        max_description_length: None,
+    },
+};
+
+/// Gemini CLI
+pub const GEMINI: RuleProfile = RuleProfile {
+    name: "gemini",
+    rules: ProfileRules {
+        max_name_length: Some(64),
+        name_pattern: Some(r"^[a-zA-Z_][a-zA-Z0-9_]*$"),
+        unsupported_keywords: &["oneOf", "anyOf", "allOf", "$ref", "additionalProperties"],
+        strippable_keywords: &["additionalProperties"],
+        nullable: NullableEncoding::Keyword,
+        object_output_schema: true,
+        max_description_length: Some(1024),
+    },
+};
+
+/// Every profile that can be selected by name
+pub const PROFILES: &[&RuleProfile] = &[&PERMISSIVE, &GEMINI];
+
+/// Names of the selectable profiles
+pub fn profile_names() -> Vec<&'static str> {
+    PROFILES.iter().map(|profile| profile.name).collect()
+}
+
+pub fn profile_by_name(name: &str) -> Option<&'static dyn CompatibilityProfile> {
+    PROFILES
+        .iter()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))
+        .map(|profile| *profile as &'static dyn CompatibilityProfile)
+}
+
+/// Check a schema found at `path` (e.g. `tasks[].status`; empty for the root), then its
+/// properties and array items
+fn validate_schema_at(
+    rules: &ProfileRules,
+    schema: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let mut report = |message: String| {
+        errors.push(if path.is_empty() {
+            message
+        } else {
+            format!("In property '{}': {}", path, message)
+        })
+    };
+    for keyword in rules.unsupported_keywords {
+        if schema.contains_key(*keyword) {
+            report(format!("Schema contains unsupported keyword '{}'", keyword));
+        }
+    }
+    if rules.nullable == NullableEncoding::Keyword
+        && schema.get("type").is_some_and(Value::is_array)
+    {
+        report("Schema type is an array; use a single type with 'nullable: true'".to_string());
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (prop_name, prop_schema) in properties {
+            if let Some(prop_obj) = prop_schema.as_object() {
+                let prop_path = if path.is_empty() {
+                    prop_name.clone()
+                } else {
+                    format!("{}.{}", path, prop_name)
+                };
+                validate_schema_at(rules, prop_obj, &prop_path, errors);
+            }
+        }
+    }
+    match schema.get("items") {
+        Some(Value::Object(items)) => {
+            validate_schema_at(rules, items, &format!("{}[]", path), errors);
+        }
+        // Tuple form: one schema per position
+        Some(Value::Array(items)) => {
+            for (index, item) in items.iter().enumerate() {
+                if let Some(item) = item.as_object() {
+                    validate_schema_at(rules, item, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Turn `"type": [T, "null"]` into `"type": T, "nullable": true`; arrays of several
+/// non-null types have no such encoding and are left for the validator to report
+fn encode_nullable_keyword(schema: &mut Map<String, Value>) {
+    let Some(Value::Array(types)) = schema.get("type") else {
+        return;
+    };
+    let nullable = types.iter().any(|t| t == "null");
+    let non_null: Vec<Value> = types.iter().filter(|t| *t != "null").cloned().collect();
+    if let [single] = non_null.as_slice() {
+        schema.insert("type".to_string(), single.clone());
+        if nullable {
+            schema.insert("nullable".to_string(), Value::Bool(true));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use serde_json::{Map, Value};
+
+use crate::internal::gemini::profile::{CompatibilityProfile, GEMINI};
+
+/// Appended to truncated descriptions
+const ELLIPSIS: &str = "...";
+
+/// Rewrites schemas so they pass Gemini CLI validation
+pub struct GeminiSanitizer;
+
+impl GeminiSanitizer {
+    /// Remove Gemini-incompatible keywords from a schema and all nested schemas
+    pub fn sanitize_schema(schema: &mut Map<String, Value>) {
+        GEMINI.sanitize_schema(schema);
+    }
+
+    /// Shorten a description to at most `max_len` characters, ellipsis included, cutting at
+    /// the last word boundary that fits; a single over-long word is cut mid-word
+    pub fn truncate_description(description: &str, max_len: usize) -> String {
+        if description.chars().count() <= max_len {
+            return description.to_string();
+        }
+
+        let budget = max_len.saturating_sub(ELLIPSIS.len());
+        let cut: String = description.chars().take(budget).collect();
+        // A cut right before whitespace already ends on a word boundary
+        let ends_on_boundary = description
+            .chars()
+            .nth(budget)
+            .is_some_and(char::is_whitespace);
+        let kept = match cut.rfind(char::is_whitespace) {
+            Some(_) if ends_on_boundary => cut.as_str(),
+            Some(boundary) => &cut[..boundary],
+            None => cut.as_str(),
+        };
+        format!("{}{}", kept.trim_end(), ELLIPSIS)
+    }
+}
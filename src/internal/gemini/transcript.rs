@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::internal::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
+use crate::internal::server::tool::audit::redact_value;
 
 /// Parses and generates Gemini-style MCP transcripts
 pub struct TranscriptParser;
@@ -16,6 +17,12 @@ pub enum Message {
 pub struct TranscriptEntry {
     pub direction: Direction,
     pub timestamp: Option<String>,
+    /// Session the message belongs to, so transcripts of concurrent clients can be told apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// MCP protocol version negotiated by the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
     #[serde(flatten)]
     pub message: Message,
 }
@@ -50,24 +57,132 @@ impl TranscriptParser {
             .collect()
     }
 
-    /// Generate transcript entries from a message exchange
+    /// Generate transcript entries from a message exchange outside any session.
+    /// Credential-like fields (tokens, passwords, `Authorization`, ...) are redacted.
     pub fn record_exchange(
         request: &JsonRpcRequest,
         response: &JsonRpcResponse,
     ) -> Vec<TranscriptEntry> {
-        vec![
-            TranscriptEntry {
-                direction: Direction::ClientToServer,
-                timestamp: Some(chrono::Utc::now().to_rfc3339()),
-                message: Message::Request(request.clone()),
-            },
-            TranscriptEntry {
-                direction: Direction::ServerToClient,
-                timestamp: Some(chrono::Utc::now().to_rfc3339()),
-                message: Message::Response(response.clone()),
-            },
-        ]
+        exchange_entries(request, response, None, None, &[])
     }
+
+    /// Split entries into one transcript per session, in order of each session's first
+    /// entry; entries without a session id form a single session of their own
+    pub fn group_by_session(entries: Vec<TranscriptEntry>) -> Vec<SessionTranscript> {
+        let mut sessions: Vec<SessionTranscript> = Vec::new();
+        for entry in entries {
+            match sessions
+                .iter_mut()
+                .find(|session| session.session_id == entry.session_id)
+            {
+                Some(session) => session.entries.push(entry),
+                None => sessions.push(SessionTranscript {
+                    session_id: entry.session_id.clone(),
+                    entries: vec![entry],
+                }),
+            }
+        }
+        sessions
+    }
+}
+
+/// Entries of one session, in recorded order
+#[derive(Debug, Clone)]
+pub struct SessionTranscript {
+    pub session_id: Option<String>,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Records the exchanges of one live client session, tagging every entry with the session
+/// id and the protocol version negotiated by its `initialize` exchange
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptRecorder {
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+    redacted_keys: Vec<String>,
+}
+
+impl TranscriptRecorder {
+    pub fn new(session_id: Option<String>) -> Self {
+        Self {
+            session_id,
+            ..Default::default()
+        }
+    }
+
+    /// Also redact the values of these keys, at any depth of params and results,
+    /// on top of the credential-like fields that are always redacted
+    pub fn with_redacted_keys(mut self, keys: Vec<String>) -> Self {
+        self.redacted_keys = keys;
+        self
+    }
+
+    /// Protocol version of the session, once `initialize` has been recorded
+    pub fn protocol_version(&self) -> Option<&str> {
+        self.protocol_version.as_deref()
+    }
+
+    /// Generate transcript entries from a message exchange of this session
+    pub fn record_exchange(
+        &mut self,
+        request: &JsonRpcRequest,
+        response: &JsonRpcResponse,
+    ) -> Vec<TranscriptEntry> {
+        if request.method == "initialize" {
+            // The server's answer is the negotiated version; the request only proposes one
+            let version = |value: Option<&serde_json::Value>| {
+                value?.get("protocolVersion")?.as_str().map(str::to_string)
+            };
+            self.protocol_version = version(response.result.as_ref())
+                .or_else(|| version(request.params.as_ref()))
+                .or(self.protocol_version.take());
+        }
+        exchange_entries(
+            request,
+            response,
+            self.session_id.clone(),
+            self.protocol_version.clone(),
+            &self.redacted_keys,
+        )
+    }
+}
+
+/// Entries for an exchange, with sensitive values redacted before anything is serialized
+fn exchange_entries(
+    request: &JsonRpcRequest,
+    response: &JsonRpcResponse,
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+    redacted_keys: &[String],
+) -> Vec<TranscriptEntry> {
+    let redact =
+        |value: &Option<serde_json::Value>| value.as_ref().map(|v| redact_value(v, redacted_keys));
+    let request = JsonRpcRequest {
+        params: redact(&request.params),
+        ..request.clone()
+    };
+    let mut response = response.clone();
+    response.result = redact(&response.result);
+    if let Some(error) = response.error.as_mut() {
+        error.data = redact(&error.data);
+    }
+
+    vec![
+        TranscriptEntry {
+            direction: Direction::ClientToServer,
+            timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            session_id: session_id.clone(),
+            protocol_version: protocol_version.clone(),
+            message: Message::Request(request),
+        },
+        TranscriptEntry {
+            direction: Direction::ServerToClient,
+            timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            session_id,
+            protocol_version,
+            message: Message::Response(response),
+        },
+    ]
 }
 
 #[derive(Debug, thiserror::Error)]
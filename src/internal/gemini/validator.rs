@@ -1,7 +1,32 @@
-/// Validates tools and schemas for Gemini CLI compatibility
+use serde::Serialize;
+
+use crate::internal::gemini::profile::{CompatibilityProfile, ValidationOptions, GEMINI};
+
+/// Longest tool description Gemini accepts without a finding unless configured otherwise
+pub const DEFAULT_MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Validates tools and schemas for Gemini CLI compatibility, using the `GEMINI` profile
 pub struct GeminiValidator;
 
+/// Limits applied by the validator
 #[derive(Debug, Clone)]
+pub struct GeminiValidatorOptions {
+    /// Description length in characters above which a tool is reported
+    pub max_description_length: usize,
+    /// Report over-long descriptions as errors instead of warnings
+    pub strict: bool,
+}
+
+impl Default for GeminiValidatorOptions {
+    fn default() -> Self {
+        Self {
+            max_description_length: DEFAULT_MAX_DESCRIPTION_LENGTH,
+            strict: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationResult {
     pub tool_name: String,
     pub is_valid: bool,
@@ -9,120 +34,78 @@ pub struct ValidationResult {
     pub errors: Vec<String>,
 }
 
-impl GeminiValidator {
-    /// Validate a tool for Gemini compatibility
-    pub fn validate_tool(tool: &rmcp::model::Tool) -> ValidationResult {
-        let mut result = ValidationResult {
-            tool_name: tool.name.to_string(),
-            is_valid: true,
-            warnings: Vec::new(),
-            errors: Vec::new(),
-        };
-
-        // Rule 1: Tool name must be <= 64 characters
-        if tool.name.len() > 64 {
-            result.errors.push(format!(
-                "Tool name exceeds 64 characters ({} chars): '{}'",
-                tool.name.len(),
-                tool.name
-            ));
-            result.is_valid = false;
+impl From<&GeminiValidatorOptions> for ValidationOptions {
+    fn from(options: &GeminiValidatorOptions) -> Self {
+        Self {
+            strict: options.strict,
+            max_description_length: Some(options.max_description_length),
         }
-
-        // Rule 2: Tool name must match pattern [a-zA-Z_][a-zA-Z0-9_]*
-        let name_regex = regex::Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_]*$").unwrap();
-        if !name_regex.is_match(&tool.name) {
-            result.errors.push(format!(
-                "Tool name contains invalid characters: '{}'",
-                tool.name
-            ));
-            result.is_valid = false;
-        }
-
-        // Rule 3: Check JSON schema for unsupported constructs
-        let schema_issues = Self::validate_schema(&tool.input_schema);
-        for issue in schema_issues {
-            if issue.is_error {
-                result.errors.push(issue.message);
-                result.is_valid = false;
-            } else {
-                result.warnings.push(issue.message);
-            }
-        }
-
-        // Rule 4: Output schema must be an Object (fixes your specific CLI error)
-        if let Some(schema) = &tool.output_schema {
-            if let Some(type_val) = schema.get("type") {
-                if type_val != "object" {
-                    result.errors.push(format!(
-                        "Output schema type must be 'object', found '{}'",
-                        type_val
-                    ));
-                    result.is_valid = false;
-                }
-            }
-        }
-
-        result
     }
+}
 
-    /// Validate JSON Schema for Gemini compatibility
-    fn validate_schema(schema: &serde_json::Map<String, serde_json::Value>) -> Vec<SchemaIssue> {
-        let mut issues = Vec::new();
-
-        // Check for unsupported keywords
-        let unsupported = ["oneOf", "anyOf", "allOf", "$ref", "additionalProperties"];
-        for keyword in unsupported {
-            if schema.contains_key(keyword) {
-                issues.push(SchemaIssue {
-                    message: format!("Schema contains unsupported keyword '{}'", keyword),
-                    is_error: true,
-                });
-            }
-        }
-
-        // Recursively check nested schemas
-        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
-            for (prop_name, prop_schema) in properties {
-                if let Some(prop_obj) = prop_schema.as_object() {
-                    let nested_issues = Self::validate_schema(prop_obj);
-                    for mut issue in nested_issues {
-                        issue.message = format!("In property '{}': {}", prop_name, issue.message);
-                        issues.push(issue);
-                    }
-                }
-            }
-        }
+impl GeminiValidator {
+    /// Validate a tool for Gemini compatibility with the default options
+    pub fn validate_tool(tool: &rmcp::model::Tool) -> ValidationResult {
+        Self::validate_tool_with(tool, &GeminiValidatorOptions::default())
+    }
 
-        issues
+    /// Validate a tool for Gemini compatibility
+    pub fn validate_tool_with(
+        tool: &rmcp::model::Tool,
+        options: &GeminiValidatorOptions,
+    ) -> ValidationResult {
+        GEMINI.validate_tool(tool, &options.into())
     }
 
-    /// Validate all tools and return report
+    /// Validate all tools with the default options and return report
     pub fn validate_all(tools: &[rmcp::model::Tool]) -> GeminiCompatibilityReport {
-        let results: Vec<_> = tools.iter().map(Self::validate_tool).collect();
-
-        let valid_count = results.iter().filter(|r| r.is_valid).count();
-        let invalid_count = results.len() - valid_count;
-
-        GeminiCompatibilityReport {
-            total_tools: results.len(),
-            valid_tools: valid_count,
-            invalid_tools: invalid_count,
-            results,
-        }
+        Self::validate_all_with(tools, &GeminiValidatorOptions::default())
     }
-}
 
-#[derive(Debug)]
-struct SchemaIssue {
-    message: String,
-    is_error: bool,
+    /// Validate all tools and return report
+    pub fn validate_all_with(
+        tools: &[rmcp::model::Tool],
+        options: &GeminiValidatorOptions,
+    ) -> GeminiCompatibilityReport {
+        GEMINI.validate_all(tools, &options.into())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GeminiCompatibilityReport {
+    /// Name of the profile the tools were checked against
+    pub profile: String,
     pub total_tools: usize,
     pub valid_tools: usize,
     pub invalid_tools: usize,
     pub results: Vec<ValidationResult>,
 }
+
+impl GeminiCompatibilityReport {
+    pub fn is_ok(&self) -> bool {
+        self.invalid_tools == 0
+    }
+
+    /// The report as pretty-printed JSON, for CI to parse
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Plain-text report listing the errors and warnings of each tool, then the totals
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for result in &self.results {
+            for error in &result.errors {
+                report.push_str(&format!("ERROR {}: {}\n", result.tool_name, error));
+            }
+            for warning in &result.warnings {
+                report.push_str(&format!("WARN {}: {}\n", result.tool_name, warning));
+            }
+        }
+        report.push_str(&format!(
+            "{}: {} of {} tools compatible, {} incompatible\n",
+            self.profile, self.valid_tools, self.total_tools, self.invalid_tools
+        ));
+        report
+    }
+}
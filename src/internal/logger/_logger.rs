@@ -12,7 +12,8 @@ use tracing_subscriber::{
 
 use crate::internal::config::LoggingConfig;
 
-/// Initialize the global logger with the given configuration
+/// Initialize the global logger with the given configuration.
+/// Console output goes to stderr: in stdio mode stdout carries only JSON-RPC messages.
 pub fn init_logger(cfg: &LoggingConfig) -> anyhow::Result<()> {
     // Build filter using EnvFilter (no feature flags needed)
     let filter =
@@ -34,6 +35,7 @@ pub fn init_logger(cfg: &LoggingConfig) -> anyhow::Result<()> {
                 .with(filter)
                 .with(
                     fmt::layer()
+                        .with_writer(io::stderr)
                         .with_ansi(cfg.color)
                         .with_level(true)
                         .with_target(true)
@@ -75,6 +77,7 @@ pub fn init_logger(cfg: &LoggingConfig) -> anyhow::Result<()> {
                 .with(filter)
                 .with(
                     fmt::layer()
+                        .with_writer(io::stderr)
                         .with_ansi(cfg.color)
                         .with_level(true)
                         .with_target(true)
@@ -1,3 +1,4 @@
 pub mod processor;
 pub mod protocol;
 pub mod registry;
+pub mod session;
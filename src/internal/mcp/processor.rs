@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use crate::internal::{
     mcp::{
-        protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, McpMethod},
+        protocol::{
+            JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, McpMethod,
+            ToolsDescribeParams, ToolsListParams,
+        },
         registry::ToolRegistry,
     },
-    server::_server::Server,
+    server::{_server::Server, tool::ToolCallError},
 };
 use rmcp::model::{CallToolRequestParam, ListToolsResult, ServerInfo};
 use rmcp::ServerHandler;
@@ -25,6 +28,11 @@ impl McpProcessor {
         }
     }
 
+    /// Receiver notified when the tool set changes, to tell clients their list is stale
+    pub fn tool_changes(&self) -> tokio::sync::watch::Receiver<u64> {
+        self.tool_registry.subscribe()
+    }
+
     /// Process a raw JSON-RPC request and return a response
     /// This is the CORE testable unit
     pub async fn process_request(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
@@ -48,7 +56,10 @@ impl McpProcessor {
                 }
             }
             McpMethod::ToolsList => {
-                let tools = self.tool_registry.list_metadata();
+                let params = ToolsListParams::from_params(request.params.as_ref());
+                let tools = self
+                    .tool_registry
+                    .list_metadata_matching(params.filter.as_deref());
                 tracing::debug!("Tools listed: {:?}", tools); // Add debug print
                 let result = ListToolsResult {
                     tools,
@@ -80,16 +91,26 @@ impl McpProcessor {
                                 result: Some(serde_json::to_value(result).unwrap()),
                                 error: None,
                             },
-                            Err(e) => JsonRpcResponse {
-                                jsonrpc: "2.0".to_string(),
-                                id: request.id.clone(),
-                                result: None,
-                                error: Some(JsonRpcError {
-                                    code: -32000,
-                                    message: e.to_string(),
-                                    data: None,
-                                }),
-                            },
+                            Err(e) => {
+                                let error = match e.downcast_ref::<ToolCallError>() {
+                                    Some(tool_error) => JsonRpcError {
+                                        code: tool_error.code(),
+                                        message: tool_error.to_string(),
+                                        data: Some(tool_error.data()),
+                                    },
+                                    None => JsonRpcError {
+                                        code: -32000,
+                                        message: e.to_string(),
+                                        data: None,
+                                    },
+                                };
+                                JsonRpcResponse {
+                                    jsonrpc: "2.0".to_string(),
+                                    id: request.id.clone(),
+                                    result: None,
+                                    error: Some(error),
+                                }
+                            }
                         }
                     } else {
                         JsonRpcResponse {
@@ -116,6 +137,39 @@ impl McpProcessor {
                     }
                 }
             }
+            McpMethod::ToolsDescribe => {
+                let params: Result<ToolsDescribeParams, _> =
+                    serde_json::from_value(request.params.clone().unwrap_or_default());
+                let (result, error) = match params {
+                    Ok(params) => match self.tool_registry.describe(&params.name) {
+                        Some(description) => {
+                            (Some(serde_json::to_value(description).unwrap()), None)
+                        }
+                        None => (
+                            None,
+                            Some(JsonRpcError {
+                                code: -32601,
+                                message: "Tool not found".to_string(),
+                                data: None,
+                            }),
+                        ),
+                    },
+                    Err(_) => (
+                        None,
+                        Some(JsonRpcError {
+                            code: -32602,
+                            message: "Invalid params".to_string(),
+                            data: None,
+                        }),
+                    ),
+                };
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id.clone(),
+                    result,
+                    error,
+                }
+            }
             McpMethod::Ping => JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id: request.id.clone(),
@@ -140,6 +194,11 @@ impl McpProcessor {
         serde_json::from_slice(input)
     }
 
+    /// Serialize a notification to bytes
+    pub fn serialize_notification(notification: &JsonRpcNotification) -> Vec<u8> {
+        serde_json::to_vec(notification).unwrap_or_default()
+    }
+
     /// Serialize response to bytes
     pub fn serialize_response(response: &JsonRpcResponse) -> Vec<u8> {
         serde_json::to_vec(response).unwrap_or_default()
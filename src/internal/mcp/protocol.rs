@@ -1,3 +1,4 @@
+use rmcp::model::Tool;
 use serde::{Deserialize, Serialize};
 
 /// Raw JSON-RPC request envelope
@@ -20,6 +21,28 @@ pub struct JsonRpcResponse {
     pub error: Option<JsonRpcError>,
 }
 
+/// Server-initiated JSON-RPC notification: no id, so no response is expected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: &str) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: None,
+        }
+    }
+}
+
+/// Sent to clients when the set of tools changed after they listed it
+pub const TOOLS_LIST_CHANGED: &str = "notifications/tools/list_changed";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcError {
     pub code: i32,
@@ -28,6 +51,48 @@ pub struct JsonRpcError {
     pub data: Option<serde_json::Value>,
 }
 
+/// Params accepted by `tools/list`: the pagination cursor plus an optional text filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolsListParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Case-insensitive substring matched against tool names and descriptions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+impl ToolsListParams {
+    /// Read the params of a `tools/list` request; missing or malformed params mean no filter
+    pub fn from_params(params: Option<&serde_json::Value>) -> Self {
+        params
+            .and_then(|p| serde_json::from_value(p.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Params of the non-standard `tools/describe` method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolsDescribeParams {
+    pub name: String,
+}
+
+/// Result of `tools/describe`: the full tool and the upstream route it calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDescription {
+    pub tool: Tool,
+    /// `None` for built-in tools that call no upstream route
+    pub route: Option<ToolRoute>,
+}
+
+/// HTTP method, path and base URL behind a tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolRoute {
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
 /// MCP-specific method types
 #[derive(Debug, Clone, PartialEq)]
 pub enum McpMethod {
@@ -35,6 +100,8 @@ pub enum McpMethod {
     Initialized, // notification
     ToolsList,
     ToolsCall,
+    /// Non-standard: full details of one tool
+    ToolsDescribe,
     Ping,
     Unknown(String),
 }
@@ -46,6 +113,7 @@ impl From<&str> for McpMethod {
             "notifications/initialized" => McpMethod::Initialized,
             "tools/list" => McpMethod::ToolsList,
             "tools/call" => McpMethod::ToolsCall,
+            "tools/describe" => McpMethod::ToolsDescribe,
             "ping" => McpMethod::Ping,
             other => McpMethod::Unknown(other.to_string()),
         }
@@ -1,17 +1,24 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
 
+use crate::internal::mcp::protocol::{ToolDescription, ToolRoute};
+use crate::internal::requester::RouteConfig;
 use crate::internal::server::tool::handler::ToolExecutor;
 use rmcp::model::Tool;
 
 pub struct RegisteredTool {
     pub metadata: Tool,
     pub executor: ToolExecutor,
+    /// Upstream route the tool calls; `None` for built-in tools
+    pub route: Option<RouteConfig>,
 }
 
 /// Thread-safe tool registry that can be shared across transports
 pub struct ToolRegistry {
     tools: RwLock<HashMap<String, Arc<RegisteredTool>>>,
+    /// Bumped on every change to the tool set
+    changes: watch::Sender<u64>,
 }
 
 impl Default for ToolRegistry {
@@ -24,14 +31,43 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: RwLock::new(HashMap::new()),
+            changes: watch::channel(0).0,
         }
     }
     pub fn register(&self, name: String, tool: RegisteredTool) {
         self.tools.write().unwrap().insert(name, Arc::new(tool));
+        self.changes.send_modify(|version| *version += 1);
+    }
+    /// Remove a tool, returning whether it was registered
+    pub fn remove(&self, name: &str) -> bool {
+        let removed = self.tools.write().unwrap().remove(name).is_some();
+        if removed {
+            self.changes.send_modify(|version| *version += 1);
+        }
+        removed
+    }
+    /// Receiver notified whenever a tool is registered or removed after subscribing
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
     }
     pub fn get(&self, name: &str) -> Option<Arc<RegisteredTool>> {
         self.tools.read().unwrap().get(name).cloned()
     }
+    /// Route behind a registered tool, to see which method, path and base URL it calls
+    pub fn get_route(&self, name: &str) -> Option<RouteConfig> {
+        self.get(name).and_then(|tool| tool.route.clone())
+    }
+    /// Full metadata of a tool together with the route it calls
+    pub fn describe(&self, name: &str) -> Option<ToolDescription> {
+        self.get(name).map(|tool| ToolDescription {
+            tool: tool.metadata.clone(),
+            route: tool.route.as_ref().map(|route| ToolRoute {
+                method: route.method.clone(),
+                path: route.path.clone(),
+                base_url: route.base_url.clone(),
+            }),
+        })
+    }
     pub fn list_metadata(&self) -> Vec<Tool> {
         self.tools
             .read()
@@ -40,6 +76,34 @@ impl ToolRegistry {
             .map(|tool| tool.metadata.clone())
             .collect()
     }
+    /// Metadata of the tools whose name or description contains the filter, ignoring case.
+    /// No filter, or a blank one, lists every tool.
+    pub fn list_metadata_matching(&self, filter: Option<&str>) -> Vec<Tool> {
+        let needle = match filter.map(str::trim).filter(|f| !f.is_empty()) {
+            Some(f) => f.to_lowercase(),
+            None => return self.list_metadata(),
+        };
+        self.tools
+            .read()
+            .unwrap()
+            .values()
+            .filter(|tool| {
+                tool.metadata.name.to_lowercase().contains(&needle)
+                    || tool
+                        .metadata
+                        .description
+                        .as_deref()
+                        .is_some_and(|d| d.to_lowercase().contains(&needle))
+            })
+            .map(|tool| tool.metadata.clone())
+            .collect()
+    }
+    /// Names of the registered tools, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tools.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
     pub fn count(&self) -> usize {
         self.tools.read().unwrap().len()
     }
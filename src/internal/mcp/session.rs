@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use rmcp::model::{ListToolsResult, ServerInfo, Tool};
+use serde_json::Value;
+
+use crate::internal::{
+    mcp::{
+        processor::McpProcessor,
+        protocol::{JsonRpcRequest, JsonRpcResponse, ToolDescription},
+    },
+    server::_server::Server,
+};
+
+/// Errors returned by an in-process MCP session
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("JSON-RPC error {code}: {message}")]
+    Rpc {
+        code: i32,
+        message: String,
+        data: Option<Value>,
+    },
+    #[error("Unexpected response: {0}")]
+    InvalidResponse(String),
+}
+
+/// In-process MCP client that drives a processor without any transport.
+/// Every call goes through `process_request`, so behavior matches the wire protocol.
+pub struct McpSession {
+    processor: McpProcessor,
+    next_id: AtomicI64,
+}
+
+impl McpSession {
+    pub fn new(processor: McpProcessor) -> Self {
+        Self {
+            processor,
+            next_id: AtomicI64::new(1),
+        }
+    }
+
+    /// Open a session against a server whose tools are already set up
+    pub async fn from_server(server: &Server) -> Self {
+        let registry = server.get_tool_registry().await;
+        Self::new(McpProcessor::new(server, registry))
+    }
+
+    /// Perform the initialize handshake and return the server info
+    pub async fn initialize(&self) -> Result<ServerInfo, SessionError> {
+        let params = serde_json::json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "yas-mcp-session", "version": env!("CARGO_PKG_VERSION") }
+        });
+        let result = self.request("initialize", Some(params)).await?;
+        self.notify("notifications/initialized").await;
+        Self::decode(result)
+    }
+
+    /// List the tools exposed by the server
+    pub async fn list_tools(&self) -> Result<Vec<Tool>, SessionError> {
+        let result = self.request("tools/list", None).await?;
+        Self::decode::<ListToolsResult>(result).map(|r| r.tools)
+    }
+
+    /// List the tools whose name or description contains `filter`, ignoring case
+    pub async fn search_tools(&self, filter: &str) -> Result<Vec<Tool>, SessionError> {
+        let params = serde_json::json!({ "filter": filter });
+        let result = self.request("tools/list", Some(params)).await?;
+        Self::decode::<ListToolsResult>(result).map(|r| r.tools)
+    }
+
+    /// Call a tool and return its raw `CallToolResult` JSON
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<Value, SessionError> {
+        let params = serde_json::json!({ "name": name, "arguments": arguments });
+        self.request("tools/call", Some(params)).await
+    }
+
+    /// Full details of one tool via the non-standard `tools/describe` method
+    pub async fn describe_tool(&self, name: &str) -> Result<ToolDescription, SessionError> {
+        let params = serde_json::json!({ "name": name });
+        let result = self.request("tools/describe", Some(params)).await?;
+        Self::decode(result)
+    }
+
+    async fn request(&self, method: &str, params: Option<Value>) -> Result<Value, SessionError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::from(id)),
+            method: method.to_string(),
+            params,
+        };
+
+        let response = self.processor.process_request(&request).await;
+        Self::into_result(response)
+    }
+
+    async fn notify(&self, method: &str) {
+        let notification = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            method: method.to_string(),
+            params: None,
+        };
+        self.processor.process_request(&notification).await;
+    }
+
+    fn into_result(response: JsonRpcResponse) -> Result<Value, SessionError> {
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(SessionError::Rpc {
+                code: error.code,
+                message: error.message,
+                data: error.data,
+            }),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(SessionError::InvalidResponse(
+                "response has neither result nor error".to_string(),
+            )),
+        }
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, SessionError> {
+        serde_json::from_value(value).map_err(|e| SessionError::InvalidResponse(e.to_string()))
+    }
+}
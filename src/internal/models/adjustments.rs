@@ -1,4 +1,6 @@
+use crate::internal::requester::{JsonPath, StatusRange};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteFieldUpdate {
@@ -19,10 +21,73 @@ pub struct RouteSelection {
     pub methods: Vec<String>,
 }
 
+/// Annotation hints forced onto a tool; unset hints keep the value inferred from the method
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolAnnotationOverrides {
+    pub read_only: Option<bool>,
+    pub destructive: Option<bool>,
+    pub idempotent: Option<bool>,
+    pub open_world: Option<bool>,
+}
+
+/// Per-tool overrides, keyed by the generated tool name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolOverride {
+    pub name: String,
+    /// Display title; free text, unlike the tool name
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub annotations: Option<ToolAnnotationOverrides>,
+    /// Content-Type of the request body, replacing the media type from the spec
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Argument names exposed to clients, keyed by the name the upstream expects
+    /// (e.g. `per_page: pageSize`); applies to path, query, header and body fields
+    #[serde(default)]
+    pub argument_aliases: HashMap<String, String>,
+    /// Query parameters added to every call of this tool unless the call sets them;
+    /// merged over the endpoint's `default_query`
+    #[serde(default)]
+    pub default_query: HashMap<String, String>,
+    /// Upstream statuses treated as success, e.g. `[201]` or `["200-299"]` (default 200-399);
+    /// any other status is returned as a tool error
+    #[serde(default)]
+    pub success_statuses: Vec<StatusRange>,
+    /// JSONPath into a successful response body (e.g. `$.error`); when the value there is
+    /// truthy the result is a tool error carrying it. Overrides the top-level `error_field`
+    #[serde(default)]
+    pub error_field: Option<JsonPath>,
+}
+
+/// How adjustment paths are compared to spec paths; the default is exact matching
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PathMatching {
+    /// Compare paths case-insensitively (`/Users` matches `/users`)
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Compare path structure only (`/users/{id}` matches `/users/{userId}`)
+    #[serde(default)]
+    pub ignore_param_names: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpAdjustments {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub descriptions: Vec<RouteDescription>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub routes: Vec<RouteSelection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolOverride>,
+    #[serde(default)]
+    pub path_matching: PathMatching,
+    /// Tools hidden by generated name, even when their route is selected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+    /// Routes hidden by path and method; takes precedence over `routes`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_routes: Vec<RouteSelection>,
+    /// Error field applied to every tool without its own `error_field`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<JsonPath>,
 }
@@ -1,51 +1,399 @@
+use crate::internal::config::{ParserConfig, DEFAULT_MAX_DESCRIPTION_LENGTH};
+use crate::internal::gemini::profile::{profile_by_name, profile_names, CompatibilityProfile};
 use crate::internal::parser::adjuster::Adjuster;
-use crate::internal::parser::types::{Parser, RouteTool};
+use crate::internal::parser::example::generate_example;
+use crate::internal::parser::refs::{
+    fetch_spec, inline_path_item_refs, parse_document, remote_spec_url, RefResolver,
+};
+use crate::internal::parser::sanitize::sanitize_description;
+use crate::internal::parser::types::{
+    DiagnosticSeverity, ParseDiagnostic, Parser, RouteTool, SpecInfo,
+};
+use crate::internal::requester::auth::{ApiKeyLocation, AuthScheme, RouteAuth};
 use crate::internal::requester::types::RouteConfig;
 use anyhow::{Context, Result};
-use openapiv3::{OpenAPI, Parameter, ReferenceOr, Schema, SchemaKind, Type};
+use openapiv3::{
+    APIKeyLocation, AdditionalProperties, AnySchema, MediaType, OpenAPI, Operation, Parameter,
+    ParameterSchemaOrContent, ReferenceOr, RequestBody, Schema, SchemaKind, SecurityRequirement,
+    SecurityScheme, Server, Type,
+};
 use regex::Regex;
+use rmcp::model::ToolAnnotations;
 use serde_json::{Map, Value};
+use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::io::Read;
+use std::path::Path;
 use std::sync::OnceLock;
+use std::thread;
+
+/// Below this many operations tools are generated on the calling thread
+const PARALLEL_TOOL_THRESHOLD: usize = 64;
+
+/// Operation extension that keeps an operation from becoming a tool when `true`
+pub const EXCLUDE_EXTENSION: &str = "x-mcp-exclude";
+/// Operation extension replacing the generated tool name
+pub const NAME_EXTENSION: &str = "x-mcp-name";
+/// Operation extension replacing the summary/description the tool description is built from
+pub const DESCRIPTION_EXTENSION: &str = "x-mcp-description";
 
 pub struct SwaggerParser {
     doc: Option<OpenAPI>,
     adjuster: Adjuster,
+    config: ParserConfig,
     cache_tools: Vec<RouteTool>,
+    diagnostics: Vec<ParseDiagnostic>,
+    external_documents: Vec<String>,
 }
 
 impl SwaggerParser {
     pub fn new(adjuster: Adjuster) -> Self {
+        Self::with_config(adjuster, ParserConfig::default())
+    }
+
+    pub fn with_config(adjuster: Adjuster, config: ParserConfig) -> Self {
         Self {
             doc: None,
             adjuster,
+            config,
             cache_tools: Vec::new(),
+            diagnostics: Vec::new(),
+            external_documents: Vec::new(),
         }
     }
 
+    /// Record schema constructs that schema_to_json_schema can only render as a plain string
+    fn collect_schema_issues<S: Borrow<Schema>>(
+        schema_ref: &ReferenceOr<S>,
+        location: &str,
+        issues: &mut Vec<String>,
+    ) {
+        let schema = match schema_ref {
+            ReferenceOr::Reference { reference } => {
+                issues.push(format!(
+                    "unresolved $ref '{}' at {}, rendered as string",
+                    reference, location
+                ));
+                return;
+            }
+            ReferenceOr::Item(s) => s.borrow(),
+        };
+
+        let unsupported = match &schema.schema_kind {
+            SchemaKind::Type(Type::Object(obj)) => {
+                for (name, prop) in &obj.properties {
+                    Self::collect_schema_issues(prop, &format!("{}.{}", location, name), issues);
+                }
+                if let Some(AdditionalProperties::Schema(value_schema)) = &obj.additional_properties
+                {
+                    Self::collect_schema_issues(
+                        value_schema.as_ref(),
+                        &format!("{}.*", location),
+                        issues,
+                    );
+                }
+                None
+            }
+            SchemaKind::Any(AnySchema {
+                additional_properties: Some(_),
+                ..
+            }) => None,
+            SchemaKind::Type(Type::Array(arr)) => {
+                match &arr.items {
+                    Some(items) => {
+                        Self::collect_schema_issues(items, &format!("{}[]", location), issues)
+                    }
+                    None => issues.push(format!(
+                        "array without items at {}, items rendered as string",
+                        location
+                    )),
+                }
+                None
+            }
+            SchemaKind::Type(_) => None,
+            SchemaKind::OneOf { .. } => Some("oneOf"),
+            SchemaKind::AllOf { .. } => Some("allOf"),
+            SchemaKind::AnyOf { .. } => Some("anyOf"),
+            SchemaKind::Not { .. } => Some("not"),
+            SchemaKind::Any(_) => Some("untyped"),
+        };
+        if let Some(kind) = unsupported {
+            issues.push(format!(
+                "unsupported {} schema at {}, rendered as string",
+                kind, location
+            ));
+        }
+    }
+
+    /// Describe each declared parameter that cannot be exposed in the tool's input schema
+    fn unsupported_parameters(path: &str, op: &Operation) -> Vec<String> {
+        let path_params = Self::extract_path_params(path);
+        let mut unsupported = Vec::new();
+
+        for param in &op.parameters {
+            match param {
+                ReferenceOr::Reference { reference } => unsupported.push(format!(
+                    "parameter $ref '{}' is not resolved, parameter is not exposed",
+                    reference
+                )),
+                ReferenceOr::Item(Parameter::Cookie { parameter_data, .. }) => {
+                    unsupported.push(format!(
+                        "cookie parameter '{}' is not supported, parameter is not exposed",
+                        parameter_data.name
+                    ))
+                }
+                ReferenceOr::Item(Parameter::Path { parameter_data, .. })
+                    if !path_params.contains(&parameter_data.name) =>
+                {
+                    unsupported.push(format!(
+                        "path parameter '{}' does not appear in the path template, parameter is not exposed",
+                        parameter_data.name
+                    ))
+                }
+                ReferenceOr::Item(_) => {}
+            }
+        }
+
+        unsupported
+    }
+
+    /// Inspect an operation for anything the generated tool cannot represent faithfully
+    fn diagnose_operation(&self, path: &str, method: &str, op: &Operation) -> Vec<ParseDiagnostic> {
+        let mut issues = Vec::new();
+
+        issues.extend(Self::unsupported_parameters(path, op));
+        for param in op.parameters.iter().filter_map(|p| p.as_item()) {
+            let data = param.parameter_data_ref();
+            if let ParameterSchemaOrContent::Schema(schema_ref) = &data.format {
+                Self::collect_schema_issues(
+                    schema_ref,
+                    &format!("parameter '{}'", data.name),
+                    &mut issues,
+                );
+            }
+        }
+
+        match &op.request_body {
+            None => {}
+            Some(_) if !matches!(method, "POST" | "PUT" | "PATCH") => {
+                issues.push(format!("request body on {} is ignored", method))
+            }
+            Some(ReferenceOr::Reference { reference }) => issues.push(format!(
+                "request body $ref '{}' is not resolved, body is not exposed",
+                reference
+            )),
+            Some(ReferenceOr::Item(body)) => match Self::body_media_type(body) {
+                Some((content_type, _)) if !Self::is_encodable_media_type(content_type) => {
+                    issues.push(format!(
+                        "request body is {}, which cannot be built from arguments; body is not exposed",
+                        content_type
+                    ))
+                }
+                None => issues.push("request body has no content, body is not exposed".to_string()),
+                Some((_, media)) => match &media.schema {
+                    None => issues
+                        .push("request body is missing a schema, body is not exposed".to_string()),
+                    Some(schema_ref) => {
+                        Self::collect_schema_issues(schema_ref, "body", &mut issues)
+                    }
+                },
+            },
+        }
+
+        issues
+            .into_iter()
+            .map(|message| ParseDiagnostic::new(path, method, DiagnosticSeverity::Warning, message))
+            .collect()
+    }
+
+    /// Flag deprecated operations and append the configured `x-` extension values
+    fn decorate_description(&self, op: &Operation, description: String) -> String {
+        let mut parts = Vec::new();
+        if op.deprecated {
+            parts.push("[DEPRECATED]".to_string());
+        }
+        if !description.is_empty() {
+            parts.push(description);
+        }
+        for key in &self.config.description_extensions {
+            match op.extensions.get(key) {
+                Some(Value::String(s)) => parts.push(s.clone()),
+                Some(Value::Null) | None => {}
+                Some(other) => parts.push(other.to_string()),
+            }
+        }
+        parts.join(" ")
+    }
+
     fn clean_description(desc: &str) -> String {
+        let cleaned = Self::normalize_description(desc);
+
+        if cleaned.is_empty() {
+            return "No description provided".to_string();
+        }
+
+        Self::truncate_description(cleaned, DEFAULT_MAX_DESCRIPTION_LENGTH)
+    }
+
+    /// Strip HTML tags and collapse all whitespace to single spaces
+    fn normalize_description(desc: &str) -> String {
         static RE: OnceLock<Regex> = OnceLock::new();
         let re = RE.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
 
         let no_html = re.replace_all(desc, " ");
-        let cleaned = no_html
+        no_html
             .replace(['\n', '\r'], " ")
             .split_whitespace()
             .collect::<Vec<_>>()
-            .join(" ");
+            .join(" ")
+    }
 
-        if cleaned.is_empty() {
-            return "No description provided".to_string();
+    /// Profile generated tools are sanitized against: `profile` if set, else `gemini` when
+    /// `gemini_compatible` is on. Unknown names are rejected by `init`.
+    fn compatibility_profile(&self) -> Option<&'static dyn CompatibilityProfile> {
+        match &self.config.profile {
+            Some(name) => profile_by_name(name),
+            None if self.config.gemini_compatible => profile_by_name("gemini"),
+            None => None,
         }
+    }
 
-        if cleaned.len() > 700 {
-            let mut truncated = cleaned[..700].to_string();
-            truncated.push_str("...");
-            truncated
-        } else {
-            cleaned
+    /// Cut a description to at most `max_len` characters plus an ellipsis; 0 means unlimited
+    fn truncate_description(desc: String, max_len: usize) -> String {
+        if max_len == 0 || desc.chars().count() <= max_len {
+            return desc;
+        }
+
+        let mut truncated: String = desc.chars().take(max_len).collect();
+        truncated.truncate(truncated.trim_end().len());
+        truncated.push_str("...");
+        truncated
+    }
+
+    /// Rename upstream argument names to their aliases in the input schema and its body;
+    /// an alias that would replace another argument is ignored
+    fn apply_argument_aliases(
+        schema: &mut serde_json::Map<String, Value>,
+        aliases: &HashMap<String, String>,
+    ) {
+        if aliases.is_empty() {
+            return;
+        }
+        let rename = |schema: &mut serde_json::Map<String, Value>| {
+            let Some(Value::Object(properties)) = schema.get_mut("properties") else {
+                return;
+            };
+            let mut renamed = Vec::new();
+            for (alias, upstream) in aliases {
+                if properties.contains_key(alias) {
+                    continue;
+                }
+                if let Some(property) = properties.remove(upstream) {
+                    properties.insert(alias.clone(), property);
+                    renamed.push((upstream.as_str(), alias.as_str()));
+                }
+            }
+            if let Some(Value::Array(required)) = schema.get_mut("required") {
+                for name in required.iter_mut() {
+                    if let Some((_, alias)) = renamed.iter().find(|(u, _)| name == *u) {
+                        *name = Value::String(alias.to_string());
+                    }
+                }
+            }
+        };
+
+        rename(schema);
+        if let Some(Value::Object(body)) =
+            schema.get_mut("properties").and_then(|p| p.get_mut("body"))
+        {
+            rename(body);
+        }
+    }
+
+    /// Append the spec's body example, or one generated from the body schema, to the
+    /// `body` argument's description
+    fn add_body_example(schema: &mut serde_json::Map<String, Value>, route: &RouteConfig) {
+        let Some(Value::Object(body)) =
+            schema.get_mut("properties").and_then(|p| p.get_mut("body"))
+        else {
+            return;
+        };
+        let mut example = match &route.method_config.body_example {
+            Some(example) => example.clone(),
+            None => generate_example(&Value::Object(body.clone())),
+        };
+        // Spec examples use upstream names; the schema may expose aliases
+        if let Value::Object(fields) = &mut example {
+            for (alias, upstream) in &route.method_config.argument_aliases {
+                if let Some(value) = fields.remove(upstream) {
+                    fields.insert(alias.clone(), value);
+                }
+            }
         }
+
+        let example = format!("Example: {}", example);
+        let description = match body.get("description").and_then(Value::as_str) {
+            Some(description) if !description.is_empty() => {
+                format!("{}\n{}", description, example)
+            }
+            _ => example,
+        };
+        body.insert("description".to_string(), Value::String(description));
+    }
+
+    /// "Required: a, b. Optional: c." line for the input schema, `None` without properties
+    fn argument_summary(schema: &serde_json::Map<String, Value>) -> Option<String> {
+        let properties = schema.get("properties").and_then(Value::as_object)?;
+        if properties.is_empty() {
+            return None;
+        }
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter(|name| properties.contains_key(*name))
+            .collect();
+        let optional: Vec<&str> = properties
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !required.contains(name))
+            .collect();
+
+        let mut parts = Vec::new();
+        if !required.is_empty() {
+            parts.push(format!("Required: {}.", required.join(", ")));
+        }
+        if !optional.is_empty() {
+            parts.push(format!("Optional: {}.", optional.join(", ")));
+        }
+        Some(parts.join(" "))
+    }
+
+    /// Join summary and description as "summary — description", skipping empty or repeated parts
+    fn combine_summary_and_description(summary: Option<&str>, description: Option<&str>) -> String {
+        let summary = summary.map(str::trim).filter(|s| !s.is_empty());
+        let description = description.map(str::trim).filter(|s| !s.is_empty());
+
+        match (summary, description) {
+            (Some(s), Some(d)) if Self::repeats_summary(d, s) => d.to_string(),
+            (Some(s), Some(d)) => format!("{} — {}", s, d),
+            (Some(s), None) => s.to_string(),
+            (None, Some(d)) => d.to_string(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Whether the description opens with the summary's words: "List tasks. Returns..."
+    /// repeats "List tasks", but "Lists all tasks" does not repeat "List"
+    fn repeats_summary(description: &str, summary: &str) -> bool {
+        let summary = summary.trim_end_matches('.');
+        description.strip_prefix(summary).is_some_and(|rest| {
+            rest.chars()
+                .next()
+                .is_none_or(|c| !c.is_alphanumeric() && c != '_')
+        })
     }
 
     fn ensure_strict_object(value: &mut Value) {
@@ -92,6 +440,30 @@ impl SwaggerParser {
         }
     }
 
+    /// `x-mcp-name` of an operation: `None` when absent, `Err` when not a valid tool name
+    /// (letters, digits and underscores, not starting with a digit, at most 64 characters)
+    fn extension_tool_name(op: &Operation) -> Option<Result<String, String>> {
+        let value = op.extensions.get(NAME_EXTENSION)?;
+        static VALID: OnceLock<Regex> = OnceLock::new();
+        let valid = VALID.get_or_init(|| Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_]{0,63}$").unwrap());
+        Some(match value.as_str() {
+            Some(name) if valid.is_match(name) => Ok(name.to_string()),
+            _ => Err(format!(
+                "{} {} is not a valid tool name, using the generated name",
+                NAME_EXTENSION, value
+            )),
+        })
+    }
+
+    /// Tool name for an operation. Precedence: `x-mcp-name`, then the name generated from
+    /// method and path; adjustments refer to tools by this final name
+    fn tool_name(path: &str, method: &str, op: &Operation) -> String {
+        match Self::extension_tool_name(op) {
+            Some(Ok(name)) => name,
+            _ => Self::normalize_tool_name(path, method),
+        }
+    }
+
     fn extract_path_params(path: &str) -> Vec<String> {
         let re = Regex::new(r"\{([^}]+)\}").unwrap();
         re.captures_iter(path)
@@ -109,10 +481,19 @@ impl SwaggerParser {
             Self::clean_description(schema.schema_data.description.as_deref().unwrap_or(""));
 
         match &schema.schema_kind {
-            SchemaKind::Type(Type::String(_)) => serde_json::json!({
-                "type": "string",
-                "description": description
-            }),
+            SchemaKind::Type(Type::String(string_type)) => {
+                let mut json = serde_json::json!({
+                    "type": "string",
+                    "description": description
+                });
+                let variants: Vec<&String> = string_type.enumeration.iter().flatten().collect();
+                if !variants.is_empty() {
+                    if let Some(map) = json.as_object_mut() {
+                        map.insert("enum".to_string(), serde_json::json!(variants));
+                    }
+                }
+                json
+            }
             SchemaKind::Type(Type::Number(_)) => serde_json::json!({
                 "type": "number",
                 "description": description
@@ -143,13 +524,31 @@ impl SwaggerParser {
                     "description": description
                 });
 
-                if !obj.required.is_empty() {
-                    if let Some(map) = json.as_object_mut() {
+                if let Some(map) = json.as_object_mut() {
+                    if !obj.required.is_empty() {
                         map.insert("required".to_string(), serde_json::json!(obj.required));
                     }
+                    // Keep map/dictionary schemas open so clients may send arbitrary keys
+                    if let Some(additional) = &obj.additional_properties {
+                        map.insert(
+                            "additionalProperties".to_string(),
+                            Self::additional_properties_to_json(additional),
+                        );
+                    }
                 }
                 json
             }
+            SchemaKind::Any(AnySchema {
+                additional_properties: Some(additional),
+                ..
+            }) => {
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": Self::additional_properties_to_json(additional),
+                    "description": description
+                })
+            }
             SchemaKind::Type(Type::Array(arr)) => {
                 let items = match &arr.items {
                     Some(items_ref) => {
@@ -176,6 +575,13 @@ impl SwaggerParser {
         }
     }
 
+    fn additional_properties_to_json(additional: &AdditionalProperties) -> Value {
+        match additional {
+            AdditionalProperties::Any(allowed) => Value::Bool(*allowed),
+            AdditionalProperties::Schema(schema_ref) => Self::schema_to_json_schema(schema_ref),
+        }
+    }
+
     fn parameter_data_to_json_schema(
         &self,
         param_data: &openapiv3::ParameterData,
@@ -183,10 +589,15 @@ impl SwaggerParser {
         let raw_desc = param_data.description.as_deref().unwrap_or("");
         let description = Self::clean_description(raw_desc);
 
-        let schema = serde_json::json!({
-            "type": "string",
-            "description": description
-        });
+        // Use the declared parameter schema so the tool advertises (and the
+        // executor can coerce to) the real type; content-encoded params stay strings
+        let mut schema = match &param_data.format {
+            ParameterSchemaOrContent::Schema(schema_ref) => Self::schema_to_json_schema(schema_ref),
+            ParameterSchemaOrContent::Content(_) => serde_json::json!({ "type": "string" }),
+        };
+        if let Some(map) = schema.as_object_mut() {
+            map.insert("description".to_string(), Value::String(description));
+        }
 
         (schema, param_data.required)
     }
@@ -237,6 +648,45 @@ impl SwaggerParser {
         None
     }
 
+    /// Media type the request body is sent as: JSON when offered, else the first declared
+    fn body_media_type(body: &RequestBody) -> Option<(&str, &MediaType)> {
+        body.content
+            .get_key_value("application/json")
+            .or_else(|| {
+                body.content
+                    .iter()
+                    .find(|(media_type, _)| media_type.ends_with("+json"))
+            })
+            .or_else(|| body.content.first())
+            .map(|(media_type, media)| (media_type.as_str(), media))
+    }
+
+    /// Example of the request body: the media type's `example`, its first inline
+    /// `examples` value, or the schema's `example`
+    fn body_example(body: &RequestBody) -> Option<serde_json::Value> {
+        let (_, media) = Self::body_media_type(body)?;
+        media
+            .example
+            .clone()
+            .or_else(|| {
+                media.examples.values().find_map(|example| match example {
+                    ReferenceOr::Item(example) => example.value.clone(),
+                    ReferenceOr::Reference { .. } => None,
+                })
+            })
+            .or_else(|| match &media.schema {
+                Some(ReferenceOr::Item(schema)) => schema.schema_data.example.clone(),
+                _ => None,
+            })
+    }
+
+    /// Media types a body can be encoded in from JSON arguments
+    fn is_encodable_media_type(content_type: &str) -> bool {
+        content_type == "application/json"
+            || content_type.ends_with("+json")
+            || content_type == "application/x-www-form-urlencoded"
+    }
+
     fn get_body_schema(&self, route: &RouteConfig) -> Option<serde_json::Value> {
         let doc = self.doc.as_ref()?;
         let path_item = doc.paths.paths.get(&route.path)?;
@@ -257,7 +707,8 @@ impl SwaggerParser {
             ReferenceOr::Reference { .. } => return None,
         };
 
-        if let Some(content) = request_body.content.get("application/json") {
+        let (content_type, content) = Self::body_media_type(request_body)?;
+        if Self::is_encodable_media_type(content_type) {
             if let Some(schema) = &content.schema {
                 let mut json_schema = Self::schema_to_json_schema(schema);
                 Self::ensure_strict_object(&mut json_schema);
@@ -273,13 +724,14 @@ impl SwaggerParser {
 
         let path_params = Self::extract_path_params(&route.path);
         for param in &path_params {
-            properties.insert(
-                param.clone(),
-                serde_json::json!({
+            let param_schema = match self.get_parameter_schema(route, param, "path") {
+                Some((param_schema, _)) => param_schema,
+                None => serde_json::json!({
                     "type": "string",
                     "description": format!("Path parameter: {}", param)
                 }),
-            );
+            };
+            properties.insert(param.clone(), param_schema);
             required.push(param.clone());
         }
 
@@ -354,25 +806,277 @@ impl SwaggerParser {
         schema
     }
 
-    fn generate_tool(&self, route: &RouteConfig) -> rmcp::model::Tool {
-        let tool_name = Self::normalize_tool_name(&route.path, &route.method);
+    /// Turn an operationId like `getTaskById` or `list_tasks` into "Get task by id"
+    fn humanize_operation_id(operation_id: &str) -> String {
+        let mut words: Vec<String> = Vec::new();
+        let mut current = String::new();
+        let mut prev_lower = false;
+
+        for c in operation_id.chars() {
+            if c == '_' || c == '-' || c == '.' || c.is_whitespace() {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                prev_lower = false;
+                continue;
+            }
+            if c.is_uppercase() && prev_lower && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            current.extend(c.to_lowercase());
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+
+        let sentence = words.join(" ");
+        let mut chars = sentence.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => sentence,
+        }
+    }
+
+    /// Title from the adjustments override, the operation summary or the humanized operationId
+    fn tool_title(&self, tool_name: &str, op: &Operation) -> Option<String> {
+        if let Some(title) = self
+            .adjuster
+            .get_tool_override(tool_name)
+            .and_then(|t| t.title.clone())
+        {
+            return Some(title);
+        }
+
+        op.summary
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                op.operation_id
+                    .as_deref()
+                    .map(Self::humanize_operation_id)
+                    .filter(|s| !s.is_empty())
+            })
+    }
 
-        let raw_desc = format!("{} {} - {}", route.method, route.path, route.description);
-        let description = Self::clean_description(&raw_desc);
+    /// URL of the first server, with server variables replaced by their defaults
+    fn server_url(servers: &[Server]) -> Option<String> {
+        let server = servers.first()?;
+        let mut url = server.url.clone();
+        if let Some(variables) = &server.variables {
+            for (name, variable) in variables {
+                url = url.replace(&format!("{{{}}}", name), &variable.default);
+            }
+        }
+        Some(url)
+    }
+
+    /// Auth scheme for the first security requirement whose scheme can be applied.
+    /// `Err` lists the declared schemes when none of them can.
+    fn security_auth(
+        doc: &OpenAPI,
+        requirements: &[SecurityRequirement],
+    ) -> std::result::Result<Option<AuthScheme>, String> {
+        let mut unsupported = Vec::new();
+        for requirement in requirements {
+            // An empty requirement makes auth optional
+            let Some(name) = requirement.keys().next() else {
+                return Ok(None);
+            };
+            let scheme = doc
+                .components
+                .as_ref()
+                .and_then(|c| c.security_schemes.get(name))
+                .and_then(|s| s.as_item());
+            let auth = match scheme {
+                Some(SecurityScheme::APIKey { location, name, .. }) => match location {
+                    APIKeyLocation::Header => Some(AuthScheme::ApiKey {
+                        name: name.clone(),
+                        location: ApiKeyLocation::Header,
+                    }),
+                    APIKeyLocation::Query => Some(AuthScheme::ApiKey {
+                        name: name.clone(),
+                        location: ApiKeyLocation::Query,
+                    }),
+                    APIKeyLocation::Cookie => None,
+                },
+                Some(SecurityScheme::HTTP { scheme, .. }) => {
+                    match scheme.to_ascii_lowercase().as_str() {
+                        "bearer" => Some(AuthScheme::Bearer),
+                        "basic" => Some(AuthScheme::Basic),
+                        _ => None,
+                    }
+                }
+                Some(SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. }) => {
+                    Some(AuthScheme::OAuth2)
+                }
+                None => None,
+            };
+            match auth {
+                Some(auth) => return Ok(Some(auth)),
+                None => unsupported.push(name.clone()),
+            }
+        }
+
+        if unsupported.is_empty() {
+            Ok(None)
+        } else {
+            Err(format!(
+                "security scheme(s) {} cannot be applied automatically; configure endpoint auth_type",
+                unsupported.join(", ")
+            ))
+        }
+    }
+
+    /// Scopes of the first requirement using an OAuth2 or OpenID Connect scheme
+    fn oauth_scopes(doc: &OpenAPI, requirements: &[SecurityRequirement]) -> Vec<String> {
+        let is_oauth = |name: &String| {
+            matches!(
+                doc.components
+                    .as_ref()
+                    .and_then(|c| c.security_schemes.get(name))
+                    .and_then(|s| s.as_item()),
+                Some(SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. })
+            )
+        };
+        let Some(requirement) = requirements
+            .iter()
+            .find(|requirement| requirement.keys().any(is_oauth))
+        else {
+            return Vec::new();
+        };
+
+        let mut scopes: Vec<String> = requirement
+            .iter()
+            .filter(|(name, _)| is_oauth(name))
+            .flat_map(|(_, scopes)| scopes.iter().cloned())
+            .collect();
+        scopes.sort();
+        scopes.dedup();
+        scopes
+    }
+
+    /// Whether the path falls under a configured path prefix, matching whole segments only
+    fn matches_path_prefix(&self, path: &str) -> bool {
+        if self.config.path_prefixes.is_empty() {
+            return true;
+        }
+        self.config.path_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Methods that never modify server state
+    fn is_safe_method(method: &str) -> bool {
+        matches!(method, "GET" | "HEAD" | "OPTIONS")
+    }
+
+    /// Infer behaviour hints from the HTTP method, then apply any adjustments override
+    fn tool_annotations(&self, tool_name: &str, method: &str) -> ToolAnnotations {
+        let mut annotations = match method {
+            "GET" | "HEAD" => ToolAnnotations::new().read_only(true).idempotent(true),
+            "DELETE" => ToolAnnotations::new()
+                .read_only(false)
+                .destructive(true)
+                .idempotent(true),
+            "PUT" => ToolAnnotations::new()
+                .read_only(false)
+                .destructive(false)
+                .idempotent(true),
+            _ => ToolAnnotations::new()
+                .read_only(false)
+                .destructive(false)
+                .idempotent(false),
+        };
+
+        if let Some(overrides) = self
+            .adjuster
+            .get_tool_override(tool_name)
+            .and_then(|t| t.annotations.as_ref())
+        {
+            if overrides.read_only.is_some() {
+                annotations.read_only_hint = overrides.read_only;
+            }
+            if overrides.destructive.is_some() {
+                annotations.destructive_hint = overrides.destructive;
+            }
+            if overrides.idempotent.is_some() {
+                annotations.idempotent_hint = overrides.idempotent;
+            }
+            if overrides.open_world.is_some() {
+                annotations.open_world_hint = overrides.open_world;
+            }
+        }
+
+        annotations
+    }
+
+    fn generate_tool(&self, route: &RouteConfig, op: &Operation) -> rmcp::model::Tool {
+        let tool_name = Self::tool_name(&route.path, &route.method, op);
+        let title = self.tool_title(&tool_name, op);
+
+        // The tool name already encodes method and path, so only fall back to them
+        let mut description = if self.config.sanitize_descriptions {
+            sanitize_description(&route.description)
+        } else {
+            Self::normalize_description(&route.description)
+        };
+        if description.is_empty() {
+            description = format!("{} {}", route.method, route.path);
+        }
 
         let input_schema = self.create_input_schema(route);
         let mut input_val = serde_json::Value::Object(input_schema);
         Self::ensure_strict_object(&mut input_val);
 
-        let final_input = input_val.as_object().unwrap().clone();
+        let mut final_input = input_val.as_object().unwrap().clone();
+        Self::apply_argument_aliases(&mut final_input, &route.method_config.argument_aliases);
+        if self.config.body_examples {
+            Self::add_body_example(&mut final_input, route);
+        }
+        let profile = self.compatibility_profile();
+        if let Some(profile) = profile {
+            profile.sanitize_schema(&mut final_input);
+        }
+
+        let max_len = self.config.max_description_length;
+        let description = match Self::argument_summary(&final_input) {
+            // Shorten the description rather than the argument list when both do not fit
+            Some(summary) if max_len == 0 || summary.chars().count() < max_len => {
+                let budget = max_len.saturating_sub(summary.chars().count() + 1);
+                if max_len > 0 && budget == 0 {
+                    // No room left next to the summary; a budget of 0 would mean unlimited
+                    summary
+                } else {
+                    format!(
+                        "{}\n{}",
+                        Self::truncate_description(description, budget),
+                        summary
+                    )
+                }
+            }
+            Some(summary) => {
+                Self::truncate_description(format!("{}\n{}", description, summary), max_len)
+            }
+            None => Self::truncate_description(description, max_len),
+        };
+        let description = match profile {
+            Some(profile) => profile.sanitize_description(&description),
+            None => description,
+        };
+        let annotations = self.tool_annotations(&tool_name, &route.method);
 
         rmcp::model::Tool {
             name: tool_name.into(),
-            title: None,
+            title,
             description: Some(description.into()),
             input_schema: final_input.into(),
             output_schema: None,
-            annotations: None,
+            annotations: Some(annotations),
             icons: None,
             meta: None,
         }
@@ -381,20 +1085,85 @@ impl SwaggerParser {
 
 impl Parser for SwaggerParser {
     fn init(&mut self, swagger_path: &str, adjustments_path: Option<&str>) -> Result<()> {
-        let data = std::fs::read(swagger_path).context("Failed to read Swagger file")?;
+        if let Some(name) = &self.config.profile {
+            if profile_by_name(name).is_none() {
+                anyhow::bail!(
+                    "Unknown compatibility profile '{}' (expected one of: {})",
+                    name,
+                    profile_names().join(", ")
+                );
+            }
+        }
+
+        // http(s) specs are fetched with retries; a missing local file fails right away
+        let (data, resolver) = match remote_spec_url(swagger_path) {
+            Some(url) => (
+                fetch_spec(&url, self.config.spec_fetch_retries)?,
+                RefResolver::for_url(url, self.config.remote_refs),
+            ),
+            None => (
+                std::fs::read(swagger_path).context("Failed to read Swagger file")?,
+                RefResolver::new(Some(Path::new(swagger_path)), self.config.remote_refs),
+            ),
+        };
 
         // Load adjustments if provided
         if let Some(adj_path) = adjustments_path {
-            self.adjuster.load(adj_path)?;
+            self.load_adjustments(adj_path)?;
         }
 
-        let mut json_value: serde_json::Value = if let Ok(v) = serde_json::from_slice(&data) {
-            v
-        } else if let Ok(v) = serde_yaml::from_slice::<serde_json::Value>(&data) {
-            v
-        } else {
-            return Err(anyhow::anyhow!("Failed to parse spec as JSON or YAML"));
-        };
+        self.parse_spec(&data, resolver)
+    }
+
+    fn get_route_tools(&self) -> &[RouteTool] {
+        &self.cache_tools
+    }
+
+    fn get_diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.diagnostics
+    }
+
+    fn get_spec_info(&self) -> Option<SpecInfo> {
+        self.doc.as_ref().map(|doc| SpecInfo {
+            title: doc.info.title.clone(),
+            version: doc.info.version.clone(),
+            description: doc.info.description.clone(),
+        })
+    }
+
+    fn get_external_documents(&self) -> &[String] {
+        &self.external_documents
+    }
+
+    fn load_adjustments(&mut self, adjustments_file: &str) -> Result<()> {
+        self.adjuster.load(adjustments_file)
+    }
+
+    fn parse_reader(&mut self, mut reader: Box<dyn Read>) -> Result<()> {
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .context("Failed to read Swagger spec")?;
+        let resolver = RefResolver::new(None, self.config.remote_refs);
+        self.parse_spec(&data, resolver)
+    }
+}
+
+impl SwaggerParser {
+    /// Parse spec bytes (JSON or YAML) into route tools, replacing any previous result.
+    /// External `$ref`s resolve relative to where the resolver says the spec came from.
+    fn parse_spec(&mut self, data: &[u8], mut resolver: RefResolver) -> Result<()> {
+        let mut json_value = parse_document(data)?;
+
+        resolver.resolve(&mut json_value);
+        self.external_documents = resolver.loaded_documents();
+        let path_item_issues = inline_path_item_refs(&mut json_value);
+        let ref_issues: Vec<ParseDiagnostic> = resolver
+            .issues()
+            .iter()
+            .chain(&path_item_issues)
+            .map(|issue| ParseDiagnostic::new("*", "*", DiagnosticSeverity::Warning, issue))
+            .collect();
 
         fn sanitize_refs(value: &mut Value) {
             match value {
@@ -422,14 +1191,63 @@ impl Parser for SwaggerParser {
         let doc: OpenAPI = serde_json::from_value(json_value)
             .context("Failed to parse into strict OpenAPI struct")?;
         self.doc = Some(doc);
+        self.cache_tools.clear();
+        self.diagnostics = ref_issues;
 
+        let mut tools = Vec::new();
         if let Some(doc) = &self.doc {
+            let spec_auth = match Self::security_auth(doc, doc.security.as_deref().unwrap_or(&[])) {
+                Ok(auth) => auth,
+                Err(message) => {
+                    self.diagnostics.push(ParseDiagnostic::new(
+                        "*",
+                        "*",
+                        DiagnosticSeverity::Warning,
+                        message,
+                    ));
+                    None
+                }
+            };
+            let global_auth = spec_auth.map_or(RouteAuth::Endpoint, RouteAuth::Global);
+            let mut pending = Vec::new();
             for (path, item) in &doc.paths.paths {
+                // Coarse filter first; paths outside the prefixes are not reported
+                if !self.matches_path_prefix(path) {
+                    continue;
+                }
+
                 let item = match item {
                     ReferenceOr::Item(i) => i,
-                    _ => continue,
+                    ReferenceOr::Reference { reference } => {
+                        self.diagnostics.push(ParseDiagnostic::new(
+                            path,
+                            "*",
+                            DiagnosticSeverity::Warning,
+                            format!(
+                                "path item $ref '{}' is not resolved, path skipped",
+                                reference
+                            ),
+                        ));
+                        continue;
+                    }
                 };
 
+                let unsupported = [
+                    ("HEAD", &item.head),
+                    ("OPTIONS", &item.options),
+                    ("TRACE", &item.trace),
+                ];
+                for (method, op_opt) in unsupported {
+                    if op_opt.is_some() {
+                        self.diagnostics.push(ParseDiagnostic::new(
+                            path,
+                            method,
+                            DiagnosticSeverity::Info,
+                            "method not supported, operation skipped",
+                        ));
+                    }
+                }
+
                 let operations = [
                     ("GET", &item.get),
                     ("POST", &item.post),
@@ -440,8 +1258,65 @@ impl Parser for SwaggerParser {
 
                 for (method, op_opt) in operations {
                     if let Some(op) = op_opt {
+                        // Spec authors can hide operations inline, whatever the adjustments select
+                        if op.extensions.get(EXCLUDE_EXTENSION) == Some(&Value::Bool(true)) {
+                            self.diagnostics.push(ParseDiagnostic::new(
+                                path,
+                                method,
+                                DiagnosticSeverity::Info,
+                                format!("excluded by {}", EXCLUDE_EXTENSION),
+                            ));
+                            continue;
+                        }
+
+                        // Read-only mode hides mutating operations before any adjustment
+                        if self.config.read_only && !Self::is_safe_method(method) {
+                            self.diagnostics.push(ParseDiagnostic::new(
+                                path,
+                                method,
+                                DiagnosticSeverity::Info,
+                                "hidden by read-only mode",
+                            ));
+                            continue;
+                        }
+
+                        if let Some(Err(message)) = Self::extension_tool_name(op) {
+                            self.diagnostics.push(ParseDiagnostic::new(
+                                path,
+                                method,
+                                DiagnosticSeverity::Warning,
+                                message,
+                            ));
+                        }
+                        let tool_name = Self::tool_name(path, method, op);
                         // Check if this route should be included via adjuster
-                        if !self.adjuster.exists_in_mcp(path, method) {
+                        if !self.adjuster.exists_in_mcp(path, method)
+                            || self.adjuster.is_excluded(path, method, &tool_name)
+                        {
+                            self.diagnostics.push(ParseDiagnostic::new(
+                                path,
+                                method,
+                                DiagnosticSeverity::Info,
+                                "excluded by adjustments",
+                            ));
+                            continue;
+                        }
+
+                        let found = self.diagnose_operation(path, method, op);
+                        self.diagnostics.extend(found);
+
+                        // In strict mode a lossy input schema is worse than no tool at all
+                        let unsupported = Self::unsupported_parameters(path, op);
+                        if self.config.strict && !unsupported.is_empty() {
+                            self.diagnostics.push(ParseDiagnostic::new(
+                                path,
+                                method,
+                                DiagnosticSeverity::Error,
+                                format!(
+                                    "tool not registered in strict mode: {} parameter(s) cannot be exposed",
+                                    unsupported.len()
+                                ),
+                            ));
                             continue;
                         }
 
@@ -460,15 +1335,119 @@ impl Parser for SwaggerParser {
                             }
                         }
 
-                        // Get base description and apply adjuster modifications
-                        let base_description = op
-                            .summary
-                            .clone()
-                            .or(op.description.clone())
-                            .unwrap_or_default();
+                        // Precedence: adjustments, then x-mcp-description, then summary/description
+                        let base_description = match op
+                            .extensions
+                            .get(DESCRIPTION_EXTENSION)
+                            .and_then(Value::as_str)
+                            .map(str::trim)
+                        {
+                            Some(description) if !description.is_empty() => description.to_string(),
+                            _ => Self::combine_summary_and_description(
+                                op.summary.as_deref(),
+                                op.description.as_deref(),
+                            ),
+                        };
                         let description =
                             self.adjuster
                                 .get_description(path, method, &base_description);
+                        let description = self.decorate_description(op, description);
+
+                        // Operation-level servers win over path-level ones
+                        let servers = if op.servers.is_empty() {
+                            &item.servers
+                        } else {
+                            &op.servers
+                        };
+                        let base_url = match Self::server_url(servers) {
+                            Some(url) if url::Url::parse(&url).is_ok() => Some(url),
+                            Some(url) => {
+                                self.diagnostics.push(ParseDiagnostic::new(
+                                    path,
+                                    method,
+                                    DiagnosticSeverity::Warning,
+                                    format!(
+                                        "relative server URL '{}' ignored, using the endpoint base URL",
+                                        url
+                                    ),
+                                ));
+                                None
+                            }
+                            None => None,
+                        };
+
+                        // `security: []` opens the operation up; unusable schemes fall back
+                        let auth = match op
+                            .security
+                            .as_deref()
+                            .map(|reqs| Self::security_auth(doc, reqs))
+                        {
+                            None => global_auth.clone(),
+                            Some(Ok(Some(scheme))) => RouteAuth::Operation(scheme),
+                            Some(Ok(None)) => RouteAuth::Public,
+                            Some(Err(message)) => {
+                                self.diagnostics.push(ParseDiagnostic::new(
+                                    path,
+                                    method,
+                                    DiagnosticSeverity::Warning,
+                                    message,
+                                ));
+                                global_auth.clone()
+                            }
+                        };
+
+                        // Operation-level security replaces the global requirements
+                        let required_scopes = Self::oauth_scopes(
+                            doc,
+                            op.security
+                                .as_deref()
+                                .or(doc.security.as_deref())
+                                .unwrap_or(&[]),
+                        );
+
+                        // Overrides are keyed by upstream name; routes look up by alias
+                        let argument_aliases = self
+                            .adjuster
+                            .get_tool_override(&tool_name)
+                            .map(|t| {
+                                t.argument_aliases
+                                    .iter()
+                                    .map(|(upstream, alias)| (alias.clone(), upstream.clone()))
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        let default_query = self
+                            .adjuster
+                            .get_tool_override(&tool_name)
+                            .map(|t| t.default_query.clone())
+                            .unwrap_or_default();
+                        let error_field = self.adjuster.get_error_field(&tool_name).cloned();
+                        let success_statuses = self
+                            .adjuster
+                            .get_tool_override(&tool_name)
+                            .map(|t| t.success_statuses.clone())
+                            .unwrap_or_default();
+                        let content_type = self
+                            .adjuster
+                            .get_tool_override(&tool_name)
+                            .and_then(|t| t.content_type.clone())
+                            .or_else(|| match &op.request_body {
+                                Some(ReferenceOr::Item(body))
+                                    if matches!(method, "POST" | "PUT" | "PATCH") =>
+                                {
+                                    Self::body_media_type(body).map(|(ct, _)| ct.to_string())
+                                }
+                                _ => None,
+                            });
+
+                        let body_example = match &op.request_body {
+                            Some(ReferenceOr::Item(body))
+                                if matches!(method, "POST" | "PUT" | "PATCH") =>
+                            {
+                                Self::body_example(body)
+                            }
+                            _ => None,
+                        };
 
                         let route_config = RouteConfig {
                             path: path.clone(),
@@ -477,27 +1456,119 @@ impl Parser for SwaggerParser {
                             method_config: crate::internal::requester::types::MethodConfig {
                                 query_params,
                                 header_params,
+                                content_type,
+                                argument_aliases,
+                                default_query,
+                                body_example,
+                                success_statuses,
+                                error_field,
+                                required_scopes,
                                 ..Default::default()
                             },
                             headers: HashMap::new(),
                             parameters: HashMap::new(),
+                            base_url,
+                            auth,
                         };
 
-                        let tool = self.generate_tool(&route_config);
-                        self.cache_tools.push(RouteTool { route_config, tool });
+                        pending.push((route_config, op));
                     }
                 }
             }
+
+            let (unique, issues) = dedupe_tool_names(self.build_tools(pending));
+            self.diagnostics.extend(issues);
+            tools = unique;
         }
+        self.cache_tools = tools;
 
         Ok(())
     }
 
-    fn get_route_tools(&self) -> &[RouteTool] {
-        &self.cache_tools
+    /// Generate the tools for the filtered routes, keeping their order.
+    /// Schema generation dominates parse time on large specs, so it is split across threads.
+    fn build_tools(&self, pending: Vec<(RouteConfig, &Operation)>) -> Vec<RouteTool> {
+        let workers = thread::available_parallelism().map_or(1, |n| n.get());
+        if workers < 2 || pending.len() < PARALLEL_TOOL_THRESHOLD {
+            return pending
+                .into_iter()
+                .map(|(route_config, op)| self.build_tool(route_config, op))
+                .collect();
+        }
+
+        let chunk_size = pending.len().div_ceil(workers);
+        let mut chunks = Vec::with_capacity(workers);
+        let mut routes = pending.into_iter().peekable();
+        while routes.peek().is_some() {
+            chunks.push(routes.by_ref().take(chunk_size).collect::<Vec<_>>());
+        }
+
+        // Chunks are joined in spawn order, so the result matches a sequential build
+        thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .into_iter()
+                            .map(|(route_config, op)| self.build_tool(route_config, op))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
     }
 
-    fn parse_reader(&mut self, _reader: Box<dyn Read>) -> Result<()> {
-        Ok(())
+    fn build_tool(&self, mut route_config: RouteConfig, op: &Operation) -> RouteTool {
+        let tool = self.generate_tool(&route_config, op);
+
+        // Keep per-argument schemas so the executor can coerce types
+        if let Some(Value::Object(props)) = tool.input_schema.get("properties") {
+            route_config.method_config.param_schemas = props.clone().into_iter().collect();
+        }
+
+        RouteTool { route_config, tool }
     }
 }
+
+/// Keep the first tool of each name and drop and report the others.
+///
+/// Different operations can end up with the same tool name: name normalization maps
+/// `/a-b` and `/a_b` to one name, long names are cut, and `x-mcp-name` may be reused.
+/// The registry holds one tool per name, so a later tool would silently replace an
+/// earlier one; each dropped tool is reported as an error naming both operations.
+pub fn dedupe_tool_names(tools: Vec<RouteTool>) -> (Vec<RouteTool>, Vec<ParseDiagnostic>) {
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<String, (String, String)> = HashMap::new();
+    let unique = tools
+        .into_iter()
+        .filter(|route_tool| {
+            let route = &route_tool.route_config;
+            let name = route_tool.tool.name.to_string();
+            if let Some((method, path)) = seen.get(&name) {
+                diagnostics.push(ParseDiagnostic::new(
+                    &route.path,
+                    &route.method,
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "tool name '{}' is also generated by {} {}, tool not registered",
+                        name, method, path
+                    ),
+                ));
+                return false;
+            }
+            seen.insert(name, (route.method.clone(), route.path.clone()));
+            true
+        })
+        .collect();
+    (unique, diagnostics)
+}
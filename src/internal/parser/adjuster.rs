@@ -3,11 +3,12 @@ use serde_yaml;
 use std::fs;
 use tracing::{debug, info, warn};
 
-use crate::internal::models::adjustments::McpAdjustments;
+use crate::internal::models::adjustments::{McpAdjustments, PathMatching, ToolOverride};
+use crate::internal::requester::JsonPath;
 
 /// Adjuster provides filtering and description overrides based on YAML configuration
 pub struct Adjuster {
-    pub adjustments: McpAdjustments,
+    adjustments: McpAdjustments,
 }
 
 impl Adjuster {
@@ -17,6 +18,11 @@ impl Adjuster {
             adjustments: McpAdjustments {
                 descriptions: Vec::new(),
                 routes: Vec::new(),
+                tools: Vec::new(),
+                path_matching: PathMatching::default(),
+                disabled_tools: Vec::new(),
+                exclude_routes: Vec::new(),
+                error_field: None,
             },
         }
     }
@@ -66,16 +72,12 @@ impl Adjuster {
 
         // Look through all route selections
         for selection in &self.adjustments.routes {
-            // Check if this path matches (handle trailing slashes)
-            let normalized_selection_path = selection.path.trim_end_matches('/');
-            let normalized_route = route.trim_end_matches('/');
-
             debug!(
                 "Comparing: selection='{}' vs route='{}'",
-                normalized_selection_path, normalized_route
+                selection.path, route
             );
 
-            if normalized_selection_path == normalized_route {
+            if self.paths_match(&selection.path, route) {
                 // Check if the method is in the list of selected methods
                 let method_exists = selection
                     .methods
@@ -93,35 +95,111 @@ impl Adjuster {
         false // Route not found in adjustments
     }
 
+    /// Check if a route/method or its tool has been explicitly excluded.
+    /// Exclusions win over route selections.
+    pub fn is_excluded(&self, route: &str, method: &str, tool_name: &str) -> bool {
+        if self
+            .adjustments
+            .disabled_tools
+            .iter()
+            .any(|t| t == tool_name)
+        {
+            debug!("Tool '{}' is disabled in adjustments", tool_name);
+            return true;
+        }
+
+        let excluded = self.adjustments.exclude_routes.iter().any(|selection| {
+            self.paths_match(&selection.path, route)
+                && selection
+                    .methods
+                    .iter()
+                    .any(|m| m.eq_ignore_ascii_case(method))
+        });
+        if excluded {
+            debug!("Route {} {} is excluded in adjustments", method, route);
+        }
+        excluded
+    }
+
     /// Get the updated description for a route/method if it exists
     pub fn get_description(&self, route: &str, method: &str, original_desc: &str) -> String {
-        if self.adjustments.descriptions.is_empty() {
-            return original_desc.to_string(); // Return original if no adjustments
-        }
+        self.description_for(route, method)
+            .unwrap_or(original_desc)
+            .to_string()
+    }
 
+    /// Description override configured for a route/method, if any
+    pub fn description_for(&self, route: &str, method: &str) -> Option<&str> {
         debug!("Looking for description override for {} {}", method, route);
 
-        // Look through all route descriptions
-        for desc in &self.adjustments.descriptions {
-            if desc.path == route {
-                // Look through all updates for this route
-                for update in &desc.updates {
-                    if update.method == method {
-                        debug!("Found description override for {} {}", method, route);
-                        return update.new_description.clone();
-                    }
-                }
-                break; // Found the route but no matching method
+        // Only the first entry matching the route is considered
+        let desc = self
+            .adjustments
+            .descriptions
+            .iter()
+            .find(|desc| self.paths_match(&desc.path, route))?;
+        let update = desc.updates.iter().find(|update| update.method == method)?;
+        debug!("Found description override for {} {}", method, route);
+        Some(&update.new_description)
+    }
+
+    /// Compare an adjustments path to a spec path, honouring the path_matching options.
+    /// Trailing slashes are always ignored.
+    pub fn paths_match(&self, adjustment_path: &str, route: &str) -> bool {
+        let options = &self.adjustments.path_matching;
+        let normalize = |path: &str| {
+            let mut path = path.trim_end_matches('/').to_string();
+            if options.ignore_param_names {
+                path = Self::erase_param_names(&path);
             }
-        }
+            if options.case_insensitive {
+                path = path.to_lowercase();
+            }
+            path
+        };
+
+        normalize(adjustment_path) == normalize(route)
+    }
+
+    /// Replace every `{name}` placeholder with `{}`
+    fn erase_param_names(path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.starts_with('{') && segment.ends_with('}') {
+                    "{}"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Get the overrides configured for a tool, if any
+    pub fn get_tool_override(&self, tool_name: &str) -> Option<&ToolOverride> {
+        self.adjustments.tools.iter().find(|t| t.name == tool_name)
+    }
 
-        original_desc.to_string()
+    /// Response error field for a tool: its own, else the top-level one
+    pub fn get_error_field(&self, tool_name: &str) -> Option<&JsonPath> {
+        self.get_tool_override(tool_name)
+            .and_then(|t| t.error_field.as_ref())
+            .or(self.adjustments.error_field.as_ref())
     }
 
     /// Get the number of route selections in the adjuster
     pub fn get_routes_count(&self) -> usize {
         self.adjustments.routes.len()
     }
+
+    /// Number of description overrides, counting each route/method pair once
+    pub fn descriptions_count(&self) -> usize {
+        self.adjustments
+            .descriptions
+            .iter()
+            .map(|desc| desc.updates.len())
+            .sum()
+    }
 }
 
 impl Default for Adjuster {
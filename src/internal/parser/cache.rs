@@ -0,0 +1,150 @@
+// src/internal/parser/cache.rs
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use super::refs::remote_spec_url;
+use super::types::{ParseDiagnostic, RouteTool, SpecInfo};
+use crate::internal::config::{ParserConfig, VERSION};
+
+/// Parse result stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSpec {
+    /// Key the entry was stored under, checked again on load
+    pub key: String,
+    pub route_tools: Vec<RouteTool>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    #[serde(default)]
+    pub info: Option<SpecInfo>,
+    /// Files the spec pulled in through `$ref`s; the entry is stale once any of them changes
+    #[serde(default)]
+    pub documents: Vec<CachedDocument>,
+}
+
+/// External `$ref` document a cached parse depended on, with the hash of what was read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedDocument {
+    pub path: PathBuf,
+    pub sha256: String,
+}
+
+impl CachedDocument {
+    fn read(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            sha256: format!("{:x}", Sha256::digest(&data)),
+        })
+    }
+
+    fn is_current(&self) -> bool {
+        Self::read(&self.path).is_ok_and(|current| current.sha256 == self.sha256)
+    }
+}
+
+/// ToolCache stores parsed tools on disk, keyed by a hash of everything that shapes them
+pub struct ToolCache {
+    dir: PathBuf,
+}
+
+impl ToolCache {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Hash of the spec, the adjustments, the parser config and the yas-mcp version.
+    /// Documents reached through external `$ref`s are only known after parsing, so they
+    /// are checked on load instead (see [`CachedSpec::documents`]).
+    pub fn key(spec: &[u8], adjustments: Option<&[u8]>, config: &ParserConfig) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(VERSION.as_bytes());
+        hasher.update([0]);
+        hasher.update(serde_json::to_vec(config).unwrap_or_default());
+        hasher.update([0]);
+        hasher.update(adjustments.unwrap_or_default());
+        hasher.update([0]);
+        hasher.update(spec);
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Cached parse result for the key; any missing, unreadable or mismatched entry is a miss
+    pub fn load(&self, key: &str) -> Option<CachedSpec> {
+        let path = self.entry_path(key);
+        let data = std::fs::read(&path).ok()?;
+        match serde_json::from_slice::<CachedSpec>(&data) {
+            Ok(cached) if cached.key == key => {
+                if cached.documents.iter().all(CachedDocument::is_current) {
+                    Some(cached)
+                } else {
+                    debug!(
+                        "Ignoring tool cache entry with changed $ref documents: {:?}",
+                        path
+                    );
+                    None
+                }
+            }
+            Ok(_) => {
+                debug!(
+                    "Ignoring tool cache entry with a mismatched key: {:?}",
+                    path
+                );
+                None
+            }
+            Err(e) => {
+                debug!("Ignoring unreadable tool cache entry {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Store a parse result under the key, along with the external documents it loaded.
+    /// Results that depend on remote documents are not stored, as those are fetched anew.
+    pub fn store(
+        &self,
+        key: &str,
+        route_tools: &[RouteTool],
+        diagnostics: &[ParseDiagnostic],
+        info: Option<&SpecInfo>,
+        external_documents: &[String],
+    ) -> Result<()> {
+        if external_documents
+            .iter()
+            .any(|location| remote_spec_url(location).is_some())
+        {
+            debug!("Not caching tools that depend on remote $ref documents");
+            return Ok(());
+        }
+        let documents = external_documents
+            .iter()
+            .map(|location| CachedDocument::read(Path::new(location)))
+            .collect::<Result<Vec<_>>>()?;
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache dir: {:?}", self.dir))?;
+
+        let cached = CachedSpec {
+            key: key.to_string(),
+            route_tools: route_tools.to_vec(),
+            diagnostics: diagnostics.to_vec(),
+            info: info.cloned(),
+            documents,
+        };
+        // Write then rename so a concurrent start never reads a partial entry
+        let path = self.entry_path(key);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(&cached)?)
+            .with_context(|| format!("Failed to write cache entry: {:?}", tmp_path))?;
+        std::fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to write cache entry: {:?}", path))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// src/internal/parser/diff.rs
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::internal::config::ParserConfig;
+use crate::internal::parser::types::{Parser, RouteTool};
+use crate::internal::parser::{Adjuster, SwaggerParser};
+
+/// Parse a spec (file path or http(s) URL) with optional adjustments and return its tools
+pub fn generate_tools(
+    spec: &str,
+    adjustments: Option<&str>,
+    config: ParserConfig,
+) -> Result<Vec<RouteTool>> {
+    let mut parser = SwaggerParser::with_config(Adjuster::new(), config);
+    parser
+        .init(spec, adjustments)
+        .with_context(|| format!("Failed to parse {}", spec))?;
+    Ok(parser.get_route_tools().to_vec())
+}
+
+/// Differences between the tools generated from two versions of a spec
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ToolSetDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ToolChange>,
+}
+
+/// Changes to one tool present in both versions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolChange {
+    pub tool: String,
+    pub changes: Vec<String>,
+}
+
+impl ToolSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Plain-text report, one line per added, removed or changed tool
+    pub fn report(&self) -> String {
+        if self.is_empty() {
+            return "No tool changes\n".to_string();
+        }
+        let mut report = String::new();
+        for name in &self.added {
+            report.push_str(&format!("+ {}\n", name));
+        }
+        for name in &self.removed {
+            report.push_str(&format!("- {}\n", name));
+        }
+        for change in &self.changed {
+            report.push_str(&format!("~ {}\n", change.tool));
+            for line in &change.changes {
+                report.push_str(&format!("    {}\n", line));
+            }
+        }
+        report.push_str(&format!(
+            "{} added, {} removed, {} changed\n",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        ));
+        report
+    }
+}
+
+/// Compare two tool sets by name: added and removed tools, and for tools in both, changes
+/// to the route and to the arguments of the input schema
+pub fn diff_tools(old: &[RouteTool], new: &[RouteTool]) -> ToolSetDiff {
+    let by_name = |tools: &[RouteTool]| -> BTreeMap<String, RouteTool> {
+        tools
+            .iter()
+            .map(|t| (t.tool.name.to_string(), t.clone()))
+            .collect()
+    };
+    let (old, new) = (by_name(old), by_name(new));
+
+    let mut diff = ToolSetDiff {
+        added: new
+            .keys()
+            .filter(|name| !old.contains_key(*name))
+            .cloned()
+            .collect(),
+        removed: old
+            .keys()
+            .filter(|name| !new.contains_key(*name))
+            .cloned()
+            .collect(),
+        changed: Vec::new(),
+    };
+    for (name, old_tool) in &old {
+        if let Some(new_tool) = new.get(name) {
+            let changes = tool_changes(old_tool, new_tool);
+            if !changes.is_empty() {
+                diff.changed.push(ToolChange {
+                    tool: name.clone(),
+                    changes,
+                });
+            }
+        }
+    }
+    diff
+}
+
+fn tool_changes(old: &RouteTool, new: &RouteTool) -> Vec<String> {
+    let mut changes = Vec::new();
+    let (old_route, new_route) = (&old.route_config, &new.route_config);
+    if (&old_route.method, &old_route.path) != (&new_route.method, &new_route.path) {
+        changes.push(format!(
+            "route: {} {} -> {} {}",
+            old_route.method, old_route.path, new_route.method, new_route.path
+        ));
+    }
+
+    let properties = |tool: &RouteTool| -> Map<String, Value> {
+        tool.tool
+            .input_schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let required = |tool: &RouteTool| -> BTreeSet<String> {
+        tool.tool
+            .input_schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let (old_props, new_props) = (properties(old), properties(new));
+    let (old_required, new_required) = (required(old), required(new));
+
+    let names: BTreeSet<&String> = old_props.keys().chain(new_props.keys()).collect();
+    for name in names {
+        match (old_props.get(name), new_props.get(name)) {
+            (None, Some(_)) if new_required.contains(name) => {
+                changes.push(format!("argument '{}' added (required)", name))
+            }
+            (None, Some(_)) => changes.push(format!("argument '{}' added", name)),
+            (Some(_), None) => changes.push(format!("argument '{}' removed", name)),
+            (Some(old_schema), Some(new_schema)) => {
+                if old_schema != new_schema {
+                    changes.push(format!("argument '{}' schema changed", name));
+                }
+                match (old_required.contains(name), new_required.contains(name)) {
+                    (false, true) => changes.push(format!("argument '{}' is now required", name)),
+                    (true, false) => changes.push(format!("argument '{}' is now optional", name)),
+                    _ => {}
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    changes
+}
@@ -0,0 +1,97 @@
+// src/internal/parser/example.rs
+
+use serde_json::{json, Map, Value};
+
+/// Nesting depth after which generated examples stop descending
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+/// Build a minimal example value from a JSON schema. Declared `example`, `default` and
+/// `enum` values are used first; otherwise each field gets a placeholder of its type.
+/// Objects that list `required` properties only get those.
+pub fn generate_example(schema: &Value) -> Value {
+    example_at_depth(schema, 0)
+}
+
+fn example_at_depth(schema: &Value, depth: usize) -> Value {
+    if depth > MAX_EXAMPLE_DEPTH {
+        return Value::Null;
+    }
+    let Some(schema) = schema.as_object() else {
+        return Value::Null;
+    };
+    if let Some(example) = schema.get("example").or_else(|| schema.get("default")) {
+        return example.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|v| v.first())
+    {
+        return first.clone();
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|v| v.first())
+        {
+            return example_at_depth(first, depth + 1);
+        }
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in all_of {
+            if let Value::Object(fields) = example_at_depth(part, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => json!(string_placeholder(schema)),
+        Some("integer") => json!(0),
+        Some("number") => json!(0.0),
+        Some("boolean") => json!(false),
+        Some("array") => match schema.get("items") {
+            Some(items) => json!([example_at_depth(items, depth + 1)]),
+            None => json!([]),
+        },
+        Some("object") | None if schema.contains_key("properties") => {
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let fields = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .filter(|(name, _)| {
+                            required.is_empty() || required.contains(&name.as_str())
+                        })
+                        .map(|(name, property)| {
+                            (name.clone(), example_at_depth(property, depth + 1))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Value::Object(fields)
+        }
+        Some("object") => json!({}),
+        _ => Value::Null,
+    }
+}
+
+fn string_placeholder(schema: &Map<String, Value>) -> &'static str {
+    match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("email") => "user@example.com",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("uri") | Some("url") => "https://example.com",
+        _ => "string",
+    }
+}
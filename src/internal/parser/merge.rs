@@ -0,0 +1,69 @@
+// src/internal/parser/merge.rs
+
+use std::collections::HashMap;
+
+use super::types::{DiagnosticSeverity, ParseDiagnostic, RouteTool};
+
+/// Tools parsed from one OpenAPI file
+pub struct SpecTools {
+    pub file: String,
+    pub tool_prefix: Option<String>,
+    pub route_tools: Vec<RouteTool>,
+}
+
+/// Merge the tools of several files into one set.
+///
+/// Tool names get their file's prefix. When two files define the same operation
+/// (method and path), the first file wins and the conflict is reported as a warning.
+/// Different operations that end up with the same tool name would replace each other
+/// in the registry, so only the first is kept and the others are reported as errors.
+pub fn merge_spec_tools(specs: Vec<SpecTools>) -> (Vec<RouteTool>, Vec<ParseDiagnostic>) {
+    let mut merged: Vec<RouteTool> = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut operations: HashMap<(String, String), String> = HashMap::new();
+    // Tool name -> file, method and path of the operation that has it
+    let mut names: HashMap<String, (String, String, String)> = HashMap::new();
+
+    for spec in specs {
+        for mut route_tool in spec.route_tools {
+            if let Some(prefix) = &spec.tool_prefix {
+                route_tool.tool.name = format!("{}_{}", prefix, route_tool.tool.name).into();
+            }
+
+            let method = route_tool.route_config.method.clone();
+            let path = route_tool.route_config.path.clone();
+            let name = route_tool.tool.name.to_string();
+
+            if let Some(first) = operations.get(&(method.clone(), path.clone())) {
+                diagnostics.push(ParseDiagnostic::new(
+                    &path,
+                    &method,
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "operation in {} conflicts with {}, keeping the first",
+                        spec.file, first
+                    ),
+                ));
+                continue;
+            }
+            if let Some((file, first_method, first_path)) = names.get(&name) {
+                diagnostics.push(ParseDiagnostic::new(
+                    &path,
+                    &method,
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "tool name '{}' in {} is also generated by {} {} in {}, tool not registered",
+                        name, spec.file, first_method, first_path, file
+                    ),
+                ));
+                continue;
+            }
+
+            operations.insert((method.clone(), path.clone()), spec.file.clone());
+            names.insert(name, (spec.file.clone(), method, path));
+            merged.push(route_tool);
+        }
+    }
+
+    (merged, diagnostics)
+}
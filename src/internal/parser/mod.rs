@@ -2,13 +2,25 @@
 
 pub mod _parser;
 pub mod adjuster;
+pub mod cache;
+pub mod diff;
+pub mod example;
+pub mod merge;
+pub mod refs;
+pub mod sanitize;
 pub mod types;
 
 // Export the Parser trait and RouteTool from types
-pub use types::{Parser, RouteTool};
+pub use types::{DiagnosticSeverity, ParseDiagnostic, Parser, RouteTool, SpecInfo};
 
 // Export SwaggerParser from parser (where it's actually implemented)
-pub use _parser::SwaggerParser;
+pub use _parser::{dedupe_tool_names, SwaggerParser};
 
 // Export Adjuster
 pub use adjuster::Adjuster;
+
+// Export multi-file merging
+pub use merge::{merge_spec_tools, SpecTools};
+
+// Export tool set diffing
+pub use diff::{diff_tools, generate_tools, ToolSetDiff};
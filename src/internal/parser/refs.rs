@@ -0,0 +1,361 @@
+// src/internal/parser/refs.rs
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
+use url::Url;
+
+/// Timeout for fetching a remote `$ref` document
+const REMOTE_REF_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delay before the first retry of a remote spec fetch; doubled after each failure
+const SPEC_FETCH_BACKOFF: Duration = Duration::from_millis(500);
+const SPEC_FETCH_MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Parse a JSON or YAML document
+pub fn parse_document(data: &[u8]) -> Result<Value> {
+    if let Ok(v) = serde_json::from_slice(data) {
+        Ok(v)
+    } else if let Ok(v) = serde_yaml::from_slice::<Value>(data) {
+        Ok(v)
+    } else {
+        Err(anyhow!("Failed to parse spec as JSON or YAML"))
+    }
+}
+
+/// The spec location as a URL when it is http(s) rather than a file path
+pub fn remote_spec_url(location: &str) -> Option<Url> {
+    Url::parse(location)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// Fetch a remote spec, retrying transient failures up to `retries` times with backoff
+pub fn fetch_spec(url: &Url, retries: u32) -> Result<Vec<u8>> {
+    let mut delay = SPEC_FETCH_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        match fetch(url) {
+            Ok(data) => return Ok(data),
+            Err(e) if attempt < retries && is_transient(&e) => {
+                attempt += 1;
+                warn!(
+                    "Fetching spec {} failed (attempt {} of {}): {:#}; retrying in {:?}",
+                    url,
+                    attempt,
+                    retries + 1,
+                    e,
+                    delay
+                );
+                std::thread::sleep(delay);
+                delay = (delay * 2).min(SPEC_FETCH_MAX_BACKOFF);
+            }
+            Err(e) => {
+                return Err(e.context(format!(
+                    "Failed to fetch spec {} after {} attempt(s)",
+                    url,
+                    attempt + 1
+                )))
+            }
+        }
+    }
+}
+
+/// Connection errors, timeouts, 429 and 5xx may pass; other client errors will not
+fn is_transient(error: &anyhow::Error) -> bool {
+    match error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+    {
+        Some(status) => status.is_server_error() || status.as_u16() == 429,
+        None => true,
+    }
+}
+
+/// Replace `paths` entries that are local `$ref`s (e.g. `#/components/pathItems/Tasks`)
+/// with the path item they point to; sibling keys of the ref override the target's.
+/// Returns one message per ref that could not be resolved; those entries are kept.
+pub fn inline_path_item_refs(spec: &mut Value) -> Vec<String> {
+    let mut issues = Vec::new();
+    let paths: Vec<String> = match spec.get("paths").and_then(Value::as_object) {
+        Some(paths) => paths.keys().cloned().collect(),
+        None => return issues,
+    };
+
+    for path in paths {
+        let mut seen = Vec::new();
+        // Follow chains of refs until an inline path item is reached
+        while let Some(entry) = spec["paths"].get(&path) {
+            let Some(reference) = entry.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            let reference = reference.to_string();
+            let Some(pointer) = reference.strip_prefix('#') else {
+                break;
+            };
+            if seen.contains(&reference) {
+                issues.push(format!(
+                    "path item $ref '{}' for {} is circular",
+                    reference, path
+                ));
+                break;
+            }
+
+            let Some(Value::Object(target)) = spec.pointer(pointer).cloned() else {
+                issues.push(format!(
+                    "path item $ref '{}' for {} does not point to a path item",
+                    reference, path
+                ));
+                break;
+            };
+            seen.push(reference);
+
+            let mut item = target;
+            if let Some(Value::Object(siblings)) = spec["paths"].get(&path) {
+                for (key, value) in siblings.iter().filter(|(k, _)| *k != "$ref") {
+                    item.insert(key.clone(), value.clone());
+                }
+            }
+            spec["paths"][&path] = Value::Object(item);
+        }
+    }
+
+    issues
+}
+
+/// Where a document was loaded from; relative refs inside it resolve against this
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DocSource {
+    File(PathBuf),
+    Url(Url),
+    /// The main spec when it was not read from a file (e.g. stdin)
+    Inline,
+}
+
+/// RefResolver inlines `$ref`s that point outside the main spec document.
+///
+/// `./models/user.yaml#/User` is loaded relative to the referring document and replaced
+/// by the target; refs inside loaded documents are followed the same way. Local refs of
+/// the main spec (`#/components/...`) are left for the parser. Relative file refs may
+/// not leave the main spec's directory, and URLs are only fetched when allowed.
+pub struct RefResolver {
+    main: DocSource,
+    root: Option<PathBuf>,
+    allow_remote: bool,
+    documents: HashMap<DocSource, Value>,
+    /// Refs currently being inlined, to stop on cycles
+    stack: Vec<(DocSource, String)>,
+    issues: Vec<String>,
+}
+
+impl RefResolver {
+    /// Resolver for a spec read from `spec_path`, or from a stream when `None`
+    /// (relative refs then resolve against the working directory)
+    pub fn new(spec_path: Option<&Path>, allow_remote: bool) -> Self {
+        let main = match spec_path.and_then(|p| p.canonicalize().ok()) {
+            Some(path) => DocSource::File(path),
+            None => DocSource::Inline,
+        };
+        Self::with_main(main, allow_remote)
+    }
+
+    /// Resolver for a spec fetched from `url`; its relative refs are URLs too,
+    /// so they are only fetched when remote refs are allowed
+    pub fn for_url(url: Url, allow_remote: bool) -> Self {
+        Self::with_main(DocSource::Url(url), allow_remote)
+    }
+
+    fn with_main(main: DocSource, allow_remote: bool) -> Self {
+        let root = match &main {
+            DocSource::File(path) => path.parent().map(Path::to_path_buf),
+            _ => std::env::current_dir()
+                .ok()
+                .and_then(|d| d.canonicalize().ok()),
+        };
+
+        Self {
+            main,
+            root,
+            allow_remote,
+            documents: HashMap::new(),
+            stack: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    /// Inline every external `$ref` in the spec; refs that cannot be resolved are kept
+    pub fn resolve(&mut self, spec: &mut Value) {
+        let main = self.main.clone();
+        self.walk(spec, &main, true);
+    }
+
+    /// Problems met while resolving, one message per unresolved ref
+    pub fn issues(&self) -> &[String] {
+        &self.issues
+    }
+
+    /// Files and URLs loaded to resolve refs, other than the main spec, sorted
+    pub fn loaded_documents(&self) -> Vec<String> {
+        let mut loaded: Vec<String> = self
+            .documents
+            .keys()
+            .filter(|source| **source != self.main)
+            .filter_map(|source| match source {
+                DocSource::File(path) => Some(path.display().to_string()),
+                DocSource::Url(url) => Some(url.to_string()),
+                DocSource::Inline => None,
+            })
+            .collect();
+        loaded.sort();
+        loaded
+    }
+
+    fn walk(&mut self, value: &mut Value, source: &DocSource, in_main: bool) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    let reference = reference.clone();
+                    if let Some(resolved) = self.resolve_ref(&reference, source, in_main) {
+                        *value = resolved;
+                    }
+                    return;
+                }
+                for child in map.values_mut() {
+                    self.walk(child, source, in_main);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.walk(item, source, in_main);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Target of the ref with its own refs inlined; `None` keeps the ref as written
+    fn resolve_ref(&mut self, reference: &str, source: &DocSource, in_main: bool) -> Option<Value> {
+        let (location, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        if location.is_empty() && in_main {
+            return None;
+        }
+
+        let target = if location.is_empty() {
+            source.clone()
+        } else {
+            match self.locate(location, source) {
+                Ok(target) => target,
+                Err(e) => {
+                    self.issues
+                        .push(format!("$ref '{}' not resolved: {:#}", reference, e));
+                    return None;
+                }
+            }
+        };
+
+        let key = (target.clone(), pointer.to_string());
+        if self.stack.contains(&key) {
+            self.issues
+                .push(format!("$ref '{}' is circular, left unresolved", reference));
+            return None;
+        }
+
+        let mut resolved = match self.load(&target).and_then(|doc| {
+            doc.pointer(pointer)
+                .cloned()
+                .ok_or_else(|| anyhow!("'#{}' does not exist in the document", pointer))
+        }) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                self.issues
+                    .push(format!("$ref '{}' not resolved: {:#}", reference, e));
+                return None;
+            }
+        };
+
+        self.stack.push(key);
+        let in_main = target == self.main;
+        self.walk(&mut resolved, &target, in_main);
+        self.stack.pop();
+        Some(resolved)
+    }
+
+    /// Document a ref location points to, relative to the referring document
+    fn locate(&self, location: &str, source: &DocSource) -> Result<DocSource> {
+        if let Ok(url) = Url::parse(location) {
+            return self.remote(url);
+        }
+
+        let base_dir = match source {
+            DocSource::Url(base) => {
+                let url = base
+                    .join(location)
+                    .with_context(|| format!("invalid relative URL '{}'", location))?;
+                return self.remote(url);
+            }
+            DocSource::File(path) => path.parent().map(Path::to_path_buf),
+            DocSource::Inline => self.root.clone(),
+        }
+        .ok_or_else(|| anyhow!("no directory to resolve '{}' against", location))?;
+
+        let path = base_dir
+            .join(location)
+            .canonicalize()
+            .with_context(|| format!("cannot read '{}'", location))?;
+        match &self.root {
+            Some(root) if path.starts_with(root) => Ok(DocSource::File(path)),
+            _ => Err(anyhow!("'{}' is outside the spec directory", location)),
+        }
+    }
+
+    fn remote(&self, url: Url) -> Result<DocSource> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(anyhow!("unsupported URL scheme '{}'", url.scheme()));
+        }
+        if !self.allow_remote {
+            return Err(anyhow!(
+                "remote refs are disabled; set parser.remote_refs to fetch {}",
+                url
+            ));
+        }
+        Ok(DocSource::Url(url))
+    }
+
+    /// Parsed document, loaded once per resolver
+    fn load(&mut self, source: &DocSource) -> Result<&Value> {
+        if !self.documents.contains_key(source) {
+            let data = match source {
+                DocSource::File(path) => {
+                    std::fs::read(path).with_context(|| format!("cannot read {:?}", path))?
+                }
+                DocSource::Url(url) => fetch(url)?,
+                DocSource::Inline => return Err(anyhow!("the main spec has no file to reload")),
+            };
+            self.documents
+                .insert(source.clone(), parse_document(&data)?);
+        }
+        Ok(&self.documents[source])
+    }
+}
+
+/// Fetch a remote document on a helper thread, so parsing works inside or outside a runtime
+fn fetch(url: &Url) -> Result<Vec<u8>> {
+    let target = url.clone();
+    std::thread::spawn(move || -> Result<Vec<u8>> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async {
+            let client = reqwest::Client::builder()
+                .timeout(REMOTE_REF_TIMEOUT)
+                .build()?;
+            let response = client.get(target).send().await?.error_for_status()?;
+            Ok(response.bytes().await?.to_vec())
+        })
+    })
+    .join()
+    .map_err(|_| anyhow!("fetching {} panicked", url))?
+}
@@ -0,0 +1,93 @@
+// src/internal/parser/sanitize.rs
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Strip HTML and Markdown markup meant for rendered docs from a description.
+///
+/// Code spans (`like this`) are kept verbatim and URLs survive: autolinks lose their
+/// angle brackets and `[text](url)` becomes `text (url)`. The result is a single line.
+pub fn sanitize_description(text: &str) -> String {
+    let lines: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !is_table_separator(line))
+        .map(clean_line)
+        .collect();
+    let joined = lines.join(" ");
+
+    // Backticks delimit code spans; odd segments are code and stay untouched
+    let segments: Vec<&str> = joined.split('`').collect();
+    let balanced = segments.len() % 2 == 1;
+    let last = segments.len() - 1;
+    let mut result = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if i % 2 == 1 && (balanced || i != last) {
+            result.push('`');
+            result.push_str(segment);
+            result.push('`');
+        } else {
+            if i % 2 == 1 {
+                // Unmatched trailing backtick, keep it literally
+                result.push('`');
+            }
+            result.push_str(&clean_inline(segment));
+        }
+    }
+
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Markdown table rule such as `|---|:---:|`
+fn is_table_separator(line: &str) -> bool {
+    line.contains('-') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Drop block-level markup: headings, quotes, bullets and table pipes
+fn clean_line(line: &str) -> String {
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    let block = BLOCK.get_or_init(|| Regex::new(r"^(#{1,6}\s+|>\s?|[-*+]\s+)").unwrap());
+
+    let line = block.replace(line, "");
+    if line.starts_with('|') && line.ends_with('|') && line.len() > 1 {
+        line[1..line.len() - 1]
+            .split('|')
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .collect::<Vec<_>>()
+            .join("; ")
+    } else {
+        line.to_string()
+    }
+}
+
+/// Drop inline markup outside code spans
+fn clean_inline(text: &str) -> String {
+    static AUTOLINK: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    static IMAGE: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static BOLD: OnceLock<Regex> = OnceLock::new();
+    static BOLD_UNDERSCORE: OnceLock<Regex> = OnceLock::new();
+
+    let autolink = AUTOLINK.get_or_init(|| Regex::new(r"<((?:https?|mailto):[^>\s]+)>").unwrap());
+    let tag = TAG.get_or_init(|| Regex::new(r"</?[A-Za-z][^<>]*>").unwrap());
+    let image = IMAGE.get_or_init(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)[^)]*\)").unwrap());
+    let bold = BOLD.get_or_init(|| Regex::new(r"\*\*([^*]+)\*\*").unwrap());
+    let bold_underscore = BOLD_UNDERSCORE.get_or_init(|| Regex::new(r"__([^_]+)__").unwrap());
+
+    let text = autolink.replace_all(text, "$1");
+    let text = tag.replace_all(&text, " ");
+    let text = image.replace_all(&text, "$1");
+    let text = link.replace_all(&text, "$1 ($2)");
+    let text = bold.replace_all(&text, "$1");
+    let text = bold_underscore.replace_all(&text, "$1");
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
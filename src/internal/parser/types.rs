@@ -1,4 +1,6 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io::Read;
 
 // Assuming we'll create these modules later
@@ -8,12 +10,65 @@ use crate::internal::requester::RouteConfig;
 pub type McpTool = rmcp::model::Tool;
 
 /// RouteTool combines a route configuration with its corresponding MCP tool
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteTool {
     pub route_config: RouteConfig,
     pub tool: rmcp::model::Tool,
 }
 
+/// SpecInfo is the `info` block of a parsed specification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecInfo {
+    pub title: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Severity of a parse diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// ParseDiagnostic describes a spec quality issue found while generating tools
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    pub path: String,
+    pub method: String,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+impl ParseDiagnostic {
+    pub fn new(
+        path: &str,
+        method: &str,
+        severity: DiagnosticSeverity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            method: method.to_string(),
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:?}] {} {}: {}",
+            self.severity, self.method, self.path, self.message
+        )
+    }
+}
+
 /// Parser handles parsing of Swagger/OpenAPI specifications
 pub trait Parser: Send + Sync {
     /// Init parses a Swagger/OpenAPI specification from a file
@@ -26,4 +81,24 @@ pub trait Parser: Send + Sync {
 
     /// GetRouteTools returns the parsed route tools
     fn get_route_tools(&self) -> &[RouteTool];
+
+    /// LoadAdjustments loads an adjustments file to apply to the next parse
+    fn load_adjustments(&mut self, _adjustments_file: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// GetDiagnostics returns the spec issues collected by the last parse
+    fn get_diagnostics(&self) -> &[ParseDiagnostic] {
+        &[]
+    }
+
+    /// GetSpecInfo returns the `info` block of the last parsed spec
+    fn get_spec_info(&self) -> Option<SpecInfo> {
+        None
+    }
+
+    /// GetExternalDocuments returns the files and URLs the last parse loaded through `$ref`s
+    fn get_external_documents(&self) -> &[String] {
+        &[]
+    }
 }
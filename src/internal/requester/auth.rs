@@ -0,0 +1,139 @@
+// src/internal/requester/auth.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::internal::config::{AuthType, EndpointConfig};
+
+/// Header used for API keys when neither the config nor the spec names one
+pub const DEFAULT_API_KEY_HEADER: &str = "X-API-Key";
+
+/// Where an API key is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+/// AuthScheme describes how a request is authenticated; the credentials come from
+/// `endpoint.auth_config` (`token`, `username`/`password` or `api_key`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthScheme {
+    Bearer,
+    Basic,
+    ApiKey {
+        name: String,
+        location: ApiKeyLocation,
+    },
+    /// OAuth2 access token, sent as a bearer token
+    OAuth2,
+}
+
+impl AuthScheme {
+    /// Scheme configured on the endpoint, or `None` when `auth_type` is `none`.
+    /// API key placement comes from `auth_config.name`/`auth_config.in`, then from `spec`.
+    pub fn from_endpoint(endpoint: &EndpointConfig, spec: Option<&AuthScheme>) -> Option<Self> {
+        match endpoint.auth_type {
+            AuthType::None => None,
+            AuthType::Bearer => Some(AuthScheme::Bearer),
+            AuthType::Basic => Some(AuthScheme::Basic),
+            AuthType::OAuth2 => Some(AuthScheme::OAuth2),
+            AuthType::ApiKey => {
+                let (spec_name, spec_location) = match spec {
+                    Some(AuthScheme::ApiKey { name, location }) => (Some(name.as_str()), *location),
+                    _ => (None, ApiKeyLocation::Header),
+                };
+                let name = endpoint
+                    .auth_config
+                    .get("name")
+                    .map(String::as_str)
+                    .or(spec_name)
+                    .unwrap_or(DEFAULT_API_KEY_HEADER)
+                    .to_string();
+                let location = match endpoint.auth_config.get("in").map(String::as_str) {
+                    Some("query") => ApiKeyLocation::Query,
+                    Some(_) => ApiKeyLocation::Header,
+                    None => spec_location,
+                };
+                Some(AuthScheme::ApiKey { name, location })
+            }
+        }
+    }
+
+    /// Add the credentials to the request; a scheme whose credentials are not configured
+    /// leaves the request unchanged
+    pub fn apply(
+        &self,
+        request: reqwest::RequestBuilder,
+        credentials: &HashMap<String, String>,
+    ) -> reqwest::RequestBuilder {
+        match self {
+            AuthScheme::Bearer | AuthScheme::OAuth2 => match credentials.get("token") {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            },
+            AuthScheme::Basic => match credentials.get("username") {
+                Some(username) => request.basic_auth(username, credentials.get("password")),
+                None => request,
+            },
+            AuthScheme::ApiKey { name, location } => match credentials.get("api_key") {
+                Some(key) => match location {
+                    ApiKeyLocation::Header => request.header(name.as_str(), key),
+                    ApiKeyLocation::Query => request.query(&[(name, key)]),
+                },
+                None => request,
+            },
+        }
+    }
+}
+
+/// Required scopes missing from those granted to the configured OAuth2 token, listed
+/// space-separated in `auth_config.scope`. Nothing is missing when the grant is unknown.
+pub fn missing_scopes(endpoint: &EndpointConfig, required: &[String]) -> Vec<String> {
+    let Some(granted) = endpoint.auth_config.get("scope") else {
+        return Vec::new();
+    };
+    let granted: Vec<&str> = granted.split_whitespace().collect();
+    required
+        .iter()
+        .filter(|scope| !granted.contains(&scope.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// RouteAuth is the auth requirement the spec resolves for one operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteAuth {
+    /// The spec declares no requirement; the endpoint's `auth_type` applies
+    #[default]
+    Endpoint,
+    /// From the spec's global `security`; a configured `auth_type` wins
+    Global(AuthScheme),
+    /// From the operation's own `security`; wins over the configured `auth_type`
+    Operation(AuthScheme),
+    /// The operation opts out with `security: []`; no auth scheme is applied
+    Public,
+}
+
+impl RouteAuth {
+    /// Scheme to apply to the route's requests, if any
+    pub fn resolve(&self, endpoint: &EndpointConfig) -> Option<AuthScheme> {
+        match self {
+            RouteAuth::Endpoint => AuthScheme::from_endpoint(endpoint, None),
+            RouteAuth::Global(scheme) => {
+                AuthScheme::from_endpoint(endpoint, Some(scheme)).or_else(|| Some(scheme.clone()))
+            }
+            // Configured name/in still correct where an operation's API key goes
+            RouteAuth::Operation(scheme @ AuthScheme::ApiKey { .. })
+                if endpoint.auth_type == AuthType::ApiKey =>
+            {
+                AuthScheme::from_endpoint(endpoint, Some(scheme))
+            }
+            RouteAuth::Operation(scheme) => Some(scheme.clone()),
+            RouteAuth::Public => None,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// src/internal/requester/coercion.rs
+
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Coerce stringly-typed arguments to the scalar types declared in their schemas.
+///
+/// Clients frequently send `"1"` for an integer or `"false"` for a boolean. Values are
+/// only rewritten when the declared type is number/integer/boolean and the string parses
+/// cleanly; strings, unknown types and unparseable values are left untouched.
+pub fn coerce_arguments(arguments: &mut Map<String, Value>, schemas: &HashMap<String, Value>) {
+    for (name, value) in arguments.iter_mut() {
+        if let Some(schema) = schemas.get(name) {
+            coerce_value(name, value, schema);
+        }
+    }
+}
+
+fn coerce_value(path: &str, value: &mut Value, schema: &Value) {
+    let schema_type = schema.get("type").and_then(|t| t.as_str());
+
+    match (schema_type, &mut *value) {
+        (Some("number") | Some("integer"), Value::String(s)) => {
+            if let Some(number) = parse_number(s.trim()) {
+                debug!("Coerced argument '{}' from string {:?} to number", path, s);
+                *value = Value::Number(number);
+            }
+        }
+        (Some("boolean"), Value::String(s)) => {
+            let coerced = match s.trim().to_ascii_lowercase().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            };
+            if let Some(b) = coerced {
+                debug!("Coerced argument '{}' from string {:?} to boolean", path, s);
+                *value = Value::Bool(b);
+            }
+        }
+        (Some("object"), Value::Object(map)) => {
+            if let Some(Value::Object(props)) = schema.get("properties") {
+                for (key, inner) in map.iter_mut() {
+                    if let Some(inner_schema) = props.get(key) {
+                        coerce_value(&format!("{}.{}", path, key), inner, inner_schema);
+                    }
+                }
+            }
+        }
+        (Some("array"), Value::Array(items)) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter_mut().enumerate() {
+                    coerce_value(&format!("{}[{}]", path, i), item, item_schema);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_number(s: &str) -> Option<Number> {
+    if let Ok(i) = s.parse::<i64>() {
+        return Some(Number::from(i));
+    }
+    s.parse::<f64>().ok().and_then(Number::from_f64)
+}
@@ -1,13 +1,26 @@
 use anyhow::{anyhow, Context, Result};
+use regex::Regex;
 use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tracing::info;
-
-use crate::internal::config::_config::EndpointConfig;
-use crate::internal::requester::RouteExecutor;
+use tracing::{info, warn};
+
+use crate::internal::auth::token_manager::OAuth2TokenManager;
+use crate::internal::config::_config::{AuthType, EndpointConfig};
+use crate::internal::requester::auth::{missing_scopes, ApiKeyLocation, AuthScheme};
+use crate::internal::requester::coercion::coerce_arguments;
+use crate::internal::requester::interceptor::Interceptor;
+use crate::internal::requester::limiter::{host_key, ConcurrencyLimiter};
+use crate::internal::requester::request::{
+    build_url, rename_arguments, take_accept_override, take_dry_run_flag, take_if_none_match,
+    take_raw_body, take_traceparent, RequestParts, RAW_BODY_ARG,
+};
+use crate::internal::requester::signing::RequestSigner;
+use crate::internal::requester::trace::TRACEPARENT_HEADER;
+use crate::internal::requester::{Requester, RouteConfig, RouteExecutor};
+use crate::internal::server::tool::ToolCallError;
 
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
@@ -16,52 +29,192 @@ pub struct HttpResponse {
     pub headers: HashMap<String, String>,
 }
 
+/// Default per-request timeout for upstream calls
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// User-Agent for upstream calls when the endpoint does not set one
+pub const DEFAULT_USER_AGENT: &str = concat!("yas-mcp/", env!("CARGO_PKG_VERSION"));
+
 #[derive(Clone)]
 pub struct HttpRequester {
     client: Client,
     service_cfg: EndpointConfig,
+    interceptor: Option<Arc<dyn Interceptor>>,
+    timeout: Duration,
+    limiter: Arc<ConcurrencyLimiter>,
+    token_manager: Option<Arc<OAuth2TokenManager>>,
+    trace_context: bool,
 }
 
 impl HttpRequester {
     pub fn new(service_cfg: &EndpointConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .context("Failed to create HTTP client")?;
+        // Some APIs (e.g. GitHub) reject requests without a User-Agent
+        let mut builder = Client::builder().user_agent(
+            service_cfg
+                .user_agent
+                .as_deref()
+                .unwrap_or(DEFAULT_USER_AGENT),
+        );
+        if let Some(max_idle) = service_cfg.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_secs) = service_cfg.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(Duration::from_secs(idle_secs));
+        }
+        if service_cfg.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(interval_secs) = service_cfg.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(Duration::from_secs(interval_secs))
+                .http2_keep_alive_while_idle(true);
+        }
+        let client = builder.build().context("Failed to create HTTP client")?;
+        let token_manager = match service_cfg.auth_type {
+            AuthType::OAuth2 => OAuth2TokenManager::from_auth_config(
+                &service_cfg.auth_config,
+                service_cfg.token_refresh_skew.map(Duration::from_secs),
+            )
+            .transpose()
+            .context("Failed to create OAuth2 token manager")?
+            .map(Arc::new),
+            _ => None,
+        };
 
         Ok(Self {
             client,
             service_cfg: service_cfg.clone(),
+            interceptor: None,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            limiter: Arc::new(ConcurrencyLimiter::new(service_cfg.max_concurrent_requests)),
+            token_manager,
+            trace_context: false,
         })
     }
 
-    pub fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
-        self.client = Client::builder()
-            .timeout(timeout)
-            .build()
-            .context("Failed to recreate HTTP client with new timeout")?;
+    /// Send the `__traceparent` argument upstream as the `traceparent` header; when off,
+    /// the argument is dropped
+    pub fn with_trace_context(mut self, enabled: bool) -> Self {
+        self.trace_context = enabled;
+        self
+    }
+
+    /// Fetch OAuth2 access tokens from `manager` for every request instead of `auth_config.token`
+    pub fn with_token_manager(mut self, manager: Arc<OAuth2TokenManager>) -> Self {
+        self.token_manager = Some(manager);
+        self
+    }
+
+    /// Run the given interceptor around every upstream call made by route executors
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptor = Some(interceptor);
+        self
+    }
+
+    /// Change the per-request timeout for executors built afterwards.
+    /// The client, and with it the connection pool, is kept.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Limiter shared by all executors, e.g. to report requests in flight
+    pub fn limiter(&self) -> Arc<ConcurrencyLimiter> {
+        self.limiter.clone()
+    }
+
+    /// Make sure requests would go to an absolute URL rather than a relative one
+    fn validate_base_url(base_url: &str) -> Result<()> {
+        if base_url.trim().is_empty() {
+            return Err(anyhow!(
+                "no API endpoint configured; pass --endpoint or set servers in the spec"
+            ));
+        }
+        url::Url::parse(base_url).map_err(|e| {
+            anyhow!(
+                "API endpoint '{}' is not an absolute URL ({}); pass --endpoint like http://localhost:8080",
+                base_url,
+                e
+            )
+        })?;
         Ok(())
     }
 
-    pub fn build_route_executor(
-        &self,
-        config: &crate::internal::requester::RouteConfig,
-    ) -> Result<RouteExecutor> {
-        let base_url = self.service_cfg.base_url.clone();
+    pub fn build_route_executor(&self, config: &RouteConfig) -> Result<RouteExecutor> {
+        // Routes with their own server go there instead of the endpoint base URL
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| self.service_cfg.base_url.clone());
+        Self::validate_base_url(&base_url)?;
         let method = config.method.clone();
-        let path = config.path.clone();
+        // Tool names come from the spec path; only the request uses the rewritten one
+        let path = Self::rewrite_path(
+            &config.path,
+            self.service_cfg.path_prefix_strip.as_deref(),
+            self.service_cfg.path_prefix_add.as_deref(),
+        );
+        // Environment variables are read once, when the tool is registered
+        let path = match &self.service_cfg.path_template {
+            Some(template) => Self::expand_path_template(template, &path)?,
+            None => path,
+        };
         let mut static_headers = config.headers.clone();
 
         // Capture known param names from config to separate them
         // Fields are Vec<String>, so we just clone them
         let known_header_params = config.method_config.header_params.clone();
         let known_query_params = config.method_config.query_params.clone();
+        let param_schemas = config.method_config.param_schemas.clone();
+        let content_type = config.method_config.content_type.clone();
+        let argument_aliases = config.method_config.argument_aliases.clone();
+        // Per-tool defaults win over endpoint ones; sorted so the URL is stable
+        let mut default_query: Vec<(String, String)> = self
+            .service_cfg
+            .default_query
+            .iter()
+            .filter(|(key, _)| !config.method_config.default_query.contains_key(*key))
+            .chain(&config.method_config.default_query)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        default_query.sort();
 
         for (key, value) in &self.service_cfg.headers {
             static_headers.entry(key.clone()).or_insert(value.clone());
         }
 
+        // Operation-level security wins over auth_type, which wins over global security
+        let auth = config.auth.resolve(&self.service_cfg).filter(|scheme| {
+            // An explicit Authorization header is left alone
+            matches!(scheme, AuthScheme::ApiKey { .. })
+                || !static_headers
+                    .keys()
+                    .any(|k| k.eq_ignore_ascii_case("authorization"))
+        });
+        if auth == Some(AuthScheme::OAuth2) {
+            let missing = missing_scopes(&self.service_cfg, &config.method_config.required_scopes);
+            if !missing.is_empty() {
+                warn!(
+                    "{} {} requires OAuth2 scope(s) {} not granted to the configured token",
+                    config.method,
+                    config.path,
+                    missing.join(", ")
+                );
+            }
+        }
+        let credentials = self.service_cfg.auth_config.clone();
+        let signer = RequestSigner::from_endpoint(&self.service_cfg);
+
         let client = self.client.clone();
+        let timeout = self.timeout;
+        let dry_run = self.service_cfg.dry_run;
+        let interceptor = self.interceptor.clone();
+        let limiter = self.limiter.clone();
+        let token_manager = self.token_manager.clone();
+        let trace_context = self.trace_context;
 
         let executor: RouteExecutor = Arc::new(move |params_json: &str| {
             let base_url = base_url.clone();
@@ -69,10 +222,20 @@ impl HttpRequester {
             let path = path.clone();
             let static_headers = static_headers.clone();
             let client = client.clone();
+            let interceptor = interceptor.clone();
+            let limiter = limiter.clone();
+            let token_manager = token_manager.clone();
+            let auth = auth.clone();
+            let credentials = credentials.clone();
+            let signer = signer.clone();
 
             // Capture these for the closure
             let known_header_params = known_header_params.clone();
             let known_query_params = known_query_params.clone();
+            let param_schemas = param_schemas.clone();
+            let content_type = content_type.clone();
+            let argument_aliases = argument_aliases.clone();
+            let default_query = default_query.clone();
 
             let params_json = params_json.to_string();
 
@@ -83,34 +246,52 @@ impl HttpRequester {
 
                 // Convert to object for manipulation (so we can remove fields as we use them)
                 let mut active_params = params_value.as_object().cloned().unwrap_or_default();
+                let dry_run = take_dry_run_flag(&mut active_params) || dry_run;
+                let accept = take_accept_override(&mut active_params);
+                let if_none_match = take_if_none_match(&mut active_params);
+                let raw_body = take_raw_body(&mut active_params);
+                let traceparent = take_traceparent(&mut active_params).filter(|_| trace_context);
+                let has_raw_body = raw_body.is_some();
+
+                // Coerce "1"/"true" style strings to the types declared in the schema
+                coerce_arguments(&mut active_params, &param_schemas);
+                // Schemas are keyed by the exposed names, so rename only after coercing
+                rename_arguments(&mut active_params, &argument_aliases);
 
                 // 1. Build URL & Handle Path Params
-                // (Iterate all params to see if they match URL placeholders)
-                let mut url = format!("{}{}", base_url, path);
+                // (Iterate all params to see if they match path placeholders)
+                let mut route_path = path.clone();
 
                 // We collect keys to remove to avoid modification during iteration
                 let mut used_keys = Vec::new();
                 for (key, value) in &active_params {
-                    if let serde_json::Value::String(str_value) = value {
-                        let placeholder = format!("{{{}}}", key);
-                        if url.contains(&placeholder) {
-                            url = url.replace(&placeholder, str_value);
-                            used_keys.push(key.clone());
-                        }
+                    // Coerced path params may be numbers/bools, so render those too
+                    let str_value = match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        serde_json::Value::Number(n) => n.to_string(),
+                        serde_json::Value::Bool(b) => b.to_string(),
+                        _ => continue,
+                    };
+                    let placeholder = format!("{{{}}}", key);
+                    if route_path.contains(&placeholder) {
+                        route_path = route_path.replace(&placeholder, &str_value);
+                        used_keys.push(key.clone());
                     }
                 }
                 // Remove path params from map so they aren't sent in body/query
                 for k in used_keys {
                     active_params.remove(&k);
                 }
+                // Query parameters of the base URL stay; route query params are appended
+                let url = build_url(&base_url, &route_path)?;
 
                 // 2. Build Request
                 let mut request_builder = match method.as_str() {
-                    "GET" => client.get(&url),
-                    "POST" => client.post(&url),
-                    "PUT" => client.put(&url),
-                    "DELETE" => client.delete(&url),
-                    "PATCH" => client.patch(&url),
+                    "GET" => client.get(url),
+                    "POST" => client.post(url),
+                    "PUT" => client.put(url),
+                    "DELETE" => client.delete(url),
+                    "PATCH" => client.patch(url),
                     _ => return Err(anyhow!("Unsupported HTTP method: {}", method)),
                 };
 
@@ -118,6 +299,24 @@ impl HttpRequester {
                 for (key, value) in &static_headers {
                     request_builder = request_builder.header(key, value);
                 }
+                // The managed token this request carries, kept to retry once if rejected
+                let mut managed_token = None;
+                if let Some(auth) = &auth {
+                    let mut credentials = credentials;
+                    // Asked per request so a token is refreshed before it expires
+                    if let (AuthScheme::OAuth2, Some(manager), false) =
+                        (auth, &token_manager, dry_run)
+                    {
+                        let token = manager.access_token().await?;
+                        credentials.insert("token".to_string(), token.clone());
+                        managed_token = Some(token);
+                    }
+                    request_builder = auth.apply(request_builder, &credentials);
+                }
+
+                if let Some(traceparent) = &traceparent {
+                    request_builder = request_builder.header(TRACEPARENT_HEADER, traceparent);
+                }
 
                 // 4. Handle Dynamic Headers
                 for header_key in &known_header_params {
@@ -146,30 +345,214 @@ impl HttpRequester {
                 }
 
                 // 6. Handle Remaining Params (Body vs Query Fallback)
+                if let Some(raw_body) = raw_body {
+                    // Escape hatch: the body is sent as given and leftovers are not encoded
+                    request_builder = request_builder
+                        .header(
+                            reqwest::header::CONTENT_TYPE,
+                            content_type.as_deref().unwrap_or("application/json"),
+                        )
+                        .body(raw_body);
+                }
                 if !active_params.is_empty() {
                     if method == "GET" {
                         // For GET, anything leftover goes to query (fallback behavior)
                         request_builder = request_builder.query(&active_params);
-                    } else {
-                        // For POST/PUT/PATCH, leftovers go to JSON body
-                        request_builder = request_builder.json(&active_params);
+                    } else if !has_raw_body {
+                        // For POST/PUT/PATCH, leftovers form the body in the route's media type
+                        request_builder = match content_type.as_deref() {
+                            Some("application/x-www-form-urlencoded") => {
+                                // Form fields cannot nest, so the body argument's fields are sent
+                                if let Some(serde_json::Value::Object(body)) =
+                                    active_params.remove("body")
+                                {
+                                    active_params.extend(body);
+                                }
+                                request_builder.form(&active_params)
+                            }
+                            Some(content_type) if is_json_media_type(content_type) => {
+                                request_builder
+                                    .header(reqwest::header::CONTENT_TYPE, content_type)
+                                    .json(&active_params)
+                            }
+                            // Arguments cannot be encoded as XML, text, multipart, ...
+                            Some(content_type) => {
+                                return Err(anyhow::Error::new(ToolCallError::InvalidArguments(
+                                    vec![format!(
+                                        "{} {} takes a {} body; pass it as a string in {}",
+                                        method, path, content_type, RAW_BODY_ARG
+                                    )],
+                                )));
+                            }
+                            None => request_builder.json(&active_params),
+                        };
                     }
                 }
 
-                info!("Executing request: {} {}", method, url);
+                let mut request = request_builder
+                    .build()
+                    .context("Failed to build HTTP request")?;
+                // Defaults fill in only keys the call (or base URL) did not set
+                let missing: Vec<&(String, String)> = default_query
+                    .iter()
+                    .filter(|(key, _)| !request.url().query_pairs().any(|(k, _)| k == *key))
+                    .collect();
+                if !missing.is_empty() {
+                    request.url_mut().query_pairs_mut().extend_pairs(missing);
+                }
+                // The per-call override replaces any Accept from config or arguments
+                if let Some(accept) = accept {
+                    let value = reqwest::header::HeaderValue::from_str(&accept)
+                        .with_context(|| format!("Invalid Accept override: {}", accept))?;
+                    request.headers_mut().insert(reqwest::header::ACCEPT, value);
+                }
+                if let Some(etag) = if_none_match {
+                    let value = reqwest::header::HeaderValue::from_str(&etag)
+                        .with_context(|| format!("Invalid If-None-Match value: {}", etag))?;
+                    request
+                        .headers_mut()
+                        .insert(reqwest::header::IF_NONE_MATCH, value);
+                }
+
+                let mut parts = RequestParts::from_request(&request);
+                if let Some(interceptor) = &interceptor {
+                    interceptor.before_request(&mut parts);
+                }
+                let unsigned_parts = managed_token.as_ref().map(|_| parts.clone());
+                // Signed last, so the signature covers exactly what is sent
+                if let Some(signer) = &signer {
+                    signer.sign(&mut parts);
+                }
+
+                if dry_run {
+                    info!(
+                        "Dry run, not sending request: {} {}",
+                        parts.method, parts.url
+                    );
+                    // A query-placed API key is only recognizable by its configured name
+                    let secret_query: Vec<&str> = match &auth {
+                        Some(AuthScheme::ApiKey {
+                            name,
+                            location: ApiKeyLocation::Query,
+                        }) => vec![name.as_str()],
+                        _ => Vec::new(),
+                    };
+                    return Ok(parts.to_dry_run_response(&secret_query));
+                }
+
+                // Over the per-host limit, wait here until a request to the host finishes
+                let _slot = limiter.acquire(&host_key(&parts.url)).await;
+                info!("Executing request: {} {}", parts.method, parts.url);
 
-                let response = request_builder
-                    .send()
+                let mut request = parts.into_request(&client)?;
+                *request.timeout_mut() = Some(timeout);
+                let mut response = client
+                    .execute(request)
                     .await
                     .context("Failed to execute HTTP request")?;
 
-                Self::process_response(response).await
+                // A token revoked before its expiry is refreshed and the request sent once more
+                if let (Some(rejected), Some(manager), Some(mut parts)) =
+                    (&managed_token, &token_manager, unsigned_parts)
+                {
+                    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                        warn!("Upstream rejected the OAuth2 token, refreshing and retrying");
+                        let token = manager.refresh_rejected(rejected).await?;
+                        parts
+                            .headers
+                            .insert("authorization".to_string(), format!("Bearer {}", token));
+                        if let Some(signer) = &signer {
+                            signer.sign(&mut parts);
+                        }
+                        let mut request = parts.into_request(&client)?;
+                        *request.timeout_mut() = Some(timeout);
+                        response = client
+                            .execute(request)
+                            .await
+                            .context("Failed to execute HTTP request")?;
+                    }
+                }
+
+                let response = Self::process_response(response).await?;
+                if let Some(interceptor) = &interceptor {
+                    interceptor.after_response(&response);
+                }
+
+                Ok(response)
             })
         });
 
         Ok(executor)
     }
 
+    /// Apply the endpoint's prefix strip/add rules to a spec path.
+    /// The strip prefix only matches on a segment boundary, so "/v1" leaves "/v10" alone.
+    fn rewrite_path(path: &str, strip: Option<&str>, add: Option<&str>) -> String {
+        let mut rewritten = path.to_string();
+
+        if let Some(prefix) = strip
+            .map(|p| p.trim_end_matches('/'))
+            .filter(|p| !p.is_empty())
+        {
+            if let Some(rest) = rewritten.strip_prefix(prefix) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    rewritten = if rest.is_empty() {
+                        "/".to_string()
+                    } else {
+                        rest.to_string()
+                    };
+                }
+            }
+        }
+
+        if let Some(prefix) = add.map(|p| p.trim_matches('/')).filter(|p| !p.is_empty()) {
+            rewritten = format!("/{}/{}", prefix, rewritten.trim_start_matches('/'));
+        }
+
+        rewritten
+    }
+
+    /// Substitute `${path}` and `${NAME}` environment variables in a path template;
+    /// slashes doubled by an empty variable are collapsed
+    fn expand_path_template(template: &str, path: &str) -> Result<String> {
+        static VARIABLE: OnceLock<Regex> = OnceLock::new();
+        let variable =
+            VARIABLE.get_or_init(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+        if !template.contains("${path}") {
+            return Err(anyhow!(
+                "path_template '{}' does not contain ${{path}}",
+                template
+            ));
+        }
+
+        let mut missing = Vec::new();
+        let expanded = variable.replace_all(template, |caps: &regex::Captures| {
+            let name = &caps[1];
+            if name == "path" {
+                return path.to_string();
+            }
+            std::env::var(name).unwrap_or_else(|_| {
+                missing.push(name.to_string());
+                String::new()
+            })
+        });
+        if !missing.is_empty() {
+            return Err(anyhow!(
+                "path_template '{}' uses unset environment variable(s): {}",
+                template,
+                missing.join(", ")
+            ));
+        }
+
+        let mut collapsed = String::with_capacity(expanded.len());
+        for c in expanded.chars() {
+            if !(c == '/' && collapsed.ends_with('/')) {
+                collapsed.push(c);
+            }
+        }
+        Ok(collapsed)
+    }
+
     async fn process_response(response: reqwest::Response) -> Result<HttpResponse> {
         let status_code = response.status().as_u16();
         let headers_map: HashMap<String, String> = response
@@ -225,6 +608,7 @@ impl HttpRequester {
         }
 
         let response = request_builder
+            .timeout(self.timeout)
             .send()
             .await
             .context("Failed to execute HTTP request")?;
@@ -232,3 +616,20 @@ impl HttpRequester {
         Self::process_response(response).await
     }
 }
+
+impl Requester for HttpRequester {
+    fn build_route_executor(&self, config: &RouteConfig) -> Result<RouteExecutor> {
+        HttpRequester::build_route_executor(self, config)
+    }
+}
+
+/// `application/json` or a `+json` structured syntax type, ignoring parameters
+fn is_json_media_type(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
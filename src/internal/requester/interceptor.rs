@@ -0,0 +1,20 @@
+// src/internal/requester/interceptor.rs
+
+use super::http_requester::HttpResponse;
+use super::request::RequestParts;
+
+/// Interceptor hooks run around every upstream call made by a route executor.
+/// Both hooks default to no-ops, so implementors only override what they need.
+pub trait Interceptor: Send + Sync {
+    /// Called with the fully-composed request before it is sent (or returned by a dry run)
+    fn before_request(&self, _request: &mut RequestParts) {}
+
+    /// Called with the upstream response before it is turned into a tool result
+    fn after_response(&self, _response: &HttpResponse) {}
+}
+
+/// NoopInterceptor leaves requests and responses untouched
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopInterceptor;
+
+impl Interceptor for NoopInterceptor {}
@@ -0,0 +1,107 @@
+// src/internal/requester/json_path.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One step of a [`JsonPath`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+/// Simple JSONPath into a response body: `$.error`, `error.message`, `errors[0].detail`
+/// or `$["error-text"]`. Wildcards and filters are not supported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct JsonPath {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// Value at the path, `None` when any step is missing
+    pub fn find<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(value, |current, segment| match segment {
+                Segment::Field(name) => current.get(name),
+                Segment::Index(index) => current.get(index),
+            })
+    }
+
+    /// Value at the path when it is truthy: not null, false, 0, "" or an empty array/object
+    pub fn find_truthy<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.find(value).filter(|value| match value {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => n.as_f64() != Some(0.0),
+            Value::String(s) => !s.is_empty(),
+            Value::Array(items) => !items.is_empty(),
+            Value::Object(fields) => !fields.is_empty(),
+        })
+    }
+}
+
+impl std::str::FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("invalid JSONPath '{}': {}", s, reason);
+        let mut rest = s.trim();
+        rest = rest.strip_prefix('$').unwrap_or(rest);
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(bracketed) = rest.strip_prefix('[') {
+                let end = bracketed
+                    .find(']')
+                    .ok_or_else(|| invalid("unclosed bracket"))?;
+                let inner = bracketed[..end].trim();
+                let quoted = inner
+                    .strip_prefix('"')
+                    .and_then(|i| i.strip_suffix('"'))
+                    .or_else(|| inner.strip_prefix('\'').and_then(|i| i.strip_suffix('\'')));
+                segments.push(match quoted {
+                    Some(name) => Segment::Field(name.to_string()),
+                    None => Segment::Index(
+                        inner
+                            .parse()
+                            .map_err(|_| invalid("brackets need an index or a quoted name"))?,
+                    ),
+                });
+                rest = &bracketed[end + 1..];
+            } else {
+                let field = rest.strip_prefix('.').unwrap_or(rest);
+                let end = field.find(['.', '[']).unwrap_or(field.len());
+                if end == 0 {
+                    return Err(invalid("empty field name"));
+                }
+                segments.push(Segment::Field(field[..end].to_string()));
+                rest = &field[end..];
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(invalid("the path selects no field"));
+        }
+        Ok(Self {
+            source: s.to_string(),
+            segments,
+        })
+    }
+}
+
+impl TryFrom<String> for JsonPath {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<JsonPath> for String {
+    fn from(path: JsonPath) -> Self {
+        path.source
+    }
+}
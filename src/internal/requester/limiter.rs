@@ -0,0 +1,88 @@
+// src/internal/requester/limiter.rs
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Caps simultaneous upstream requests per host; requests over the limit wait for a slot.
+/// Counts the requests in flight whether or not a limit is set.
+#[derive(Debug, Default)]
+pub struct ConcurrencyLimiter {
+    max_per_host: Option<usize>,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Slot held while a request is in flight; released on drop
+#[derive(Debug)]
+pub struct InFlightGuard {
+    _permit: Option<OwnedSemaphorePermit>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ConcurrencyLimiter {
+    /// Limit each host to `max_per_host` concurrent requests; `None` or 0 means unlimited
+    pub fn new(max_per_host: Option<usize>) -> Self {
+        Self {
+            max_per_host: max_per_host.filter(|max| *max > 0),
+            ..Default::default()
+        }
+    }
+
+    /// Wait for a slot for `host` (e.g. `api.example.com:443`)
+    pub async fn acquire(&self, host: &str) -> InFlightGuard {
+        let permit = match self.semaphore(host) {
+            // The semaphore is never closed, so acquiring only fails if it were
+            Some(semaphore) => semaphore.acquire_owned().await.ok(),
+            None => None,
+        };
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            _permit: permit,
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    /// Requests currently being sent or awaiting their response, over all hosts
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Requests to `host` currently holding a slot; always 0 without a limit
+    pub fn in_flight_for(&self, host: &str) -> usize {
+        match (self.max_per_host, self.hosts.lock().unwrap().get(host)) {
+            (Some(max), Some(semaphore)) => max - semaphore.available_permits(),
+            _ => 0,
+        }
+    }
+
+    fn semaphore(&self, host: &str) -> Option<Arc<Semaphore>> {
+        let max = self.max_per_host?;
+        let mut hosts = self.hosts.lock().unwrap();
+        Some(
+            hosts
+                .entry(host.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(max)))
+                .clone(),
+        )
+    }
+}
+
+/// Key the limit applies to: host and port of the URL, or the whole URL if it has no host
+pub fn host_key(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(parsed) => match (parsed.host_str(), parsed.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => url.to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
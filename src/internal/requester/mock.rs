@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+
+use super::http_requester::HttpResponse;
+use super::types::{Requester, RouteConfig, RouteExecutor};
+
+/// A call captured by the mock requester
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub method: String,
+    pub path: String,
+    pub params: Value,
+}
+
+/// Mock requester for testing - serves canned responses and records every call.
+/// Routes can also be made slow or fail as if the connection broke.
+#[derive(Clone, Default)]
+pub struct MockRequester {
+    /// Canned responses keyed by "METHOD path"
+    pub responses: Arc<Mutex<HashMap<String, HttpResponse>>>,
+    /// Latency added before answering, keyed by "METHOD path"
+    pub delays: Arc<Mutex<HashMap<String, Duration>>>,
+    /// Connection error messages returned instead of a response, keyed by "METHOD path"
+    pub failures: Arc<Mutex<HashMap<String, String>>>,
+    /// Captured calls, in order
+    pub calls: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl MockRequester {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve the given status and JSON body for a route
+    pub fn respond_with(&self, method: &str, path: &str, status_code: u16, body: Value) {
+        let response = HttpResponse {
+            status_code,
+            body: serde_json::to_vec(&body).unwrap(),
+            headers: HashMap::from([("content-type".to_string(), "application/json".to_string())]),
+        };
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(Self::route_key(method, path), response);
+    }
+
+    /// Wait this long before answering calls to a route
+    pub fn delay(&self, method: &str, path: &str, delay: Duration) {
+        self.delays
+            .lock()
+            .unwrap()
+            .insert(Self::route_key(method, path), delay);
+    }
+
+    /// Fail calls to a route with a connection error instead of responding
+    pub fn fail_with(&self, method: &str, path: &str, message: &str) {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(Self::route_key(method, path), message.to_string());
+    }
+
+    /// Get all captured calls
+    pub fn get_calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn route_key(method: &str, path: &str) -> String {
+        format!("{} {}", method.to_uppercase(), path)
+    }
+}
+
+impl Requester for MockRequester {
+    fn build_route_executor(&self, config: &RouteConfig) -> anyhow::Result<RouteExecutor> {
+        let mock = self.clone();
+        let method = config.method.clone();
+        let path = config.path.clone();
+
+        Ok(Arc::new(move |params_json: &str| {
+            let params: anyhow::Result<Value> =
+                serde_json::from_str(params_json).context("Failed to parse parameters as JSON");
+            let key = Self::route_key(&method, &path);
+            let mock = mock.clone();
+            let method = method.clone();
+            let path = path.clone();
+
+            Box::pin(async move {
+                mock.calls.lock().unwrap().push(RecordedCall {
+                    method,
+                    path,
+                    params: params?,
+                });
+
+                let delay = mock.delays.lock().unwrap().get(&key).copied();
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+                let failure = mock.failures.lock().unwrap().get(&key).cloned();
+                if let Some(message) = failure {
+                    return Err(anyhow!(message).context("Failed to execute HTTP request"));
+                }
+
+                // Unmatched routes behave like an upstream 404
+                let response = mock.responses.lock().unwrap().get(&key).cloned();
+                Ok(response.unwrap_or_else(|| HttpResponse {
+                    status_code: 404,
+                    body: format!("No canned response for {}", key).into_bytes(),
+                    headers: HashMap::new(),
+                }))
+            })
+        }))
+    }
+}
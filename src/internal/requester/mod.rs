@@ -1,6 +1,27 @@
+pub mod auth;
+pub mod coercion;
 pub mod http_requester;
+pub mod interceptor;
+pub mod json_path;
+pub mod limiter;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
+pub mod request;
+pub mod signing;
+pub mod trace;
 pub mod types;
 
 // Re-export main types
+pub use auth::{missing_scopes, ApiKeyLocation, AuthScheme, RouteAuth};
+pub use coercion::coerce_arguments;
 pub use http_requester::{HttpRequester, HttpResponse};
-pub use types::{FileUploadConfig, MethodConfig, RouteConfig, RouteExecutor};
+pub use interceptor::{Interceptor, NoopInterceptor};
+pub use json_path::JsonPath;
+pub use limiter::ConcurrencyLimiter;
+pub use request::{build_url, join_url, RequestParts};
+pub use signing::RequestSigner;
+pub use trace::TraceContext;
+pub use types::{
+    is_success_status, FileUploadConfig, MethodConfig, Requester, RouteConfig, RouteExecutor,
+    StatusRange,
+};
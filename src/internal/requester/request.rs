@@ -0,0 +1,239 @@
+// src/internal/requester/request.rs
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::http_requester::HttpResponse;
+use super::trace::TraceContext;
+use crate::internal::config::{is_sensitive_header, REDACTED};
+
+/// Reserved tool argument that turns a single call into a dry run
+pub const DRY_RUN_ARG: &str = "__dry_run";
+
+/// Reserved tool argument that overrides the `Accept` header for a single call
+pub const ACCEPT_ARG: &str = "__accept";
+
+/// Reserved tool argument whose string value is sent verbatim as the request body
+pub const RAW_BODY_ARG: &str = "__raw_body";
+
+/// Reserved tool argument sent upstream as the `If-None-Match` header, for conditional requests
+pub const IF_NONE_MATCH_ARG: &str = "__if_none_match";
+
+/// Reserved tool argument sent upstream as the W3C `traceparent` header
+pub const TRACEPARENT_ARG: &str = "__traceparent";
+
+/// RequestParts is a snapshot of a fully-composed upstream request
+#[derive(Debug, Clone, Default)]
+pub struct RequestParts {
+    pub method: String,
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl RequestParts {
+    /// Capture the parts of a built reqwest request
+    pub fn from_request(request: &reqwest::Request) -> Self {
+        let headers = request
+            .headers()
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|v| (key.as_str().to_string(), v.to_string()))
+            })
+            .collect();
+
+        Self {
+            method: request.method().as_str().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| b.to_vec()),
+        }
+    }
+
+    /// Rebuild a sendable request from the (possibly modified) parts
+    pub fn into_request(self, client: &reqwest::Client) -> Result<reqwest::Request> {
+        let method = reqwest::Method::from_bytes(self.method.as_bytes())
+            .with_context(|| format!("Invalid HTTP method: {}", self.method))?;
+
+        let mut builder = client.request(method, &self.url);
+        for (key, value) in &self.headers {
+            builder = builder.header(key, value);
+        }
+        if let Some(body) = self.body {
+            builder = builder.body(body);
+        }
+
+        builder.build().context("Failed to build HTTP request")
+    }
+
+    /// Describe the request as JSON; bodies are shown as JSON when they parse, else as text.
+    /// Values of credential headers (`Authorization`, API keys, tokens) are redacted, since
+    /// the description is returned to the MCP client. So are query parameters listed in
+    /// `secret_query` or named like a credential header.
+    pub fn to_json(&self, secret_query: &[&str]) -> Value {
+        let body = self.body.as_ref().map(|bytes| {
+            serde_json::from_slice::<Value>(bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).to_string()))
+        });
+
+        let headers: HashMap<&str, &str> = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if is_sensitive_header(name) {
+                    REDACTED
+                } else {
+                    value.as_str()
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+
+        serde_json::json!({
+            "method": self.method,
+            "url": redact_query(&self.url, secret_query),
+            "headers": headers,
+            "body": body,
+        })
+    }
+
+    /// Build the synthetic response returned instead of sending the request
+    pub fn to_dry_run_response(&self, secret_query: &[&str]) -> HttpResponse {
+        let mut description = self.to_json(secret_query);
+        if let Some(map) = description.as_object_mut() {
+            map.insert("dry_run".to_string(), Value::Bool(true));
+        }
+
+        HttpResponse {
+            status_code: 200,
+            body: serde_json::to_vec(&description).unwrap_or_default(),
+            headers: HashMap::from([("content-type".to_string(), "application/json".to_string())]),
+        }
+    }
+}
+
+/// Replace the values of credential query parameters, leaving the rest of the URL as is
+fn redact_query(url: &str, secret_query: &[&str]) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let (query, fragment) = match query.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (query, None),
+    };
+
+    let pairs: Vec<String> = query
+        .split('&')
+        .map(|pair| {
+            let encoded_name = pair.split_once('=').map_or(pair, |(name, _)| name);
+            let name = url::form_urlencoded::parse(encoded_name.as_bytes())
+                .next()
+                .map(|(name, _)| name.into_owned())
+                .unwrap_or_default();
+            if secret_query.contains(&name.as_str()) || is_sensitive_header(&name) {
+                format!("{}={}", encoded_name, REDACTED)
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect();
+
+    let mut redacted = format!("{}?{}", base, pairs.join("&"));
+    if let Some(fragment) = fragment {
+        redacted.push('#');
+        redacted.push_str(fragment);
+    }
+    redacted
+}
+
+/// Remove the per-call dry-run flag from the arguments, returning whether it was set
+pub fn take_dry_run_flag(params: &mut serde_json::Map<String, Value>) -> bool {
+    match params.remove(DRY_RUN_ARG) {
+        Some(Value::Bool(b)) => b,
+        Some(Value::String(s)) => s.eq_ignore_ascii_case("true"),
+        _ => false,
+    }
+}
+
+/// Remove the per-call `Accept` override from the arguments, if one was given
+pub fn take_accept_override(params: &mut serde_json::Map<String, Value>) -> Option<String> {
+    match params.remove(ACCEPT_ARG) {
+        Some(Value::String(s)) if !s.trim().is_empty() => Some(s),
+        _ => None,
+    }
+}
+
+/// Remove the entity tag(s) for `If-None-Match` from the arguments, if any were given
+pub fn take_if_none_match(params: &mut serde_json::Map<String, Value>) -> Option<String> {
+    match params.remove(IF_NONE_MATCH_ARG) {
+        Some(Value::String(s)) if !s.trim().is_empty() => Some(s),
+        _ => None,
+    }
+}
+
+/// Remove the raw body from the arguments; non-string values are sent as their JSON text
+pub fn take_raw_body(params: &mut serde_json::Map<String, Value>) -> Option<String> {
+    match params.remove(RAW_BODY_ARG)? {
+        Value::String(s) => Some(s),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Remove the trace context from the arguments, if a valid one was given
+pub fn take_traceparent(params: &mut serde_json::Map<String, Value>) -> Option<String> {
+    match params.remove(TRACEPARENT_ARG) {
+        Some(Value::String(s)) => TraceContext::parse(&s).map(|trace| trace.to_header()),
+        _ => None,
+    }
+}
+
+/// Rename aliased arguments, including fields of the `body` argument, to their upstream names
+pub fn rename_arguments(
+    params: &mut serde_json::Map<String, Value>,
+    aliases: &HashMap<String, String>,
+) {
+    for (alias, upstream) in aliases {
+        if let Some(value) = params.remove(alias) {
+            params.insert(upstream.clone(), value);
+        }
+    }
+    if let Some(Value::Object(body)) = params.get_mut("body") {
+        for (alias, upstream) in aliases {
+            if let Some(value) = body.remove(alias) {
+                body.insert(upstream.clone(), value);
+            }
+        }
+    }
+}
+
+/// Join a base URL and a route path with exactly one slash at the boundary.
+/// Any path component of the base URL is kept, so `http://host/api/` + `/users` is `http://host/api/users`.
+pub fn join_url(base_url: &str, path: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if path.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}/{}", base, path.trim_start_matches('/'))
+    }
+}
+
+/// Resolve a route path against the base URL.
+/// The base URL's path is kept as a prefix and its query parameters are preserved,
+/// so `https://host/api?key=abc` + `/users` is `https://host/api/users?key=abc`.
+pub fn build_url(base_url: &str, path: &str) -> Result<url::Url> {
+    let mut url = url::Url::parse(base_url)
+        .with_context(|| format!("Invalid API endpoint URL: {}", base_url))?;
+
+    let joined = join_url(url.path(), path);
+    url.set_path(&joined);
+    url.set_fragment(None);
+    Ok(url)
+}
@@ -1,6 +1,8 @@
 // src/internal/requester/types.rs
 
+use super::auth::RouteAuth;
 use super::http_requester::HttpResponse;
+use super::json_path::JsonPath;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,8 +17,14 @@ pub type RouteExecutor = Arc<
         + Sync,
 >;
 
+/// Requester turns a route configuration into an executor that performs the call
+pub trait Requester: Send + Sync {
+    /// Build the executor invoked for every call to the route's tool
+    fn build_route_executor(&self, config: &RouteConfig) -> anyhow::Result<RouteExecutor>;
+}
+
 /// RouteConfig holds the configuration for a specific route
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RouteConfig {
     pub path: String,
     pub method: String,
@@ -24,6 +32,11 @@ pub struct RouteConfig {
     pub headers: HashMap<String, String>,
     pub parameters: HashMap<String, String>,
     pub method_config: MethodConfig,
+    /// Host for this route from operation/path-level `servers`, overriding the endpoint base URL
+    pub base_url: Option<String>,
+    /// Authentication the spec's security requirements declare for this route
+    #[serde(default)]
+    pub auth: RouteAuth,
 }
 
 /// MethodConfig holds method-specific configurations
@@ -36,6 +49,106 @@ pub struct MethodConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub form_fields: Vec<String>,
     pub file_upload: Option<String>,
+    /// Upstream argument names keyed by the alias the tool exposes
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub argument_aliases: HashMap<String, String>,
+    /// Query parameters added when the call does not set them
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub default_query: HashMap<String, String>,
+    /// Media type of the request body; JSON when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Request body example declared in the spec
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_example: Option<serde_json::Value>,
+    /// Statuses treated as success; empty means 200-399
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub success_statuses: Vec<StatusRange>,
+    /// Response field that marks a successful body as an error when truthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<JsonPath>,
+    /// JSON Schema of each tool argument, keyed by argument name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub param_schemas: HashMap<String, serde_json::Value>,
+    /// OAuth2 scopes the spec's `security` requires for the operation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_scopes: Vec<String>,
+}
+
+/// Inclusive range of HTTP status codes, written as `201` or `"200-299"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "StatusRangeRepr", into = "String")]
+pub struct StatusRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatusRangeRepr {
+    Code(u16),
+    Text(String),
+}
+
+impl StatusRange {
+    pub fn contains(&self, status: u16) -> bool {
+        (self.start..=self.end).contains(&status)
+    }
+}
+
+impl std::str::FromStr for StatusRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |code: &str| {
+            code.trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|c| (100..=599).contains(c))
+                .ok_or_else(|| format!("invalid HTTP status '{}'", code.trim()))
+        };
+        let (start, end) = match s.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => {
+                let code = parse(s)?;
+                (code, code)
+            }
+        };
+        if start > end {
+            return Err(format!("status range '{}' is reversed", s));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl TryFrom<StatusRangeRepr> for StatusRange {
+    type Error = String;
+
+    fn try_from(repr: StatusRangeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            StatusRangeRepr::Code(code) => code.to_string().parse(),
+            StatusRangeRepr::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<StatusRange> for String {
+    fn from(range: StatusRange) -> Self {
+        if range.start == range.end {
+            range.start.to_string()
+        } else {
+            format!("{}-{}", range.start, range.end)
+        }
+    }
+}
+
+/// Whether a status counts as success: within one of the ranges, or 200-399 when none are set
+pub fn is_success_status(success_statuses: &[StatusRange], status: u16) -> bool {
+    if success_statuses.is_empty() {
+        (200..400).contains(&status)
+    } else {
+        success_statuses.iter().any(|range| range.contains(status))
+    }
 }
 
 /// FileUploadConfig holds configuration for file uploads
@@ -65,6 +178,8 @@ impl RouteConfig {
             headers: HashMap::new(),
             parameters: HashMap::new(),
             method_config: MethodConfig::default(),
+            base_url: None,
+            auth: RouteAuth::default(),
         }
     }
 }
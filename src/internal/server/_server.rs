@@ -1,8 +1,16 @@
 // src/internal/server/_server.rs
 
 use crate::internal::mcp::processor::McpProcessor;
+use crate::internal::mcp::protocol::{ToolsDescribeParams, ToolsListParams};
 use crate::internal::mcp::registry::ToolRegistry;
-use crate::internal::server::tool::ToolHandler;
+use crate::internal::server::tool::audit::AuditLog;
+use crate::internal::server::tool::example::{example_executor, example_payloads, example_tool};
+use crate::internal::server::tool::info::{
+    info_executor, info_payload, info_tool, spec_instructions,
+};
+use crate::internal::server::tool::{
+    ToolCallError, ToolHandler, EXAMPLE_TOOL_NAME, INFO_TOOL_NAME,
+};
 use crate::internal::transport::runner::TransportRunner;
 use crate::internal::transport::stdio::StdioTransport;
 
@@ -10,20 +18,49 @@ use anyhow::{Context, Result};
 use rmcp::{model::*, service::RequestContext, ErrorData as McpError, RoleServer, ServerHandler};
 use std::process;
 use std::sync::Arc;
-use tracing::{error, info};
+use tokio_util::sync::CancellationToken;
+use tower_http::compression::predicate::{DefaultPredicate, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::decompression::RequestDecompressionLayer;
+use tracing::{error, info, warn};
 
 use crate::internal::config::{AppConfig, ServerMode};
-use crate::internal::parser::_parser::SwaggerParser;
-use crate::internal::parser::adjuster::Adjuster;
-use crate::internal::parser::types::Parser;
-use crate::internal::requester::HttpRequester;
+use crate::internal::parser::cache::ToolCache;
+use crate::internal::parser::merge::{merge_spec_tools, SpecTools};
+use crate::internal::parser::refs::remote_spec_url;
+use crate::internal::parser::types::{
+    DiagnosticSeverity, ParseDiagnostic, Parser, RouteTool, SpecInfo,
+};
+use crate::internal::requester::Requester;
+use crate::internal::server::builder::ServerBuilder;
+use crate::internal::server::listener::{
+    bind_unix_socket, load_tls_config, systemd_tcp_listener, TlsListener,
+};
+use crate::internal::server::self_test::{run_self_test, SelfTestReport};
+
+/// Server instructions when no spec has an `info.description`
+const DEFAULT_INSTRUCTIONS: &str = "OpenAPI MCP Server";
+
+/// HTTP responses smaller than this many bytes are sent uncompressed
+const COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// Tools, diagnostics and `info` block produced from one spec
+type ParsedSpec = (Vec<RouteTool>, Vec<ParseDiagnostic>, Option<SpecInfo>);
 
 #[derive(Clone)]
 pub struct Server {
     pub config: AppConfig,
     parser: Arc<tokio::sync::Mutex<Box<dyn Parser>>>,
-    requester: HttpRequester,
+    requester: Arc<dyn Requester>,
     pub tool_handler: Arc<tokio::sync::Mutex<ToolHandler>>,
+    registry: Arc<ToolRegistry>,
+    /// Set once the spec has been parsed and its tools registered
+    tools_ready: Arc<tokio::sync::OnceCell<()>>,
+    /// Instructions from the specs' `info.description`, set when tools are registered
+    instructions: Arc<std::sync::OnceLock<String>>,
+    /// Address the HTTP server is listening on, set once bound
+    local_addr: Arc<std::sync::OnceLock<std::net::SocketAddr>>,
+    shutdown: CancellationToken,
 }
 
 // Implement ServerHandler trait (Still needed for internal logic if called directly)
@@ -33,6 +70,7 @@ impl ServerHandler for Server {
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
+        // rmcp's paginated params carry no filter, so this path always lists every tool
         let tool_handler = self.tool_handler.lock().await;
         let tools = tool_handler.list_tool_metadata();
 
@@ -62,11 +100,7 @@ impl ServerHandler for Server {
             };
 
             let future = executor(call_request);
-            let result = future.await.map_err(|e| McpError {
-                code: ErrorCode(-32600),
-                message: e.to_string().into(),
-                data: None,
-            })?;
+            let result = future.await.map_err(Self::tool_error_to_mcp)?;
 
             Ok(result)
         } else {
@@ -81,7 +115,10 @@ impl ServerHandler for Server {
     fn get_info(&self) -> ServerInfo {
         InitializeResult {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
             server_info: Implementation {
                 name: self.config.server.name.clone(),
                 version: self.config.server.version.clone(),
@@ -89,7 +126,12 @@ impl ServerHandler for Server {
                 title: None,
                 website_url: None,
             },
-            instructions: Some("OpenAPI MCP Server".into()),
+            instructions: Some(
+                self.instructions
+                    .get()
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_INSTRUCTIONS.to_string()),
+            ),
         }
     }
 }
@@ -98,7 +140,7 @@ impl Server {
     pub async fn new(
         config: AppConfig,
         parser: Box<dyn Parser>,
-        requester: HttpRequester,
+        requester: Box<dyn Requester>,
     ) -> Result<Self> {
         if config.swagger_file.is_empty() {
             error!("Swagger file path cannot be empty");
@@ -107,32 +149,107 @@ impl Server {
 
         let auth_enabled = config.oauth.as_ref().map(|o| o.enabled).unwrap_or(false);
         let registry = Arc::new(ToolRegistry::new());
-        let tool_handler = ToolHandler::new(auth_enabled, registry);
+        let audit_log = match &config.server.audit_log {
+            Some(path) => Some(Arc::new(AuditLog::open(path)?)),
+            None => None,
+        };
+        let tool_handler = ToolHandler::new(auth_enabled, Arc::clone(&registry))
+            .with_argument_validation(config.server.validate_arguments)
+            .with_audit_log(audit_log)
+            .with_trace_context(config.server.trace_context);
 
         let server = Self {
             config,
             parser: Arc::new(tokio::sync::Mutex::new(parser)),
-            requester,
+            requester: Arc::from(requester),
             tool_handler: Arc::new(tokio::sync::Mutex::new(tool_handler)),
+            registry,
+            tools_ready: Arc::new(tokio::sync::OnceCell::new()),
+            instructions: Arc::new(std::sync::OnceLock::new()),
+            local_addr: Arc::new(std::sync::OnceLock::new()),
+            shutdown: CancellationToken::new(),
         };
 
         Ok(server)
     }
 
+    /// Parse the specs and register their tools; later calls return without reparsing
     pub async fn setup_tools(&self) -> Result<()> {
+        self.tools_ready
+            .get_or_try_init(|| self.register_tools())
+            .await?;
+        Ok(())
+    }
+
+    async fn register_tools(&self) -> Result<()> {
         info!("Loading adjustments and parsing OpenAPI spec...");
 
+        let sources = self.config.swagger_sources();
+        if self.config.server.mode == ServerMode::Stdio && sources.iter().any(|s| s.is_stdin()) {
+            return Err(anyhow::anyhow!(
+                "reading the spec from stdin (--swagger-file -) requires --mode http; stdio mode uses stdin for JSON-RPC"
+            ));
+        }
+
+        let cache = self.config.cache_dir.as_deref().map(ToolCache::new);
         let mut parser = self.parser.lock().await;
-        parser
-            .init(
-                &self.config.swagger_file,
-                self.config.adjustments_file.as_deref(),
-            )
-            .context("Failed to initialize parser")?;
-
-        let route_tools = parser.get_route_tools().to_vec();
+        let mut specs = Vec::new();
+        let mut spec_infos = Vec::new();
+        for source in sources {
+            let (route_tools, diagnostics, info) = if source.is_stdin() {
+                if let Some(adjustments_file) = &self.config.adjustments_file {
+                    parser.load_adjustments(adjustments_file)?;
+                }
+                parser
+                    .parse_reader(Box::new(std::io::stdin()))
+                    .context("Failed to parse spec from stdin")?;
+                (
+                    parser.get_route_tools().to_vec(),
+                    parser.get_diagnostics().to_vec(),
+                    parser.get_spec_info(),
+                )
+            } else {
+                self.parse_spec_file(parser.as_mut(), &source.path, cache.as_ref())?
+            };
+            Self::log_diagnostics(&diagnostics);
+            if let Some(info) = info {
+                spec_infos.push((source.tool_prefix.clone(), info));
+            }
+
+            specs.push(SpecTools {
+                file: source.path,
+                tool_prefix: source.tool_prefix,
+                route_tools,
+            });
+        }
+        drop(parser);
+
+        if let Some(instructions) =
+            spec_instructions(&spec_infos, self.config.server.max_instructions_length)
+        {
+            let _ = self.instructions.set(instructions);
+        }
+
+        let (route_tools, conflicts) = merge_spec_tools(specs);
+        Self::log_diagnostics(&conflicts);
+
         let mut tool_handler = self.tool_handler.lock().await;
 
+        if self.config.server.info_tool {
+            let payload = info_payload(&spec_infos, &route_tools);
+            tool_handler.register_tool(INFO_TOOL_NAME, info_tool(), info_executor(payload));
+            info!("Registered tool: {}", INFO_TOOL_NAME);
+        }
+        if self.config.server.example_tool {
+            let payloads = example_payloads(&route_tools);
+            tool_handler.register_tool(
+                EXAMPLE_TOOL_NAME,
+                example_tool(),
+                example_executor(payloads),
+            );
+            info!("Registered tool: {}", EXAMPLE_TOOL_NAME);
+        }
+
         for route_tool in route_tools {
             let executor = self
                 .requester
@@ -145,8 +262,19 @@ impl Server {
                 })?;
 
             let tool_name = route_tool.tool.name.clone().clone();
-            let handler = tool_handler.create_handler(&tool_name, executor);
-            tool_handler.register_tool(&tool_name, route_tool.tool.to_owned(), handler.clone());
+            let handler =
+                tool_handler.create_route_handler(&tool_name, executor, &route_tool.route_config);
+            // Introspection shows the base URL the call actually goes to
+            let mut route = route_tool.route_config.clone();
+            route
+                .base_url
+                .get_or_insert_with(|| self.config.endpoint.base_url.clone());
+            tool_handler.register_route_tool(
+                &tool_name,
+                route_tool.tool.to_owned(),
+                handler.clone(),
+                route,
+            );
 
             info!(
                 "Registered tool: {} {} -> {}",
@@ -154,13 +282,107 @@ impl Server {
             );
         }
 
-        info!(
-            "Successfully registered {} tools",
-            tool_handler.tool_count()
-        );
+        self.apply_tool_allowlist();
+
+        // Counted after the allowlist, which can drop every generated tool
+        let route_tool_count = self
+            .registry
+            .names()
+            .iter()
+            .filter(|name| self.registry.get_route(name).is_some())
+            .count();
+        if route_tool_count == 0 {
+            let message = format!(
+                "No tools were generated from {}. Likely causes: the wrong spec file, a spec \
+                 without paths, adjustments that filter out every route, --path-prefix/--read-only \
+                 excluding every operation, or a --tools allowlist naming none of the generated tools",
+                self.config.swagger_file
+            );
+            if self.config.server.fail_on_empty_tools() {
+                return Err(anyhow::anyhow!(
+                    "{} (pass --fail-on-empty false to serve anyway)",
+                    message
+                ));
+            }
+            warn!("{}", message);
+        }
+
+        if self.config.parser.path_prefixes.is_empty() {
+            info!(
+                "Successfully registered {} tools",
+                tool_handler.tool_count()
+            );
+        } else {
+            info!(
+                "Successfully registered {} tools under path prefixes {:?}",
+                tool_handler.tool_count(),
+                self.config.parser.path_prefixes
+            );
+        }
         Ok(())
     }
 
+    /// Parse a spec file, going through the tool cache when one is configured
+    fn parse_spec_file(
+        &self,
+        parser: &mut dyn Parser,
+        path: &str,
+        cache: Option<&ToolCache>,
+    ) -> Result<ParsedSpec> {
+        let adjustments_file = self.config.adjustments_file.as_deref();
+        let parse = |parser: &mut dyn Parser| -> Result<ParsedSpec> {
+            parser
+                .init(path, adjustments_file)
+                .with_context(|| format!("Failed to initialize parser for {}", path))?;
+            Ok((
+                parser.get_route_tools().to_vec(),
+                parser.get_diagnostics().to_vec(),
+                parser.get_spec_info(),
+            ))
+        };
+
+        // Remote specs are fetched on every start, so there is nothing to key a cache entry on
+        let Some(cache) = cache.filter(|_| remote_spec_url(path).is_none()) else {
+            return parse(parser);
+        };
+
+        let spec = std::fs::read(path).with_context(|| format!("Failed to read {}", path))?;
+        let adjustments = adjustments_file.and_then(|p| std::fs::read(p).ok());
+        let key = ToolCache::key(&spec, adjustments.as_deref(), &self.config.parser);
+
+        if let Some(cached) = cache.load(&key) {
+            info!(
+                "Loaded {} tools for {} from the tool cache",
+                cached.route_tools.len(),
+                path
+            );
+            return Ok((cached.route_tools, cached.diagnostics, cached.info));
+        }
+
+        let (route_tools, diagnostics, info) = parse(parser)?;
+        let stored = cache.store(
+            &key,
+            &route_tools,
+            &diagnostics,
+            info.as_ref(),
+            parser.get_external_documents(),
+        );
+        if let Err(e) = stored {
+            warn!("Failed to write the tool cache: {:#}", e);
+        }
+        Ok((route_tools, diagnostics, info))
+    }
+
+    fn log_diagnostics(diagnostics: &[ParseDiagnostic]) {
+        for diagnostic in diagnostics {
+            match diagnostic.severity {
+                DiagnosticSeverity::Info => info!("Spec: {}", diagnostic),
+                DiagnosticSeverity::Warning => warn!("Spec: {}", diagnostic),
+                DiagnosticSeverity::Error => error!("Spec: {}", diagnostic),
+            }
+        }
+    }
+
     async fn serve_stdio(&self) -> Result<()> {
         // Logs go to stderr, so this is safe
         info!("Starting STDIO server with {} tools", self.tool_count());
@@ -198,7 +420,10 @@ impl Server {
             StatusCode::OK
         }
 
-        let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
+        let addr = match &self.config.server.uds {
+            Some(path) => format!("unix:{}", path),
+            None => format!("{}:{}", self.config.server.host, self.config.server.port),
+        };
         info!(
             "Starting HTTP MCP server on {} with {} tools",
             addr,
@@ -235,7 +460,11 @@ impl Server {
                 }
                 Some("tools/list") => {
                     // Uses the simple list logic (no context needed)
-                    let tools = app_state.server.list_tools_simple().await;
+                    let params = ToolsListParams::from_params(payload.get("params"));
+                    let tools = app_state
+                        .server
+                        .list_tools_simple(params.filter.as_deref())
+                        .await;
                     match tools {
                         Ok(result) => serde_json::json!({
                             "jsonrpc": "2.0",
@@ -264,7 +493,7 @@ impl Server {
                                 }),
                                 Err(e) => serde_json::json!({
                                     "jsonrpc": "2.0",
-                                    "error": { "code": e.code.0, "message": e.message },
+                                    "error": { "code": e.code.0, "message": e.message, "data": e.data },
                                     "id": id
                                 }),
                             }
@@ -276,6 +505,30 @@ impl Server {
                         }),
                     }
                 }
+                Some("tools/describe") => {
+                    let params = payload.get("params").and_then(|p| {
+                        serde_json::from_value::<ToolsDescribeParams>(p.clone()).ok()
+                    });
+                    match params {
+                        Some(params) => match app_state.server.registry.describe(&params.name) {
+                            Some(description) => serde_json::json!({
+                                "jsonrpc": "2.0",
+                                "result": description,
+                                "id": id
+                            }),
+                            None => serde_json::json!({
+                                "jsonrpc": "2.0",
+                                "error": { "code": -32601, "message": "Tool not found" },
+                                "id": id
+                            }),
+                        },
+                        None => serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "error": { "code": -32602, "message": "Invalid params" },
+                            "id": id
+                        }),
+                    }
+                }
                 Some("notifications/initialized") | Some("ping") => {
                     serde_json::json!({
                         "jsonrpc": "2.0",
@@ -294,30 +547,113 @@ impl Server {
         }
 
         // 3. Build Router
-        let app = axum::Router::new()
-            .route("/health", get(health))
-            .route("/mcp", post(handle_mcp_request))
-            .with_state(state);
+        let mcp_path = self.config.server.mcp_route();
+        let app =
+            axum::Router::new()
+                .route("/health", get(health))
+                .route(&mcp_path, post(handle_mcp_request))
+                // gzip request bodies are inflated; other encodings are refused with 415
+                .layer(RequestDecompressionLayer::new())
+                // Only large responses, in practice `tools/list`, are worth compressing
+                .layer(CompressionLayer::new().compress_when(
+                    DefaultPredicate::new().and(SizeAbove::new(COMPRESSION_MIN_SIZE)),
+                ))
+                .with_state(state);
 
         // 4. Start Server
-        let listener = tokio::net::TcpListener::bind(&addr)
-            .await
-            .with_context(|| format!("Failed to bind to address: {}", addr))?;
+        // Load TLS material before binding so a bad certificate fails fast
+        let tls_config = match self.config.server.tls_paths()? {
+            Some((cert, key)) => Some(load_tls_config(cert, key)?),
+            None => None,
+        };
+
+        if let Some(path) = &self.config.server.uds {
+            if tls_config.is_some() {
+                return Err(anyhow::anyhow!(
+                    "TLS is not supported on Unix sockets; unset tls_cert_path/tls_key_path or --uds"
+                ));
+            }
+            return Self::serve_unix(app, path, &mcp_path, self.shutdown.clone()).await;
+        }
+
+        // Under systemd socket activation the listening socket is inherited, not bound
+        let listener = match systemd_tcp_listener()? {
+            Some(listener) => {
+                info!("Using listener passed by systemd socket activation");
+                listener
+            }
+            None => tokio::net::TcpListener::bind(&addr)
+                .await
+                .with_context(|| format!("Failed to bind to address: {}", addr))?,
+        };
+        if let Ok(local_addr) = listener.local_addr() {
+            let _ = self.local_addr.set(local_addr);
+        }
+        let addr = listener.local_addr().map(|a| a.to_string()).unwrap_or(addr);
+
+        let scheme = if tls_config.is_some() {
+            "https"
+        } else {
+            "http"
+        };
 
         info!("HTTP MCP server listening on {}", addr);
-        info!("Endpoint: POST http://{}/mcp", addr);
+        info!("Endpoint: POST {}://{}{}", scheme, addr, mcp_path);
+        info!("Health check: GET {}://{}/health", scheme, addr);
+
+        // In-flight requests finish before the server stops
+        let shutdown = self.shutdown.clone().cancelled_owned();
+        match tls_config {
+            Some(tls_config) => axum::serve(TlsListener::new(listener, tls_config), app)
+                .with_graceful_shutdown(shutdown)
+                .await
+                .context("HTTPS server failed")?,
+            None => axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown)
+                .await
+                .context("HTTP server failed")?,
+        }
+
+        info!("HTTP MCP server stopped");
+        Ok(())
+    }
+
+    /// Serve the router on a Unix socket; the socket file is removed on shutdown
+    #[cfg(unix)]
+    async fn serve_unix(
+        app: axum::Router,
+        path: &str,
+        mcp_path: &str,
+        shutdown: CancellationToken,
+    ) -> Result<()> {
+        let (listener, _guard) = bind_unix_socket(path)?;
+
+        info!("HTTP MCP server listening on unix:{}", path);
+        info!("Endpoint: POST {} on unix:{}", mcp_path, path);
 
         axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown.cancelled_owned())
             .await
             .context("HTTP server failed")?;
 
+        info!("HTTP MCP server stopped");
         Ok(())
     }
 
-    async fn list_tools_simple(&self) -> Result<ListToolsResult, McpError> {
+    #[cfg(not(unix))]
+    async fn serve_unix(
+        _app: axum::Router,
+        path: &str,
+        _mcp_path: &str,
+        _shutdown: CancellationToken,
+    ) -> Result<()> {
+        bind_unix_socket(path)
+    }
+
+    async fn list_tools_simple(&self, filter: Option<&str>) -> Result<ListToolsResult, McpError> {
         let tool_handler = self.tool_handler.lock().await;
         Ok(ListToolsResult {
-            tools: tool_handler.list_tool_metadata(),
+            tools: tool_handler.list_tool_metadata_matching(filter),
             next_cursor: None,
             meta: None, // Required for rmcp 0.12.0
         })
@@ -340,11 +676,9 @@ impl Server {
                 extensions: Extensions::default(),
             };
 
-            executor(call_request).await.map_err(|e| McpError {
-                code: ErrorCode(-32600),
-                message: e.to_string().into(),
-                data: None,
-            })
+            executor(call_request)
+                .await
+                .map_err(Self::tool_error_to_mcp)
         } else {
             Err(McpError {
                 code: ErrorCode(-32601),
@@ -354,6 +688,22 @@ impl Server {
         }
     }
 
+    /// Map an executor error to an MCP error, keeping structured tool-call errors
+    fn tool_error_to_mcp(e: anyhow::Error) -> McpError {
+        match e.downcast_ref::<ToolCallError>() {
+            Some(tool_error) => McpError {
+                code: ErrorCode(tool_error.code()),
+                message: tool_error.to_string().into(),
+                data: Some(tool_error.data()),
+            },
+            None => McpError {
+                code: ErrorCode(-32600),
+                message: e.to_string().into(),
+                data: None,
+            },
+        }
+    }
+
     // --- SSE IS DEAD: Stubbed out ---
     async fn serve_sse(&self) -> Result<()> {
         error!("SSE mode is deprecated and removed. Please use HTTP or Stdio.");
@@ -379,14 +729,72 @@ impl Server {
 
     pub async fn start_with_graceful_shutdown(&self) -> Result<()> {
         // Simple shutdown for Stdio (Ctrl+C kills the process anyway)
+        if self.config.server.mode != ServerMode::Stdio {
+            let server = self.clone();
+            tokio::spawn(async move {
+                shutdown_signal().await;
+                info!("Shutdown signal received, draining connections...");
+                server.shutdown();
+            });
+        }
         self.start().await
     }
 
+    /// Stop accepting connections and let in-flight HTTP requests finish
+    pub fn shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Drop every registered tool not named in `server.tools`; an empty list keeps them all
+    fn apply_tool_allowlist(&self) {
+        let allowlist = &self.config.server.tools;
+        if allowlist.is_empty() {
+            return;
+        }
+        let names = self.registry.names();
+        for name in &names {
+            if !allowlist.contains(name) {
+                self.registry.remove(name);
+            }
+        }
+        for name in allowlist {
+            if !names.contains(name) {
+                warn!("Allowlisted tool '{}' does not exist", name);
+            }
+        }
+        info!(
+            "Tool allowlist kept {} of {} tools",
+            self.registry.count(),
+            names.len()
+        );
+    }
+
+    /// Address the HTTP server listens on once it has bound; resolves port 0 to the actual port
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.local_addr.get().copied()
+    }
+
+    /// Number of registered tools; zero until `setup_tools` (or `start`) has run
     pub fn tool_count(&self) -> usize {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current()
-                .block_on(async { self.tool_handler.lock().await.tool_count() })
-        })
+        self.registry.count()
+    }
+
+    /// Sorted names of the registered tools, as a snapshot
+    pub async fn tool_names(&self) -> Vec<String> {
+        self.registry.names()
+    }
+
+    /// Metadata (including input and output schemas) of the registered tools, sorted by name
+    pub async fn tool_schemas(&self) -> Vec<Tool> {
+        let mut tools = self.registry.list_metadata();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Dry-run every registered route tool with an example built from its schema and
+    /// report the tools whose request is malformed; nothing is sent upstream
+    pub async fn self_test(&self) -> SelfTestReport {
+        run_self_test(&self.registry, self.requester.as_ref()).await
     }
 
     pub async fn get_tool_registry(&self) -> Arc<ToolRegistry> {
@@ -395,10 +803,37 @@ impl Server {
     }
 }
 
+/// Resolves on Ctrl+C, or SIGTERM on Unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
 pub async fn create_server(config: AppConfig) -> Result<Server> {
-    let adjuster = Adjuster::new();
-    let parser = Box::new(SwaggerParser::new(adjuster));
-    let requester =
-        HttpRequester::new(&config.endpoint).context("Failed to create HTTP requester")?;
-    Server::new(config, parser, requester).await
+    ServerBuilder::new(config).build().await
+}
+
+/// Create a server and register its tools, so the tool count is known before `start`
+pub async fn create_and_setup_server(config: AppConfig) -> Result<Server> {
+    let server = create_server(config).await?;
+    server.setup_tools().await?;
+    Ok(server)
 }
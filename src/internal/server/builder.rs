@@ -0,0 +1,61 @@
+// src/internal/server/builder.rs
+
+use anyhow::{Context, Result};
+
+use crate::internal::config::AppConfig;
+use crate::internal::parser::_parser::SwaggerParser;
+use crate::internal::parser::adjuster::Adjuster;
+use crate::internal::parser::types::Parser;
+use crate::internal::requester::{HttpRequester, Requester};
+use crate::internal::server::_server::Server;
+
+/// ServerBuilder assembles a Server, defaulting any dependency that is not supplied
+pub struct ServerBuilder {
+    config: AppConfig,
+    parser: Option<Box<dyn Parser>>,
+    requester: Option<Box<dyn Requester>>,
+}
+
+impl ServerBuilder {
+    /// Start building a server from the given configuration
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config,
+            parser: None,
+            requester: None,
+        }
+    }
+
+    /// Use a custom parser instead of the SwaggerParser
+    pub fn parser(mut self, parser: Box<dyn Parser>) -> Self {
+        self.parser = Some(parser);
+        self
+    }
+
+    /// Use a custom requester instead of an HttpRequester built from the endpoint config
+    pub fn requester(mut self, requester: Box<dyn Requester>) -> Self {
+        self.requester = Some(requester);
+        self
+    }
+
+    /// Build the server
+    pub async fn build(self) -> Result<Server> {
+        let parser = match self.parser {
+            Some(parser) => parser,
+            None => Box::new(SwaggerParser::with_config(
+                Adjuster::new(),
+                self.config.parser.clone(),
+            )),
+        };
+        let requester = match self.requester {
+            Some(requester) => requester,
+            None => Box::new(
+                HttpRequester::new(&self.config.endpoint)
+                    .context("Failed to create HTTP requester")?
+                    .with_trace_context(self.config.server.trace_context),
+            ),
+        };
+
+        Server::new(self.config, parser, requester).await
+    }
+}
@@ -0,0 +1,230 @@
+// src/internal/server/listener.rs
+
+use anyhow::{anyhow, Context, Result};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_rustls::rustls;
+use tokio_rustls::TlsAcceptor;
+
+/// Removes the Unix socket file when the server stops
+#[cfg(unix)]
+pub struct UnixSocketGuard {
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Bind a Unix domain socket, replacing a stale socket file left by a previous run.
+/// A socket that still accepts connections, or a path that is not a socket, is an error.
+#[cfg(unix)]
+pub fn bind_unix_socket(path: &str) -> Result<(tokio::net::UnixListener, UnixSocketGuard)> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let socket_path = Path::new(path);
+    if let Ok(metadata) = std::fs::symlink_metadata(socket_path) {
+        if !metadata.file_type().is_socket() {
+            return Err(anyhow!("{} exists and is not a socket", path));
+        }
+        if std::os::unix::net::UnixStream::connect(socket_path).is_ok() {
+            return Err(anyhow!("{} is in use by another server", path));
+        }
+        std::fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale socket: {}", path))?;
+    }
+
+    let listener = tokio::net::UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind Unix socket: {}", path))?;
+    Ok((
+        listener,
+        UnixSocketGuard {
+            path: socket_path.to_path_buf(),
+        },
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn bind_unix_socket(path: &str) -> Result<()> {
+    Err(anyhow!(
+        "Unix sockets are not supported on this platform: {}",
+        path
+    ))
+}
+
+/// First file descriptor passed by systemd socket activation
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: i32 = 3;
+
+/// Number of sockets systemd passed to this process, given `LISTEN_PID` and `LISTEN_FDS`.
+/// Zero unless `LISTEN_PID` names this process.
+pub fn activation_fd_count(listen_pid: Option<&str>, listen_fds: Option<&str>, pid: u32) -> usize {
+    match listen_pid.and_then(|p| p.trim().parse::<u32>().ok()) {
+        Some(listen_pid) if listen_pid == pid => listen_fds
+            .and_then(|n| n.trim().parse::<usize>().ok())
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Adopt the TCP listener passed by systemd socket activation, if any
+#[cfg(unix)]
+pub fn systemd_tcp_listener() -> Result<Option<tokio::net::TcpListener>> {
+    use std::os::unix::io::FromRawFd;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let count = activation_fd_count(
+        std::env::var("LISTEN_PID").ok().as_deref(),
+        std::env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    );
+    if count == 0 {
+        return Ok(None);
+    }
+    if count > 1 {
+        tracing::warn!(
+            "systemd passed {} sockets, only the first one is used",
+            count
+        );
+    }
+
+    // The variables are left set: changing the environment while other threads may read
+    // it is unsound, and children ignore them since LISTEN_PID names this process. The
+    // descriptor itself must only be adopted once, even if the server is started again.
+    static ADOPTED: AtomicBool = AtomicBool::new(false);
+    if ADOPTED.swap(true, Ordering::SeqCst) {
+        return Ok(None);
+    }
+
+    // SAFETY: LISTEN_PID matched our pid, so systemd handed us this descriptor, and the
+    // flag above ensures nothing else in the process has taken ownership of it
+    let listener = unsafe { std::net::TcpListener::from_raw_fd(SD_LISTEN_FDS_START) };
+    listener
+        .local_addr()
+        .context("Socket passed by systemd is not a TCP listener")?;
+    listener
+        .set_nonblocking(true)
+        .context("Failed to make the systemd socket non-blocking")?;
+
+    let listener = tokio::net::TcpListener::from_std(listener)
+        .context("Failed to adopt the systemd socket")?;
+    Ok(Some(listener))
+}
+
+#[cfg(not(unix))]
+pub fn systemd_tcp_listener() -> Result<Option<tokio::net::TcpListener>> {
+    Ok(None)
+}
+
+/// Time a client gets to complete the TLS handshake before it is dropped
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Load a PEM certificate chain and private key into a rustls server config
+pub fn load_tls_config(cert_path: &str, key_path: &str) -> Result<Arc<rustls::ServerConfig>> {
+    use rustls_pki_types::pem::PemObject;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .with_context(|| format!("Failed to read TLS certificate: {}", cert_path))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid TLS certificate: {}", cert_path))?;
+    if certs.is_empty() {
+        return Err(anyhow!("No certificate found in {}", cert_path));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("Failed to read TLS private key: {}", key_path))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to set up TLS protocol versions")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("TLS certificate and private key do not match")?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(config))
+}
+
+/// Handshaken connections waiting for axum to pick them up
+const TLS_READY_QUEUE: usize = 64;
+
+type TlsConnection = (
+    tokio_rustls::server::TlsStream<tokio::net::TcpStream>,
+    std::net::SocketAddr,
+);
+
+/// TCP listener that performs the TLS handshake before handing connections to axum.
+/// Each handshake runs in its own task, so a slow or idle client cannot hold up others.
+pub struct TlsListener {
+    local_addr: std::io::Result<std::net::SocketAddr>,
+    ready: tokio::sync::mpsc::Receiver<TlsConnection>,
+    accept_loop: tokio::task::JoinHandle<()>,
+}
+
+impl TlsListener {
+    /// Start accepting on `inner`; must be called from within a Tokio runtime
+    pub fn new(inner: tokio::net::TcpListener, config: Arc<rustls::ServerConfig>) -> Self {
+        let local_addr = inner.local_addr();
+        let (tx, ready) = tokio::sync::mpsc::channel(TLS_READY_QUEUE);
+        let accept_loop = tokio::spawn(accept_tls(inner, TlsAcceptor::from(config), tx));
+        Self {
+            local_addr,
+            ready,
+            accept_loop,
+        }
+    }
+}
+
+impl Drop for TlsListener {
+    fn drop(&mut self) {
+        self.accept_loop.abort();
+    }
+}
+
+async fn accept_tls(
+    mut inner: tokio::net::TcpListener,
+    acceptor: TlsAcceptor,
+    tx: tokio::sync::mpsc::Sender<TlsConnection>,
+) {
+    while !tx.is_closed() {
+        let (stream, addr) = axum::serve::Listener::accept(&mut inner).await;
+        let acceptor = acceptor.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            // Failed handshakes only affect that client
+            match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(tls_stream)) => {
+                    let _ = tx.send((tls_stream, addr)).await;
+                }
+                Ok(Err(e)) => tracing::debug!("TLS handshake with {} failed: {}", addr, e),
+                Err(_) => tracing::debug!("TLS handshake with {} timed out", addr),
+            }
+        });
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = tokio_rustls::server::TlsStream<tokio::net::TcpStream>;
+    type Addr = std::net::SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.ready.recv().await {
+            Some(connection) => connection,
+            // The accept loop holds a sender for as long as this listener exists
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        match &self.local_addr {
+            Ok(addr) => Ok(*addr),
+            Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 pub mod _server;
+pub mod builder;
 pub mod handler;
+pub mod listener;
+pub mod self_test;
 pub mod tool;
 
 // Re-export main types
-pub use _server::create_server;
 pub use _server::Server;
+pub use _server::{create_and_setup_server, create_server};
+pub use builder::ServerBuilder;
@@ -0,0 +1,95 @@
+// src/internal/server/self_test.rs
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::internal::mcp::registry::ToolRegistry;
+use crate::internal::parser::example::generate_example;
+use crate::internal::requester::request::DRY_RUN_ARG;
+use crate::internal::requester::Requester;
+
+/// Outcome of running every route tool once against a dry run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SelfTestReport {
+    pub passed: Vec<String>,
+    pub failures: Vec<SelfTestFailure>,
+}
+
+/// A tool whose example call did not produce a well-formed request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SelfTestFailure {
+    pub tool: String,
+    pub reason: String,
+}
+
+impl SelfTestReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Plain-text report listing each failure and the totals
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for failure in &self.failures {
+            report.push_str(&format!("FAIL {}: {}\n", failure.tool, failure.reason));
+        }
+        report.push_str(&format!(
+            "self-test: {} passed, {} failed\n",
+            self.passed.len(),
+            self.failures.len()
+        ));
+        report
+    }
+}
+
+/// Call every route tool with an example built from its input schema, as a dry run,
+/// and check that the composed request has a valid URL with every path parameter filled.
+/// Built-in tools, which call no route, are skipped.
+pub async fn run_self_test(registry: &ToolRegistry, requester: &dyn Requester) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+    for name in registry.names() {
+        let (Some(tool), Some(route)) = (registry.get(&name), registry.get_route(&name)) else {
+            continue;
+        };
+
+        let input_schema = Value::Object(tool.metadata.input_schema.as_ref().clone());
+        let mut arguments = match generate_example(&input_schema) {
+            Value::Object(arguments) => arguments,
+            _ => Default::default(),
+        };
+        arguments.insert(DRY_RUN_ARG.to_string(), Value::Bool(true));
+
+        let result = match requester.build_route_executor(&route) {
+            Ok(executor) => executor(&Value::Object(arguments).to_string())
+                .await
+                .map_err(|e| format!("request failed: {:#}", e))
+                .and_then(|response| check_dry_run(response.status_code, &response.body)),
+            Err(e) => Err(format!("executor could not be built: {:#}", e)),
+        };
+        match result {
+            Ok(()) => report.passed.push(name),
+            Err(reason) => report.failures.push(SelfTestFailure { tool: name, reason }),
+        }
+    }
+    report
+}
+
+/// Check the request described by a dry-run response
+fn check_dry_run(status_code: u16, body: &[u8]) -> Result<(), String> {
+    if status_code >= 400 {
+        return Err(format!("status {}", status_code));
+    }
+    // Requesters without dry-run support answer with their own body; the status is all we have
+    let Some(url) = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|request| request.get("url")?.as_str().map(str::to_string))
+    else {
+        return Ok(());
+    };
+    let parsed = url::Url::parse(&url).map_err(|e| format!("invalid URL {}: {}", url, e))?;
+    let path = parsed.path();
+    if path.contains('{') || path.to_ascii_lowercase().contains("%7b") {
+        return Err(format!("unresolved path parameter in {}", url));
+    }
+    Ok(())
+}
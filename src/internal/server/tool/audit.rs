@@ -0,0 +1,134 @@
+// src/internal/server/tool/audit.rs
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::internal::config::REDACTED;
+
+/// Key of the correlation id in the tool result `meta`
+pub const CORRELATION_ID_META: &str = "correlation_id";
+
+/// How a tool call ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// Upstream answered with a non-error status
+    Success,
+    /// Upstream answered with a 4xx/5xx status
+    UpstreamError,
+    /// No upstream response, e.g. a connection failure or timeout
+    Failed,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub correlation_id: String,
+    pub tool: String,
+    pub arguments: Value,
+    pub outcome: AuditOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// AuditLog appends one JSON record per tool call to a file, apart from the application log
+pub struct AuditLog {
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Open the audit file for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit log: {:?}", path))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append a record; a failed write is logged but never fails the tool call
+    pub fn record(&self, record: &AuditRecord) {
+        let mut line = match serde_json::to_vec(record) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to serialize audit record: {}", e);
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = file.write_all(&line).and_then(|_| file.flush()) {
+            warn!("Failed to write audit record: {}", e);
+        }
+    }
+}
+
+/// New id tying a tool call's audit record to its result
+pub fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Copy of the call arguments with credential-like values replaced, at any depth
+pub fn redact_arguments(arguments: &Map<String, Value>) -> Value {
+    redact_arguments_with(arguments, &[])
+}
+
+/// Like `redact_arguments`, also replacing the values of `keys` (compared case-insensitively)
+pub fn redact_arguments_with(arguments: &Map<String, Value>, keys: &[String]) -> Value {
+    Value::Object(
+        arguments
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_sensitive_argument(key)
+                    || keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+                {
+                    Value::String(REDACTED.to_string())
+                } else {
+                    redact_value(value, keys)
+                };
+                (key.clone(), value)
+            })
+            .collect(),
+    )
+}
+
+/// Copy of any JSON value with `redact_arguments_with` applied to every object in it
+pub fn redact_value(value: &Value, keys: &[String]) -> Value {
+    match value {
+        Value::Object(map) => redact_arguments_with(map, keys),
+        Value::Array(items) => Value::Array(items.iter().map(|v| redact_value(v, keys)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Argument names that usually carry credentials
+fn is_sensitive_argument(name: &str) -> bool {
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    [
+        "password",
+        "secret",
+        "token",
+        "api_key",
+        "apikey",
+        "authorization",
+        "cookie",
+    ]
+    .iter()
+    .any(|needle| name.contains(needle))
+}
@@ -0,0 +1,89 @@
+// src/internal/server/tool/example.rs
+
+use rmcp::model::{CallToolRequest, CallToolResult, Content, Tool, ToolAnnotations};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::handler::ToolExecutor;
+use crate::internal::parser::example::generate_example;
+use crate::internal::parser::types::RouteTool;
+
+/// Name of the synthetic request example tool; a valid Gemini function name
+pub const EXAMPLE_TOOL_NAME: &str = "__example_for";
+
+/// Metadata for the synthetic tool returning a request body example for another tool
+pub fn example_tool() -> Tool {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "tool": {
+                "type": "string",
+                "description": "Name of the tool to get a request body example for"
+            }
+        },
+        "required": ["tool"]
+    });
+    Tool {
+        name: EXAMPLE_TOOL_NAME.into(),
+        title: Some("Request body example".into()),
+        description: Some(
+            "Return a ready-made request body for a tool: the example from the OpenAPI spec, or one generated from the body schema."
+                .into(),
+        ),
+        input_schema: Arc::new(schema.as_object().cloned().unwrap_or_default()),
+        output_schema: None,
+        annotations: Some(ToolAnnotations::new().read_only(true).open_world(false)),
+        icons: None,
+        meta: None,
+    }
+}
+
+/// Example payload per tool that takes a request body.
+/// `source` is `spec` for a declared example and `generated` otherwise.
+pub fn example_payloads(route_tools: &[RouteTool]) -> HashMap<String, Value> {
+    route_tools
+        .iter()
+        .filter_map(|route_tool| {
+            let (source, example) = match &route_tool.route_config.method_config.body_example {
+                Some(example) => ("spec", example.clone()),
+                None => {
+                    let body = route_tool
+                        .tool
+                        .input_schema
+                        .get("properties")?
+                        .get("body")?;
+                    ("generated", generate_example(body))
+                }
+            };
+            let name = route_tool.tool.name.to_string();
+            let payload = json!({ "tool": name, "source": source, "example": example });
+            Some((name, payload))
+        })
+        .collect()
+}
+
+/// Executor answering with the precomputed example of the named tool
+pub fn example_executor(payloads: HashMap<String, Value>) -> ToolExecutor {
+    let payloads = Arc::new(payloads);
+    Arc::new(move |request: CallToolRequest| {
+        let payloads = payloads.clone();
+        Box::pin(async move {
+            let name = request
+                .params
+                .arguments
+                .as_ref()
+                .and_then(|args| args.get("tool"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let result = match payloads.get(name) {
+                Some(payload) => CallToolResult::success(vec![Content::text(payload.to_string())]),
+                None => CallToolResult::error(vec![Content::text(format!(
+                    "No request body example for tool '{}': it is unknown or takes no body",
+                    name
+                ))]),
+            };
+            Ok(result)
+        })
+    })
+}
@@ -1,13 +1,34 @@
 // src/internal/server/tool/handler.rs
 
 use crate::internal::mcp::registry::{RegisteredTool, ToolRegistry};
+use crate::internal::server::tool::audit::{
+    new_correlation_id, redact_arguments, AuditLog, AuditOutcome, AuditRecord, CORRELATION_ID_META,
+};
+use crate::internal::server::tool::validation::ArgumentValidator;
 use anyhow::{anyhow, Result};
-use rmcp::model::{Annotated, CallToolRequest, CallToolResult, RawContent, RawTextContent, Tool};
-use serde_json::Map;
+use rmcp::model::{
+    Annotated, CallToolRequest, CallToolResult, JsonObject, Meta, RawContent, RawTextContent, Tool,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::debug;
+use std::time::Instant;
+use tracing::{debug, info, info_span, warn, Instrument};
 
-use crate::internal::requester::RouteExecutor;
+use crate::internal::requester::request::TRACEPARENT_ARG;
+use crate::internal::requester::{
+    coerce_arguments, is_success_status, HttpResponse, JsonPath, RouteConfig, RouteExecutor,
+    TraceContext,
+};
+
+/// Key of the upstream HTTP status code in the tool result `meta`
+pub const HTTP_STATUS_META: &str = "http_status";
+
+/// Key of the upstream `ETag`, when sent, in the tool result `meta`
+pub const ETAG_META: &str = "etag";
+
+/// Property holding a non-object output in the output schema envelope
+pub const OUTPUT_RESULT_PROPERTY: &str = "result";
 
 // Simplify the ToolExecutor to avoid lifetime issues
 pub type ToolExecutor = Arc<
@@ -19,10 +40,38 @@ pub type ToolExecutor = Arc<
         + Sync,
 >;
 
+/// Errors raised by a tool call before it reaches the upstream API
+#[derive(Debug, thiserror::Error)]
+pub enum ToolCallError {
+    #[error("Invalid arguments: {}", .0.join("; "))]
+    InvalidArguments(Vec<String>),
+}
+
+impl ToolCallError {
+    /// JSON-RPC error code for this error
+    pub fn code(&self) -> i32 {
+        match self {
+            ToolCallError::InvalidArguments(_) => -32602,
+        }
+    }
+
+    /// Structured JSON-RPC error data for this error
+    pub fn data(&self) -> serde_json::Value {
+        match self {
+            ToolCallError::InvalidArguments(violations) => {
+                serde_json::json!({ "violations": violations })
+            }
+        }
+    }
+}
+
 /// Handler manages tool execution and authentication
 pub struct ToolHandler {
     auth_enabled: bool,
+    validate_arguments: bool,
     registry: Arc<ToolRegistry>,
+    audit_log: Option<Arc<AuditLog>>,
+    trace_context: bool,
 }
 
 impl ToolHandler {
@@ -30,16 +79,127 @@ impl ToolHandler {
     pub fn new(auth_enabled: bool, registry: Arc<ToolRegistry>) -> Self {
         Self {
             auth_enabled,
+            validate_arguments: false,
             registry,
+            audit_log: None,
+            trace_context: false,
         }
     }
 
+    /// Enable validation of call arguments against each tool's input schema
+    pub fn with_argument_validation(mut self, enabled: bool) -> Self {
+        self.validate_arguments = enabled;
+        self
+    }
+
+    /// Write an audit record for every call made through handlers created afterwards
+    pub fn with_audit_log(mut self, audit_log: Option<Arc<AuditLog>>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Propagate W3C trace context upstream from handlers created afterwards
+    pub fn with_trace_context(mut self, enabled: bool) -> Self {
+        self.trace_context = enabled;
+        self
+    }
+
     /// Register a tool with its executor
     pub fn register_tool(&mut self, name: &str, metadata: Tool, executor: ToolExecutor) {
-        let registered_tool = RegisteredTool { metadata, executor };
+        self.register(name, metadata, executor, None);
+    }
+
+    /// Register a tool that calls an upstream route, keeping the route for introspection
+    pub fn register_route_tool(
+        &mut self,
+        name: &str,
+        metadata: Tool,
+        executor: ToolExecutor,
+        route: RouteConfig,
+    ) {
+        self.register(name, metadata, executor, Some(route));
+    }
+
+    fn register(
+        &mut self,
+        name: &str,
+        mut metadata: Tool,
+        executor: ToolExecutor,
+        route: Option<RouteConfig>,
+    ) {
+        metadata.output_schema = metadata.output_schema.map(Self::object_output_schema);
+        let executor = if self.validate_arguments {
+            Self::with_validation(name, &metadata, executor)
+        } else {
+            executor
+        };
+        let registered_tool = RegisteredTool {
+            metadata,
+            executor,
+            route,
+        };
         self.registry.register(name.to_string(), registered_tool);
     }
 
+    /// MCP output schemas must describe an object, so array or scalar schemas
+    /// are wrapped as the `result` property of an object envelope
+    fn object_output_schema(schema: Arc<JsonObject>) -> Arc<JsonObject> {
+        if schema.get("type").and_then(|t| t.as_str()) == Some("object") {
+            return schema;
+        }
+        let mut properties = Map::new();
+        properties.insert(
+            OUTPUT_RESULT_PROPERTY.to_string(),
+            serde_json::Value::Object(schema.as_ref().clone()),
+        );
+
+        let mut envelope = Map::new();
+        envelope.insert("type".to_string(), "object".into());
+        envelope.insert("properties".to_string(), properties.into());
+        envelope.insert(
+            "required".to_string(),
+            serde_json::json!([OUTPUT_RESULT_PROPERTY]),
+        );
+        Arc::new(envelope)
+    }
+
+    /// Wrap an executor so arguments are checked against the tool's input schema first.
+    /// `"1"`/`"true"` style strings are coerced to their declared types before the check,
+    /// as the route executor would, and the coerced arguments are passed on.
+    fn with_validation(name: &str, metadata: &Tool, executor: ToolExecutor) -> ToolExecutor {
+        let validator = match ArgumentValidator::new(name, &metadata.input_schema) {
+            Some(validator) => Arc::new(validator),
+            None => return executor,
+        };
+        let param_schemas: Arc<HashMap<String, Value>> = Arc::new(
+            metadata
+                .input_schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .map(|props| props.clone().into_iter().collect())
+                .unwrap_or_default(),
+        );
+
+        Arc::new(move |mut request: CallToolRequest| {
+            let mut arguments = request.params.arguments.take().unwrap_or_default();
+            coerce_arguments(&mut arguments, &param_schemas);
+            if let Err(violations) = validator.validate(&arguments) {
+                debug!(
+                    "Rejecting call to {} with {} argument violation(s)",
+                    request.params.name,
+                    violations.len()
+                );
+                return Box::pin(async move {
+                    Err(anyhow::Error::new(ToolCallError::InvalidArguments(
+                        violations,
+                    )))
+                });
+            }
+            request.params.arguments = Some(arguments);
+            executor(request)
+        })
+    }
+
     /// Get an executor for a tool
     pub fn get_executor(&self, name: &str) -> Option<ToolExecutor> {
         self.registry.get(name).map(|t| t.executor.clone())
@@ -55,14 +215,37 @@ impl ToolHandler {
         self.registry.list_metadata()
     }
 
+    /// List the tool metadata matching a `tools/list` filter
+    pub fn list_tool_metadata_matching(&self, filter: Option<&str>) -> Vec<Tool> {
+        self.registry.list_metadata_matching(filter)
+    }
+
     /// Create a handler function for a specific tool
     pub fn create_handler(&self, tool_name: &str, executor: RouteExecutor) -> ToolExecutor {
+        self.create_route_handler(tool_name, executor, &RouteConfig::default())
+    }
+
+    /// Create a handler that applies the route's `success_statuses` (200-399 when empty)
+    /// and `error_field` when deciding whether a response is an error
+    pub fn create_route_handler(
+        &self,
+        tool_name: &str,
+        executor: RouteExecutor,
+        route: &RouteConfig,
+    ) -> ToolExecutor {
         let tool_name = tool_name.to_string();
+        let success_statuses = route.method_config.success_statuses.clone();
+        let error_field = route.method_config.error_field.clone();
         let auth_enabled = self.auth_enabled;
+        let audit_log = self.audit_log.clone();
+        let trace_context = self.trace_context;
 
         Arc::new(move |request: CallToolRequest| {
             let tool_name = tool_name.clone();
             let executor = executor.clone(); // Clone the async executor
+            let audit_log = audit_log.clone();
+            let success_statuses = success_statuses.clone();
+            let error_field = error_field.clone();
 
             Box::pin(async move {
                 // Validate authentication if enabled
@@ -74,20 +257,147 @@ impl ToolHandler {
                 }
 
                 // Execute the tool request
-                let params = if let Some(args) = &request.params.arguments {
-                    Self::convert_arguments_to_json(args)
-                } else {
-                    "{}".to_string()
+                let mut arguments = request.params.arguments.clone().unwrap_or_default();
+                // The upstream call is a child of the caller's span, or the root of a new trace
+                let trace = trace_context.then(|| {
+                    let trace = arguments
+                        .get(TRACEPARENT_ARG)
+                        .and_then(serde_json::Value::as_str)
+                        .and_then(TraceContext::parse)
+                        .map(|parent| parent.child())
+                        .unwrap_or_else(TraceContext::new_root);
+                    arguments.insert(TRACEPARENT_ARG.to_string(), trace.to_header().into());
+                    trace
+                });
+                let params = Self::convert_arguments_to_json(&arguments);
+
+                // Time the upstream call inside a span so every log line carries the tool name
+                let correlation_id = audit_log.as_ref().map(|_| new_correlation_id());
+                let span = info_span!(
+                    "tool_call",
+                    tool = %tool_name,
+                    correlation_id = correlation_id.as_deref().unwrap_or_default(),
+                    trace_id = trace.as_ref().map(|t| t.trace_id.as_str()).unwrap_or_default()
+                );
+                let started = Instant::now();
+                let outcome = executor(&params).instrument(span.clone()).await;
+                let duration_ms = started.elapsed().as_millis() as u64;
+                // Some APIs report failures inside a successful body
+                let embedded_error = match (&outcome, &error_field) {
+                    (Ok(response), Some(error_field))
+                        if is_success_status(&success_statuses, response.status_code) =>
+                    {
+                        Self::embedded_error(response, error_field)
+                    }
+                    _ => None,
                 };
 
-                // Now executor is async, so we can await it directly
-                let response = executor(&params).await.map_err(|e| {
-                    anyhow!("Failed to execute request for tool {}: {}", tool_name, e)
-                })?;
+                if let (Some(audit_log), Some(correlation_id)) = (&audit_log, &correlation_id) {
+                    let (outcome, status, error) = match &outcome {
+                        Ok(response)
+                            if embedded_error.is_some()
+                                || !is_success_status(&success_statuses, response.status_code) =>
+                        {
+                            (
+                                AuditOutcome::UpstreamError,
+                                Some(response.status_code),
+                                None,
+                            )
+                        }
+                        Ok(response) => (AuditOutcome::Success, Some(response.status_code), None),
+                        Err(e) => (AuditOutcome::Failed, None, Some(e.to_string())),
+                    };
+                    audit_log.record(&AuditRecord {
+                        timestamp: chrono::Utc::now(),
+                        correlation_id: correlation_id.clone(),
+                        tool: tool_name.clone(),
+                        arguments: redact_arguments(
+                            request.params.arguments.as_ref().unwrap_or(&Map::new()),
+                        ),
+                        outcome,
+                        status,
+                        error,
+                        duration_ms,
+                    });
+                }
+                let response = match outcome {
+                    Ok(response) => {
+                        span.in_scope(|| {
+                            info!(
+                                status = response.status_code,
+                                duration_ms,
+                                body_bytes = response.body.len(),
+                                "Tool call completed"
+                            )
+                        });
+                        response
+                    }
+                    Err(e) => {
+                        span.in_scope(
+                            || warn!(duration_ms, error = %e, "Tool call failed before a response"),
+                        );
+                        // Argument errors keep their type, so the client gets -32602
+                        if e.is::<ToolCallError>() {
+                            return Err(e);
+                        }
+                        return Err(anyhow!(
+                            "Failed to execute request for tool {}: {}",
+                            tool_name,
+                            e
+                        ));
+                    }
+                };
+
+                // Clients see the upstream status (200 vs 201 vs 204) next to the content
+                let mut meta = Meta::new();
+                meta.0
+                    .insert(HTTP_STATUS_META.to_string(), response.status_code.into());
+                // The id lets a client match a result to its audit record
+                if let Some(id) = correlation_id {
+                    meta.0.insert(
+                        CORRELATION_ID_META.to_string(),
+                        serde_json::Value::String(id),
+                    );
+                }
+                // Clients pass the ETag back as `__if_none_match` to poll cheaply
+                let etag = response
+                    .headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("etag"))
+                    .map(|(_, v)| v.clone());
+                if let Some(etag) = &etag {
+                    meta.0.insert(ETAG_META.to_string(), etag.clone().into());
+                }
+                let meta = Some(meta);
+
+                // The cached representation is still current: not an error, and there is no body
+                if response.status_code == 304 {
+                    let not_modified = serde_json::json!({
+                        "status": 304,
+                        "not_modified": true,
+                        "etag": etag,
+                    });
+                    return Ok(CallToolResult {
+                        content: vec![Annotated {
+                            annotations: None,
+                            raw: RawContent::Text(RawTextContent {
+                                text: not_modified.to_string(),
+                                meta: None,
+                            }),
+                        }],
+                        is_error: Some(false),
+                        meta,
+                        structured_content: Some(not_modified),
+                    });
+                }
 
                 // Handle error responses
-                if response.status_code >= 400 {
-                    let error_message = String::from_utf8_lossy(&response.body).to_string();
+                if !is_success_status(&success_statuses, response.status_code) {
+                    let problem = Self::problem_details(&response);
+                    let error_message = match problem.as_ref().and_then(Self::problem_summary) {
+                        Some(summary) => summary,
+                        None => String::from_utf8_lossy(&response.body).to_string(),
+                    };
                     return Ok(CallToolResult {
                         content: vec![Annotated {
                             annotations: None,
@@ -97,11 +407,46 @@ impl ToolHandler {
                             }),
                         }],
                         is_error: Some(true),
-                        meta: None,
+                        meta,
+                        structured_content: problem,
+                    });
+                }
+
+                if let Some(message) = embedded_error {
+                    return Ok(CallToolResult {
+                        content: vec![Annotated {
+                            annotations: None,
+                            raw: RawContent::Text(RawTextContent {
+                                text: message,
+                                meta: None,
+                            }),
+                        }],
+                        is_error: Some(true),
+                        meta,
                         structured_content: None,
                     });
                 }
 
+                // A blank text result reads as a failure, so say there was no content
+                if response.body.iter().all(u8::is_ascii_whitespace) {
+                    let empty = serde_json::json!({
+                        "status": response.status_code,
+                        "body": null,
+                    });
+                    return Ok(CallToolResult {
+                        content: vec![Annotated {
+                            annotations: None,
+                            raw: RawContent::Text(RawTextContent {
+                                text: empty.to_string(),
+                                meta: None,
+                            }),
+                        }],
+                        is_error: Some(false),
+                        meta,
+                        structured_content: Some(empty),
+                    });
+                }
+
                 // Convert successful response to text content
                 let text_content = String::from_utf8_lossy(&response.body).to_string();
 
@@ -116,12 +461,59 @@ impl ToolHandler {
                 Ok(CallToolResult {
                     content: vec![content],
                     is_error: Some(false),
-                    meta: None,
+                    meta,
                     structured_content: None,
                 })
             })
         })
     }
+    /// RFC 7807 fields of an `application/problem+json` error body, `None` for other bodies
+    fn problem_details(response: &HttpResponse) -> Option<serde_json::Value> {
+        let is_problem = response
+            .headers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case("content-type") && v.contains("problem+json"));
+        if !is_problem {
+            return None;
+        }
+        let body: Map<String, serde_json::Value> = serde_json::from_slice(&response.body).ok()?;
+
+        let mut problem = Map::new();
+        for field in ["type", "title", "detail", "instance"] {
+            if let Some(value) = body.get(field).filter(|v| v.is_string()) {
+                problem.insert(field.to_string(), value.clone());
+            }
+        }
+        let status = body
+            .get("status")
+            .filter(|v| v.is_u64())
+            .cloned()
+            .unwrap_or_else(|| response.status_code.into());
+        problem.insert("status".to_string(), status);
+        Some(serde_json::Value::Object(problem))
+    }
+
+    /// Message at the error field of a JSON body when it is truthy: strings as is,
+    /// other values as JSON
+    fn embedded_error(response: &HttpResponse, error_field: &JsonPath) -> Option<String> {
+        let body: serde_json::Value = serde_json::from_slice(&response.body).ok()?;
+        error_field.find_truthy(&body).map(|value| match value {
+            serde_json::Value::String(message) => message.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// "title: detail" text for a problem, `None` when it has neither
+    fn problem_summary(problem: &serde_json::Value) -> Option<String> {
+        let title = problem.get("title").and_then(|v| v.as_str());
+        let detail = problem.get("detail").and_then(|v| v.as_str());
+        match (title, detail) {
+            (Some(title), Some(detail)) => Some(format!("{}: {}", title, detail)),
+            (Some(text), None) | (None, Some(text)) => Some(text.to_string()),
+            (None, None) => None,
+        }
+    }
+
     /// Convert MCP tool arguments to JSON string for the executor
     fn convert_arguments_to_json(arguments: &Map<String, serde_json::Value>) -> String {
         serde_json::to_string(arguments).unwrap_or_else(|_| "{}".to_string())
@@ -0,0 +1,113 @@
+// src/internal/server/tool/info.rs
+
+use rmcp::model::{CallToolRequest, CallToolResult, Content, Tool, ToolAnnotations};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::handler::ToolExecutor;
+use crate::internal::parser::types::{RouteTool, SpecInfo};
+
+/// Name of the synthetic API metadata tool; a valid Gemini function name
+pub const INFO_TOOL_NAME: &str = "__openapi_info";
+
+/// Metadata for the synthetic tool describing the API itself
+pub fn info_tool() -> Tool {
+    let schema = json!({ "type": "object", "properties": {} });
+    Tool {
+        name: INFO_TOOL_NAME.into(),
+        title: Some("API information".into()),
+        description: Some(
+            "Describe the underlying API: title, version and description from the OpenAPI spec, and the operation behind every tool."
+                .into(),
+        ),
+        input_schema: Arc::new(schema.as_object().cloned().unwrap_or_default()),
+        output_schema: None,
+        annotations: Some(ToolAnnotations::new().read_only(true).open_world(false)),
+        icons: None,
+        meta: None,
+    }
+}
+
+/// Payload returned by the info tool.
+/// `apis` has one entry per spec, with the tool prefix when several specs are merged.
+pub fn info_payload(specs: &[(Option<String>, SpecInfo)], route_tools: &[RouteTool]) -> Value {
+    let apis: Vec<Value> = specs
+        .iter()
+        .map(|(tool_prefix, info)| {
+            let mut api = serde_json::to_value(info).unwrap_or_default();
+            if let (Some(prefix), Some(map)) = (tool_prefix, api.as_object_mut()) {
+                map.insert("tool_prefix".to_string(), json!(prefix));
+            }
+            api
+        })
+        .collect();
+
+    let operations: Vec<Value> = route_tools
+        .iter()
+        .map(|route_tool| {
+            let mut operation = json!({
+                "tool": route_tool.tool.name,
+                "method": route_tool.route_config.method,
+                "path": route_tool.route_config.path,
+            });
+            if let (Some(title), Some(map)) = (&route_tool.tool.title, operation.as_object_mut()) {
+                map.insert("title".to_string(), json!(title));
+            }
+            operation
+        })
+        .collect();
+
+    json!({ "apis": apis, "operations": operations })
+}
+
+/// Server instructions from the specs' `info.description`, cut to `max_len` characters
+/// (0 = unlimited). Merged specs each get a paragraph headed by their title.
+pub fn spec_instructions(specs: &[(Option<String>, SpecInfo)], max_len: usize) -> Option<String> {
+    let described: Vec<&SpecInfo> = specs
+        .iter()
+        .map(|(_, info)| info)
+        .filter(|info| {
+            info.description
+                .as_deref()
+                .is_some_and(|d| !d.trim().is_empty())
+        })
+        .collect();
+    let instructions = match described.as_slice() {
+        [] => return None,
+        [info] => info
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        infos => infos
+            .iter()
+            .map(|info| {
+                format!(
+                    "{}: {}",
+                    info.title,
+                    info.description.as_deref().unwrap_or_default().trim()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+
+    if max_len == 0 || instructions.chars().count() <= max_len {
+        return Some(instructions);
+    }
+    let kept: String = instructions
+        .chars()
+        .take(max_len.saturating_sub(3))
+        .collect();
+    Some(format!("{}...", kept.trim_end()))
+}
+
+/// Executor that answers every call with the precomputed payload
+pub fn info_executor(payload: Value) -> ToolExecutor {
+    let text = serde_json::to_string(&payload).unwrap_or_default();
+    Arc::new(move |_request: CallToolRequest| {
+        let text = text.clone();
+        Box::pin(async move { Ok(CallToolResult::success(vec![Content::text(text)])) })
+    })
+}
@@ -1,2 +1,8 @@
+pub mod audit;
+pub mod example;
 pub mod handler;
-pub use handler::ToolHandler;
+pub mod info;
+pub mod validation;
+pub use example::EXAMPLE_TOOL_NAME;
+pub use handler::{ToolCallError, ToolHandler};
+pub use info::INFO_TOOL_NAME;
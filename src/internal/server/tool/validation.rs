@@ -0,0 +1,49 @@
+// src/internal/server/tool/validation.rs
+
+use serde_json::{Map, Value};
+use tracing::warn;
+
+/// ArgumentValidator checks tool-call arguments against a tool's input schema
+pub struct ArgumentValidator {
+    validator: jsonschema::Validator,
+}
+
+impl ArgumentValidator {
+    /// Compile a validator for the given input schema.
+    /// Returns None (and logs) when the schema itself is not valid JSON Schema.
+    pub fn new(tool_name: &str, input_schema: &Map<String, Value>) -> Option<Self> {
+        match jsonschema::validator_for(&Value::Object(input_schema.clone())) {
+            Ok(validator) => Some(Self { validator }),
+            Err(e) => {
+                warn!(
+                    "Skipping argument validation for tool {}: invalid input schema: {}",
+                    tool_name, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Validate arguments, returning one message per violation
+    pub fn validate(&self, arguments: &Map<String, Value>) -> Result<(), Vec<String>> {
+        let instance = Value::Object(arguments.clone());
+        let violations: Vec<String> = self
+            .validator
+            .iter_errors(&instance)
+            .map(|e| {
+                let location = e.instance_path().to_string();
+                if location.is_empty() {
+                    e.to_string()
+                } else {
+                    format!("{}: {}", location, e)
+                }
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
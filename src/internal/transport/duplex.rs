@@ -0,0 +1,68 @@
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf, WriteHalf};
+
+use async_trait::async_trait;
+
+use super::{Transport, TransportError};
+
+/// In-memory transport over `tokio::io::duplex`, framed like STDIO (one JSON message per line)
+pub struct DuplexTransport {
+    reader: BufReader<ReadHalf<DuplexStream>>,
+    writer: WriteHalf<DuplexStream>,
+    buffer: Vec<u8>,
+    connected: bool,
+}
+
+impl DuplexTransport {
+    /// Create two connected ends; whatever one end writes, the other reads.
+    /// `max_buf_size` bounds the bytes in flight in each direction.
+    pub fn pair(max_buf_size: usize) -> (Self, Self) {
+        let (a, b) = tokio::io::duplex(max_buf_size);
+        (Self::from_stream(a), Self::from_stream(b))
+    }
+
+    /// Wrap one end of an existing `tokio::io::duplex` pipe
+    pub fn from_stream(stream: DuplexStream) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+            buffer: Vec::new(),
+            connected: true,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for DuplexTransport {
+    async fn read_message(&mut self) -> Result<Vec<u8>, TransportError> {
+        // Cancel-safe like `StdioTransport`: a partial line survives in `buffer`
+        self.reader.read_until(b'\n', &mut self.buffer).await?;
+
+        if self.buffer.is_empty() {
+            self.connected = false;
+            return Err(TransportError::Closed);
+        }
+
+        let line = std::mem::take(&mut self.buffer);
+        let len = line
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        Ok(line[..len].to_vec())
+    }
+
+    async fn write_message(&mut self, data: &[u8]) -> Result<(), TransportError> {
+        self.writer.write_all(data).await?;
+        self.writer.write_all(b"\n").await?;
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), TransportError> {
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
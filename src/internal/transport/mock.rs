@@ -2,18 +2,23 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
+use tokio::sync::Notify;
+use tracing::trace;
 
 use crate::internal::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
 
 use super::{Transport, TransportError};
 
-/// Mock transport for testing - allows injecting requests and capturing responses
+/// Mock transport for testing - allows injecting requests and capturing responses.
+/// The locks are only held for a push or pop, never across an await.
 #[derive(Clone)]
 pub struct MockTransport {
     /// Queued inputs to be "read"
     pub inputs: Arc<Mutex<VecDeque<Vec<u8>>>>,
     /// Captured outputs
     pub outputs: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// Signalled after every captured output
+    written: Arc<Notify>,
 }
 
 impl MockTransport {
@@ -21,6 +26,7 @@ impl MockTransport {
         Self {
             inputs: Arc::new(Mutex::new(VecDeque::new())),
             outputs: Arc::new(Mutex::new(Vec::new())),
+            written: Arc::new(Notify::new()),
         }
     }
 
@@ -47,29 +53,45 @@ impl MockTransport {
             .filter_map(|data| serde_json::from_slice(data).ok())
             .collect()
     }
+
+    /// Wait until at least `n` responses were captured and return them all.
+    /// Wrap in `tokio::time::timeout` to bound a test that may never see them.
+    pub async fn wait_for_responses(&self, n: usize) -> Vec<JsonRpcResponse> {
+        loop {
+            // Register before checking so a write between the check and the await is not missed
+            let written = self.written.notified();
+            tokio::pin!(written);
+            written.as_mut().enable();
+
+            let responses = self.get_responses();
+            if responses.len() >= n {
+                return responses;
+            }
+            written.await;
+        }
+    }
 }
 
 #[async_trait]
 impl Transport for MockTransport {
     async fn read_message(&mut self) -> Result<Vec<u8>, TransportError> {
-        match self.inputs.lock().unwrap().pop_front() {
+        let next = self.inputs.lock().unwrap().pop_front();
+        match next {
             Some(data) => {
-                eprintln!("[MockTransport] read_message: read {} bytes", data.len());
+                trace!(bytes = data.len(), "MockTransport read");
                 Ok(data)
             }
             None => {
-                eprintln!("[MockTransport] read_message: no more inputs, returning Closed");
+                trace!("MockTransport has no more inputs, closing");
                 Err(TransportError::Closed)
             }
         }
     }
 
     async fn write_message(&mut self, data: &[u8]) -> Result<(), TransportError> {
-        eprintln!(
-            "[MockTransport] write_message: writing {} bytes to outputs",
-            data.len()
-        );
+        trace!(bytes = data.len(), "MockTransport write");
         self.outputs.lock().unwrap().push(data.to_vec());
+        self.written.notify_waiters();
         Ok(())
     }
 
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+pub mod duplex;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod mock;
 pub mod runner;
@@ -1,9 +1,10 @@
 use std::sync::Arc;
+use tracing::{debug, trace, warn};
 
 use crate::internal::{
     mcp::{
         processor::McpProcessor,
-        protocol::{JsonRpcError, JsonRpcResponse},
+        protocol::{JsonRpcError, JsonRpcNotification, JsonRpcResponse, TOOLS_LIST_CHANGED},
     },
     transport::{Transport, TransportError},
 };
@@ -22,36 +23,46 @@ impl<T: Transport> TransportRunner<T> {
     }
 
     pub async fn run(&mut self) -> Result<(), TransportError> {
-        eprintln!("[TransportRunner] Starting run loop");
+        debug!("Transport runner started");
+        let mut tool_changes = self.processor.tool_changes();
+        // Clients are only told about changes once they have initialized
+        let mut initialized = false;
         loop {
-            let input = match self.transport.read_message().await {
+            let read = tokio::select! {
+                read = self.transport.read_message() => read,
+                Ok(()) = tool_changes.changed() => {
+                    if initialized {
+                        debug!("Tool set changed, notifying client");
+                        let notification = JsonRpcNotification::new(TOOLS_LIST_CHANGED);
+                        let output = McpProcessor::serialize_notification(&notification);
+                        self.transport.write_message(&output).await?;
+                        self.transport.flush().await?;
+                    }
+                    continue;
+                }
+            };
+            let input = match read {
                 Ok(data) => data,
                 Err(TransportError::Closed) => {
-                    eprintln!("[TransportRunner] Transport closed, exiting loop");
+                    debug!("Transport closed, stopping runner");
                     break;
                 }
                 Err(e) => {
-                    eprintln!("[TransportRunner] Transport error: {:?}", e);
+                    warn!(error = %e, "Transport read failed");
                     return Err(e);
                 }
             };
 
-            eprintln!(
-                "[TransportRunner] Received {} bytes for processing",
-                input.len()
-            );
+            trace!(bytes = input.len(), "Message received");
 
             // Parse request
             let request = match McpProcessor::parse_request(&input) {
                 Ok(req) => {
-                    eprintln!(
-                        "[TransportRunner] Successfully parsed request: method={}",
-                        req.method
-                    );
+                    debug!(method = %req.method, id = ?req.id, "Request parsed");
                     req
                 }
                 Err(e) => {
-                    eprintln!("[TransportRunner] Parse error: {}", e);
+                    debug!(error = %e, "Request could not be parsed");
                     let error_response = JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         id: None, // Parse errors usually don't have an ID
@@ -71,24 +82,31 @@ impl<T: Transport> TransportRunner<T> {
 
             // Process request
             let response = self.processor.process_request(&request).await;
-            eprintln!(
-                "[TransportRunner] Processed request. Response: has_result={}, has_error={}",
-                response.result.is_some(),
-                response.error.is_some()
+            if request.method == "initialize" && response.error.is_none() {
+                initialized = true;
+                // Changes before now are already reflected in the client's first listing
+                tool_changes.mark_unchanged();
+            }
+            debug!(
+                method = %request.method,
+                id = ?request.id,
+                has_result = response.result.is_some(),
+                has_error = response.error.is_some(),
+                "Request processed"
             );
 
             // Send response (skip for notifications)
             if request.id.is_some() {
-                eprintln!("[TransportRunner] Writing response for id={:?}", request.id);
                 let output = McpProcessor::serialize_response(&response);
+                trace!(id = ?request.id, bytes = output.len(), "Writing response");
                 self.transport.write_message(&output).await?;
                 self.transport.flush().await?;
             } else {
-                eprintln!("[TransportRunner] Skipping response for notification (no ID)");
+                trace!(method = %request.method, "Notification, no response sent");
             }
         }
 
-        eprintln!("[TransportRunner] Run loop finished successfully");
+        debug!("Transport runner finished");
         Ok(())
     }
 }
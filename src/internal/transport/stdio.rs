@@ -8,7 +8,7 @@ use super::{Transport, TransportError};
 pub struct StdioTransport {
     stdin: BufReader<Stdin>,
     stdout: Stdout,
-    buffer: String,
+    buffer: Vec<u8>,
 }
 
 impl Default for StdioTransport {
@@ -22,7 +22,7 @@ impl StdioTransport {
         Self {
             stdin: BufReader::new(tokio::io::stdin()),
             stdout: tokio::io::stdout(),
-            buffer: String::new(),
+            buffer: Vec::new(),
         }
     }
 }
@@ -30,16 +30,22 @@ impl StdioTransport {
 #[async_trait]
 impl Transport for StdioTransport {
     async fn read_message(&mut self) -> Result<Vec<u8>, TransportError> {
-        self.buffer.clear();
-        let bytes_read = self.stdin.read_line(&mut self.buffer).await?;
+        // `read_until` is cancel-safe: when the runner drops this future to send a
+        // notification, the bytes of a half-read line stay in `buffer` and the next
+        // call appends the rest. `read_line` would lose them.
+        self.stdin.read_until(b'\n', &mut self.buffer).await?;
 
-        if bytes_read == 0 {
+        if self.buffer.is_empty() {
             return Err(TransportError::Closed);
         }
 
         // Trim trailing newline
-        let trimmed = self.buffer.trim_end();
-        Ok(trimmed.as_bytes().to_vec())
+        let line = std::mem::take(&mut self.buffer);
+        let len = line
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        Ok(line[..len].to_vec())
     }
 
     async fn write_message(&mut self, data: &[u8]) -> Result<(), TransportError> {
@@ -1,11 +1,36 @@
 use tracing::{error, info};
-use yas_mcp::cli::{build_cli, parse_config};
-use yas_mcp::internal::server::create_server;
+use yas_mcp::cli::{build_cli, parse_config, run_diff, run_validate};
+use yas_mcp::internal::server::create_and_setup_server;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Parse command line arguments first
     let matches = build_cli().get_matches();
+    if let Some(("diff", diff_matches)) = matches.subcommand() {
+        match run_diff(diff_matches) {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("Failed to diff specs: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    if let Some(("validate", validate_matches)) = matches.subcommand() {
+        match run_validate(validate_matches) {
+            Ok((report, ok)) => {
+                println!("{}", report);
+                if !ok {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to validate spec: {:#}", e);
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
     let config = match parse_config(&matches) {
         Ok(config) => config,
         Err(e) => {
@@ -14,6 +39,11 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    if matches.get_flag("print-config") {
+        println!("{}", serde_json::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+
     // Initialize logging
     if let Err(e) = yas_mcp::internal::logger::init_logger(&config.logging) {
         eprintln!("Failed to initialize logger: {}", e);
@@ -29,20 +59,29 @@ async fn main() -> anyhow::Result<()> {
         info!("Adjustments file: {}", adjustments_file);
     }
 
-    // Create and start server - this now includes tool setup
-    let server = match create_server(config).await {
+    // Parse the spec and register tools before serving
+    let server = match create_and_setup_server(config).await {
         Ok(server) => server,
         Err(e) => {
-            error!("Failed to create server: {}", e);
+            error!("Failed to create server: {:#}", e);
             std::process::exit(1);
         }
     };
 
     info!("Server initialized with {} tools", server.tool_count());
 
+    if matches.get_flag("self-test") {
+        let report = server.self_test().await;
+        print!("{}", report.report());
+        if !report.is_ok() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Start server with graceful shutdown
     if let Err(e) = server.start_with_graceful_shutdown().await {
-        error!("Server error: {}", e);
+        error!("Server error: {:#}", e);
         std::process::exit(1);
     }
 
@@ -1,4 +1,5 @@
 use yas_mcp::internal::parser::Adjuster;
+use yas_mcp::internal::requester::{is_success_status, JsonPath};
 
 #[cfg(test)]
 mod tests {
@@ -21,8 +22,8 @@ mod tests {
         let adjuster = Adjuster::new();
 
         assert_eq!(adjuster.get_routes_count(), 0);
-        assert!(adjuster.adjustments.descriptions.is_empty());
-        assert!(adjuster.adjustments.routes.is_empty());
+        assert_eq!(adjuster.descriptions_count(), 0);
+        assert_eq!(adjuster.description_for("/users", "GET"), None);
     }
 
     #[test]
@@ -88,8 +89,12 @@ descriptions:
         let result = adjuster.load(temp_file.path().to_str().unwrap());
 
         assert!(result.is_ok());
-        assert_eq!(adjuster.adjustments.descriptions.len(), 1);
-        assert_eq!(adjuster.adjustments.descriptions[0].updates.len(), 2);
+        assert_eq!(adjuster.descriptions_count(), 2);
+        assert_eq!(
+            adjuster.description_for("/users", "POST"),
+            Some("Create a new user")
+        );
+        assert_eq!(adjuster.description_for("/users", "DELETE"), None);
     }
 
     #[test]
@@ -114,7 +119,7 @@ descriptions:
 
         assert!(result.is_ok());
         assert_eq!(adjuster.get_routes_count(), 2);
-        assert_eq!(adjuster.adjustments.descriptions.len(), 1);
+        assert_eq!(adjuster.descriptions_count(), 1);
     }
 
     #[test]
@@ -294,6 +299,121 @@ routes:
         assert!(adjuster.exists_in_mcp("/projects/{project_id}/tasks", "POST"));
     }
 
+    #[test]
+    fn test_exists_in_mcp_strict_by_default() {
+        let yaml_content = r#"
+routes:
+  - path: /Users/{id}
+    methods: [GET]
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(!adjuster.exists_in_mcp("/users/{id}", "GET"));
+        assert!(!adjuster.exists_in_mcp("/Users/{userId}", "GET"));
+    }
+
+    #[test]
+    fn test_exists_in_mcp_case_insensitive_path() {
+        let yaml_content = r#"
+path_matching:
+  case_insensitive: true
+routes:
+  - path: /Users
+    methods: [GET]
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(adjuster.exists_in_mcp("/users", "GET"));
+        assert!(adjuster.exists_in_mcp("/USERS/", "GET"));
+    }
+
+    #[test]
+    fn test_exists_in_mcp_ignore_param_names() {
+        let yaml_content = r#"
+path_matching:
+  ignore_param_names: true
+routes:
+  - path: /users/{x}/tasks
+    methods: [GET]
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(adjuster.exists_in_mcp("/users/{userId}/tasks", "GET"));
+        // Structure must still match
+        assert!(!adjuster.exists_in_mcp("/users/me/tasks", "GET"));
+        assert!(!adjuster.exists_in_mcp("/users/{userId}", "GET"));
+    }
+
+    #[test]
+    fn test_get_description_uses_path_matching() {
+        let yaml_content = r#"
+path_matching:
+  case_insensitive: true
+  ignore_param_names: true
+descriptions:
+  - path: /Users/{x}
+    updates:
+      - method: GET
+        new_description: Fetch a user
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            adjuster.get_description("/users/{id}", "GET", "original"),
+            "Fetch a user"
+        );
+    }
+
+    // ==================== is_excluded() tests ====================
+
+    #[test]
+    fn test_is_excluded_nothing_configured() {
+        let adjuster = Adjuster::new();
+
+        assert!(!adjuster.is_excluded("/users", "DELETE", "delete__users"));
+    }
+
+    #[test]
+    fn test_is_excluded_by_tool_name() {
+        let yaml_content = r#"
+disabled_tools:
+  - delete__users
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(adjuster.is_excluded("/users", "DELETE", "delete__users"));
+        assert!(!adjuster.is_excluded("/users", "GET", "get__users"));
+    }
+
+    #[test]
+    fn test_is_excluded_by_route_wins_over_selection() {
+        let yaml_content = r#"
+routes:
+  - path: /users
+    methods: [GET, DELETE]
+exclude_routes:
+  - path: /users/
+    methods: [delete]
+"#;
+        let temp_file = create_temp_yaml(yaml_content);
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(adjuster.exists_in_mcp("/users", "DELETE"));
+        assert!(adjuster.is_excluded("/users", "DELETE", "delete__users"));
+        assert!(!adjuster.is_excluded("/users", "GET", "get__users"));
+    }
+
     // ==================== get_description() tests ====================
 
     #[test]
@@ -498,4 +618,82 @@ routes:
         assert!(adjuster.exists_in_mcp("/projects", "POST"));
         assert!(adjuster.exists_in_mcp("/tasks", "DELETE"));
     }
+
+    // ==================== success_statuses tests ====================
+
+    #[test]
+    fn test_success_statuses_accept_codes_and_ranges() {
+        let temp_file = create_temp_yaml(
+            r#"
+tools:
+  - name: post__tasks
+    success_statuses: [201, "300-302"]
+"#,
+        );
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+
+        let statuses = &adjuster
+            .get_tool_override("post__tasks")
+            .unwrap()
+            .success_statuses;
+        assert!(is_success_status(statuses, 201));
+        assert!(is_success_status(statuses, 301));
+        assert!(!is_success_status(statuses, 200));
+        assert!(is_success_status(&[], 204));
+        assert!(!is_success_status(&[], 404));
+    }
+
+    #[test]
+    fn test_invalid_success_status_fails_to_load() {
+        for statuses in [r#"["299-200"]"#, "[42]", r#"["2xx"]"#] {
+            let temp_file = create_temp_yaml(&format!(
+                "tools:\n  - name: post__tasks\n    success_statuses: {}\n",
+                statuses
+            ));
+            let mut adjuster = Adjuster::new();
+
+            assert!(adjuster.load(temp_file.path().to_str().unwrap()).is_err());
+        }
+    }
+
+    // ==================== error_field tests ====================
+
+    #[test]
+    fn test_error_field_per_tool_overrides_global() {
+        let temp_file = create_temp_yaml(
+            r#"
+error_field: $.error
+tools:
+  - name: get__tasks
+    error_field: errors[0].detail
+"#,
+        );
+        let mut adjuster = Adjuster::new();
+        adjuster.load(temp_file.path().to_str().unwrap()).unwrap();
+        let body = serde_json::json!({ "error": "boom", "errors": [{ "detail": "bad id" }] });
+
+        let per_tool = adjuster.get_error_field("get__tasks").unwrap();
+        assert_eq!(per_tool.find(&body), Some(&serde_json::json!("bad id")));
+        let global = adjuster.get_error_field("post__tasks").unwrap();
+        assert_eq!(global.find(&body), Some(&serde_json::json!("boom")));
+    }
+
+    #[test]
+    fn test_error_field_paths() {
+        let body = serde_json::json!({ "data": { "error-text": "x", "ok": false, "n": 0 } });
+        let path = |p: &str| p.parse::<JsonPath>().unwrap();
+
+        assert_eq!(
+            path(r#"$.data["error-text"]"#).find(&body),
+            Some(&serde_json::json!("x"))
+        );
+        assert!(path("data.ok").find(&body).is_some());
+        assert!(path("data.ok").find_truthy(&body).is_none());
+        assert!(path("data.n").find_truthy(&body).is_none());
+        assert!(path("$.missing").find(&body).is_none());
+        for invalid in ["$", "data..ok", "data[x]", "data[0"] {
+            assert!(invalid.parse::<JsonPath>().is_err(), "{}", invalid);
+        }
+    }
 }
@@ -0,0 +1,137 @@
+use yas_mcp::internal::config::{AppConfig, EndpointConfig, OAuthConfig, ServerConfig, REDACTED};
+use yas_mcp::internal::server::listener::{activation_fd_count, load_tls_config};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_with_secrets() -> AppConfig {
+        AppConfig {
+            swagger_file: "api.yaml".to_string(),
+            endpoint: EndpointConfig {
+                base_url: "http://api.test".to_string(),
+                auth_config: HashMap::from([("token".to_string(), "s3cret".to_string())]),
+                headers: HashMap::from([
+                    ("Authorization".to_string(), "Bearer s3cret".to_string()),
+                    ("X-Api-Key".to_string(), "s3cret".to_string()),
+                    ("Accept".to_string(), "application/json".to_string()),
+                ]),
+                hmac_secret: Some("s3cret".to_string()),
+                ..Default::default()
+            },
+            oauth: Some(OAuthConfig {
+                enabled: true,
+                provider: "github".to_string(),
+                client_id: "client".to_string(),
+                client_secret: "s3cret".to_string(),
+                scopes: vec!["read".to_string()],
+                allow_origins: vec![],
+                auth_url: None,
+                token_url: None,
+                user_info_url: None,
+                redirect_uri: None,
+                extra_params: Some(HashMap::from([(
+                    "audience".to_string(),
+                    "s3cret".to_string(),
+                )])),
+                device_authorization_url: None,
+                token_cache_path: None,
+                token_cache_passphrase: Some("s3cret".to_string()),
+            }),
+            ..Default::default()
+        }
+    }
+
+    // ==================== redaction tests ====================
+
+    #[test]
+    fn test_redacted_hides_secrets() {
+        let redacted = config_with_secrets().redacted();
+
+        assert_eq!(redacted.endpoint.auth_config["token"], REDACTED);
+        assert_eq!(redacted.endpoint.headers["Authorization"], REDACTED);
+        assert_eq!(redacted.endpoint.headers["X-Api-Key"], REDACTED);
+        assert_eq!(redacted.endpoint.hmac_secret.as_deref(), Some(REDACTED));
+        let oauth = redacted.oauth.unwrap();
+        assert_eq!(oauth.client_secret, REDACTED);
+        assert_eq!(oauth.token_cache_passphrase.as_deref(), Some(REDACTED));
+        assert_eq!(oauth.extra_params.unwrap()["audience"], REDACTED);
+
+        let json = serde_json::to_string(&config_with_secrets().redacted()).unwrap();
+        assert!(!json.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_redacted_keeps_other_settings() {
+        let redacted = config_with_secrets().redacted();
+
+        assert_eq!(redacted.swagger_file, "api.yaml");
+        assert_eq!(redacted.endpoint.base_url, "http://api.test");
+        assert_eq!(redacted.endpoint.headers["Accept"], "application/json");
+        assert_eq!(redacted.oauth.unwrap().client_id, "client");
+    }
+
+    // ==================== server path tests ====================
+
+    #[test]
+    fn test_mcp_route_defaults_and_normalizes() {
+        let route = |path: &str| {
+            ServerConfig {
+                mcp_path: path.to_string(),
+                ..Default::default()
+            }
+            .mcp_route()
+        };
+
+        assert_eq!(route(""), "/mcp");
+        assert_eq!(route("/proxy/mcp"), "/proxy/mcp");
+        assert_eq!(route("rpc"), "/rpc");
+    }
+
+    // ==================== socket activation tests ====================
+
+    #[test]
+    fn test_activation_fd_count_requires_matching_pid() {
+        assert_eq!(activation_fd_count(Some("42"), Some("1"), 42), 1);
+        assert_eq!(activation_fd_count(Some("42"), Some("2"), 42), 2);
+        // Variables meant for another process (e.g. our parent) are ignored
+        assert_eq!(activation_fd_count(Some("41"), Some("1"), 42), 0);
+        assert_eq!(activation_fd_count(None, Some("1"), 42), 0);
+        assert_eq!(activation_fd_count(Some("42"), None, 42), 0);
+        assert_eq!(activation_fd_count(Some("42"), Some("x"), 42), 0);
+    }
+
+    // ==================== TLS tests ====================
+
+    const TLS_CERT: &str = "tests/fixtures/tls/cert.pem";
+    const TLS_KEY: &str = "tests/fixtures/tls/key.pem";
+
+    #[test]
+    fn test_tls_paths_require_both_files() {
+        let tls = |cert: Option<&str>, key: Option<&str>| ServerConfig {
+            tls_cert_path: cert.map(str::to_string),
+            tls_key_path: key.map(str::to_string),
+            ..Default::default()
+        };
+
+        assert!(tls(None, None).tls_paths().unwrap().is_none());
+        assert_eq!(
+            tls(Some("c.pem"), Some("k.pem")).tls_paths().unwrap(),
+            Some(("c.pem", "k.pem"))
+        );
+        let err = tls(Some("c.pem"), None).tls_paths().unwrap_err();
+        assert!(err.to_string().contains("tls_key_path is missing"));
+        assert!(tls(None, Some("k.pem")).tls_paths().is_err());
+    }
+
+    #[test]
+    fn test_load_tls_config() {
+        assert!(load_tls_config(TLS_CERT, TLS_KEY).is_ok());
+
+        let err = load_tls_config("tests/fixtures/tls/missing.pem", TLS_KEY).unwrap_err();
+        assert!(err.to_string().contains("missing.pem"));
+        // A key file holds no certificate
+        assert!(load_tls_config(TLS_KEY, TLS_KEY).is_err());
+    }
+}
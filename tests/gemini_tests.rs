@@ -0,0 +1,340 @@
+use yas_mcp::internal::gemini::profile::{
+    profile_by_name, CompatibilityProfile, ValidationOptions, GEMINI, PERMISSIVE,
+};
+use yas_mcp::internal::gemini::sanitizer::GeminiSanitizer;
+use yas_mcp::internal::gemini::transcript::{TranscriptParser, TranscriptRecorder};
+use yas_mcp::internal::gemini::validator::{
+    GeminiValidator, GeminiValidatorOptions, DEFAULT_MAX_DESCRIPTION_LENGTH,
+};
+use yas_mcp::internal::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Tool;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    fn tool(name: &str, input_schema: Value) -> Tool {
+        Tool {
+            name: name.to_string().into(),
+            title: None,
+            description: None,
+            input_schema: Arc::new(input_schema.as_object().unwrap().clone()),
+            output_schema: None,
+            annotations: None,
+            icons: None,
+            meta: None,
+        }
+    }
+
+    // ==================== report tests ====================
+
+    #[test]
+    fn test_report_serializes_failures_as_json() {
+        let tools = [
+            tool("get_tasks", json!({ "type": "object" })),
+            tool(
+                "get-task",
+                json!({ "type": "object", "properties": { "id": { "oneOf": [] } } }),
+            ),
+        ];
+        let report = GeminiValidator::validate_all(&tools);
+        assert!(!report.is_ok());
+
+        let json: Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["total_tools"], 2);
+        assert_eq!(json["invalid_tools"], 1);
+        assert_eq!(json["results"][0]["tool_name"], "get_tasks");
+        assert_eq!(json["results"][0]["is_valid"], true);
+        assert_eq!(json["results"][1]["is_valid"], false);
+        assert_eq!(json["results"][1]["errors"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_text_report_lists_errors_and_totals() {
+        let report = GeminiValidator::validate_all(&[tool("get-task", json!({}))]);
+
+        assert_eq!(
+            report.report(),
+            "ERROR get-task: Tool name contains invalid characters: 'get-task'\n\
+             gemini: 0 of 1 tools compatible, 1 incompatible\n"
+        );
+    }
+
+    // ==================== nested schema tests ====================
+
+    #[test]
+    fn test_unsupported_keyword_in_array_items_is_reported_with_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tasks": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "status": { "oneOf": [{ "type": "string" }, { "type": "integer" }] },
+                            "labels": {
+                                "type": "array",
+                                "items": { "type": "object", "additionalProperties": true }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let result = GeminiValidator::validate_tool(&tool("post_tasks", schema.clone()));
+        assert!(!result.is_valid);
+        assert_eq!(
+            result.errors,
+            vec![
+                "In property 'tasks[].labels[]': Schema contains unsupported keyword 'additionalProperties'",
+                "In property 'tasks[].status': Schema contains unsupported keyword 'oneOf'",
+            ]
+        );
+
+        // The sanitizer drops what it can; oneOf has no lossless rewrite and stays
+        let mut sanitized = schema.as_object().unwrap().clone();
+        GeminiSanitizer::sanitize_schema(&mut sanitized);
+        let result = GeminiValidator::validate_tool(&tool("post_tasks", Value::Object(sanitized)));
+        assert_eq!(
+            result.errors,
+            vec!["In property 'tasks[].status': Schema contains unsupported keyword 'oneOf'"]
+        );
+    }
+
+    // ==================== description length tests ====================
+
+    fn described(description: &str) -> Tool {
+        let mut tool = tool("get_tasks", json!({ "type": "object" }));
+        tool.description = Some(description.to_string().into());
+        tool
+    }
+
+    #[test]
+    fn test_long_description_warns_with_original_length() {
+        let tool = described(&"a".repeat(DEFAULT_MAX_DESCRIPTION_LENGTH + 1));
+
+        let result = GeminiValidator::validate_tool(&tool);
+        assert!(result.is_valid);
+        assert_eq!(
+            result.warnings,
+            vec!["Tool description exceeds 1024 characters (1025 chars)"]
+        );
+        assert!(
+            GeminiValidator::validate_tool(&described(&"a".repeat(1024)))
+                .warnings
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_long_description_is_an_error_in_strict_mode() {
+        let options = GeminiValidatorOptions {
+            max_description_length: 10,
+            strict: true,
+        };
+
+        let result =
+            GeminiValidator::validate_tool_with(&described("List all the tasks"), &options);
+        assert!(!result.is_valid);
+        assert_eq!(
+            result.errors,
+            vec!["Tool description exceeds 10 characters (18 chars)"]
+        );
+    }
+
+    #[test]
+    fn test_truncate_description_cuts_at_word_boundary() {
+        let truncate = GeminiSanitizer::truncate_description;
+
+        assert_eq!(truncate("List all the tasks", 18), "List all the tasks");
+        assert_eq!(truncate("List all the tasks", 14), "List all...");
+        // The word before the cut fits exactly
+        assert_eq!(truncate("List all the tasks", 15), "List all the...");
+        assert_eq!(truncate("Supercalifragilistic", 10), "Superca...");
+        assert_eq!(truncate("Überprüfung läuft gerade", 14), "Überprüfung...");
+    }
+
+    // ==================== profile tests ====================
+
+    #[test]
+    fn test_profile_by_name() {
+        assert_eq!(profile_by_name("gemini").unwrap().name(), "gemini");
+        assert_eq!(profile_by_name("Default").unwrap().name(), "default");
+        assert!(profile_by_name("unknown").is_none());
+    }
+
+    #[test]
+    fn test_permissive_profile_accepts_any_schema() {
+        let tool = tool(
+            "get-task.v2",
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": { "oneOf": [{ "type": "string" }, { "type": "integer" }] },
+                    "due": { "type": ["string", "null"] }
+                },
+                "additionalProperties": false
+            }),
+        );
+
+        let report = PERMISSIVE.validate_all(&[tool], &ValidationOptions::default());
+        assert!(report.is_ok());
+        assert_eq!(report.profile, "default");
+        assert!(report
+            .report()
+            .ends_with("default: 1 of 1 tools compatible, 0 incompatible\n"));
+    }
+
+    #[test]
+    fn test_gemini_profile_rejects_type_arrays_and_sanitizer_rewrites_them() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "due": { "type": ["string", "null"] },
+                "tags": { "type": "array", "items": { "type": ["integer", "null"] } }
+            }
+        });
+
+        let result = GEMINI.validate_tool(&tool("list_tasks", schema.clone()), &Default::default());
+        assert_eq!(
+            result.errors,
+            vec![
+                "In property 'due': Schema type is an array; use a single type with 'nullable: true'",
+                "In property 'tags[]': Schema type is an array; use a single type with 'nullable: true'",
+            ]
+        );
+
+        let mut sanitized = schema.as_object().unwrap().clone();
+        GEMINI.sanitize_schema(&mut sanitized);
+        assert_eq!(
+            sanitized["properties"]["due"],
+            json!({ "type": "string", "nullable": true })
+        );
+        assert_eq!(
+            sanitized["properties"]["tags"]["items"],
+            json!({ "type": "integer", "nullable": true })
+        );
+        let result = GEMINI.validate_tool(
+            &tool("list_tasks", Value::Object(sanitized)),
+            &Default::default(),
+        );
+        assert!(result.is_valid, "{:?}", result.errors);
+    }
+
+    // ==================== transcript tests ====================
+
+    fn request(id: i64, method: &str, params: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(id)),
+            method: method.to_string(),
+            params: Some(params),
+        }
+    }
+
+    fn response(id: i64, result: Value) -> JsonRpcResponse {
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(id)),
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_recorder_tags_entries_with_session_and_negotiated_version() {
+        let mut recorder = TranscriptRecorder::new(Some("s-1".to_string()));
+        recorder.record_exchange(
+            &request(1, "initialize", json!({ "protocolVersion": "2025-06-18" })),
+            &response(1, json!({ "protocolVersion": "2025-03-26" })),
+        );
+        assert_eq!(recorder.protocol_version(), Some("2025-03-26"));
+
+        let entries = recorder.record_exchange(
+            &request(2, "tools/list", json!({})),
+            &response(2, json!({})),
+        );
+        let line = serde_json::to_value(&entries[0]).unwrap();
+        assert_eq!(line["session_id"], "s-1");
+        assert_eq!(line["protocol_version"], "2025-03-26");
+        assert_eq!(line["method"], "tools/list");
+    }
+
+    #[test]
+    fn test_entries_without_session_parse_and_omit_the_fields() {
+        let entries = TranscriptParser::record_exchange(
+            &request(1, "tools/list", json!({})),
+            &response(1, json!({})),
+        );
+        let line = serde_json::to_string(&entries[0]).unwrap();
+        assert!(!line.contains("session_id"));
+
+        let parsed = TranscriptParser::parse_string(
+            r#"{"direction":">>","timestamp":null,"jsonrpc":"2.0","id":1,"method":"ping","params":null}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed[0].session_id, None);
+    }
+
+    #[test]
+    fn test_group_by_session_keeps_first_appearance_order() {
+        let transcript = [
+            r#"{"direction":">>","timestamp":null,"session_id":"b","jsonrpc":"2.0","id":1,"method":"ping","params":null}"#,
+            r#"{"direction":">>","timestamp":null,"session_id":"a","jsonrpc":"2.0","id":1,"method":"ping","params":null}"#,
+            r#"{"direction":"<<","timestamp":null,"session_id":"b","jsonrpc":"2.0","id":1,"result":{}}"#,
+            r#"{"direction":">>","timestamp":null,"jsonrpc":"2.0","id":9,"method":"ping","params":null}"#,
+        ]
+        .join("\n");
+        let entries = TranscriptParser::parse_string(&transcript).unwrap();
+
+        let sessions = TranscriptParser::group_by_session(entries);
+        let summary: Vec<_> = sessions
+            .iter()
+            .map(|s| (s.session_id.as_deref(), s.entries.len()))
+            .collect();
+        assert_eq!(summary, vec![(Some("b"), 2), (Some("a"), 1), (None, 1)]);
+    }
+
+    #[test]
+    fn test_recorded_entries_redact_credentials_and_configured_keys() {
+        let mut recorder =
+            TranscriptRecorder::new(None).with_redacted_keys(vec!["SSN".to_string()]);
+        let entries = recorder.record_exchange(
+            &request(
+                3,
+                "tools/call",
+                json!({
+                    "name": "post__users",
+                    "arguments": {
+                        "name": "Ada",
+                        "ssn": "123-45-6789",
+                        "headers": { "Authorization": "Bearer abc" },
+                        "api_key": "k-1"
+                    }
+                }),
+            ),
+            &response(3, json!({ "access_token": "t-1", "id": 7 })),
+        );
+
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect();
+        for secret in ["123-45-6789", "Bearer abc", "k-1", "t-1"] {
+            assert!(
+                !lines.iter().any(|l| l.contains(secret)),
+                "{} leaked",
+                secret
+            );
+        }
+        let request_line = serde_json::to_value(&entries[0]).unwrap();
+        assert_eq!(request_line["params"]["arguments"]["name"], "Ada");
+        assert_eq!(request_line["params"]["arguments"]["ssn"], "<redacted>");
+        let response_line = serde_json::to_value(&entries[1]).unwrap();
+        assert_eq!(response_line["result"]["id"], 7);
+    }
+}
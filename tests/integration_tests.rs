@@ -1,453 +1,385 @@
+use axum::body::Bytes;
+use axum::http::{Method, Uri};
+use axum::Json;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use reqwest::Client;
 use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::time::sleep;
+use yas_mcp::internal::config::{AppConfig, EndpointConfig, ServerConfig, ServerMode};
+use yas_mcp::internal::parser::example::generate_example;
+use yas_mcp::internal::server::{create_server, Server};
 
-/// Generate proper UUIDs for testing
-fn generate_test_uuid(seed: u32) -> String {
-    format!("550e8400-e29b-41d4-a716-44665544{:04x}", seed)
-}
+const TODO_SPEC: &str = "examples/todo-app/openapi.yaml";
 
-/// Comprehensive MCP server integration test
-/// Tests all endpoints with appropriate parameters based on the OpenAPI spec
-#[tokio::test]
-async fn test_mcp_server_comprehensive() {
-    let client = Client::new();
-    let mcp_url = "http://127.0.0.1:3000";
+/// Requests received by the mock upstream, as echoed back to the caller
+type Received = Arc<Mutex<Vec<Value>>>;
 
-    println!("🧪 Starting Comprehensive MCP Server Integration Test");
-    println!("Note: Make sure MCP server is running on {}", mcp_url);
+/// A mock upstream and a yas-mcp HTTP server in front of it, both on ephemeral ports
+struct TestHarness {
+    mcp_url: String,
+    received: Received,
+    server: Server,
+}
 
-    // Wait for server to be ready
-    if let Err(e) = wait_for_server(&client, mcp_url).await {
-        println!("❌ Server not available: {}", e);
-        println!("💡 Start the server with: cargo run -- --swagger-file examples/todo-app/openapi.yaml --mode http");
-        return;
+impl TestHarness {
+    async fn start() -> Self {
+        let (upstream_url, received) = start_upstream().await;
+        let config = AppConfig {
+            swagger_file: TODO_SPEC.to_string(),
+            endpoint: EndpointConfig {
+                base_url: upstream_url,
+                ..Default::default()
+            },
+            server: ServerConfig {
+                mode: ServerMode::Http,
+                host: "127.0.0.1".to_string(),
+                port: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let server = create_server(config)
+            .await
+            .expect("Failed to create server");
+
+        let running = server.clone();
+        tokio::spawn(async move { running.start().await });
+        let addr = wait_for_addr(&server)
+            .await
+            .expect("MCP server did not bind in time");
+
+        Self {
+            mcp_url: format!("http://{}", addr),
+            received,
+            server,
+        }
     }
 
-    // Test health endpoint
-    test_health(&client, mcp_url).await;
-
-    // Test MCP initialization
-    let session = test_initialization(&client, mcp_url).await;
+    /// Requests the upstream has received so far
+    fn received(&self) -> Vec<Value> {
+        self.received.lock().unwrap().clone()
+    }
+}
 
-    // Test tools listing
-    let tools = test_tools_listing(&client, mcp_url, &session).await;
+impl Drop for TestHarness {
+    fn drop(&mut self) {
+        self.server.shutdown();
+    }
+}
 
-    // Test all tools with appropriate parameters
-    test_all_tools_comprehensive(&client, mcp_url, &session, &tools).await;
+/// Start an upstream that answers every request with a JSON echo of it
+async fn start_upstream() -> (String, Received) {
+    let received: Received = Arc::default();
+    let log = received.clone();
+    let app = axum::Router::new().fallback(move |method: Method, uri: Uri, body: Bytes| {
+        let log = log.clone();
+        async move {
+            let request = json!({
+                "method": method.as_str(),
+                "path": uri.path(),
+                "query": uri.query(),
+                "body": serde_json::from_slice::<Value>(&body).ok(),
+            });
+            log.lock().unwrap().push(request.clone());
+            Json(request)
+        }
+    });
 
-    println!("✅ All comprehensive integration tests passed!");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind mock upstream");
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (format!("http://{}", addr), received)
 }
 
-async fn wait_for_server(client: &Client, base_url: &str) -> Result<(), String> {
-    for i in 0..10 {
-        if let Ok(response) = client.get(&format!("{}/health", base_url)).send().await {
-            if response.status().is_success() {
-                println!("✅ MCP server is ready");
-                return Ok(());
-            }
+async fn wait_for_addr(server: &Server) -> Option<std::net::SocketAddr> {
+    for _ in 0..100 {
+        if let Some(addr) = server.local_addr() {
+            return Some(addr);
         }
-        println!("Waiting for MCP server... ({}/10)", i + 1);
-        sleep(Duration::from_secs(1)).await;
+        sleep(Duration::from_millis(50)).await;
     }
-    Err("MCP server did not become ready in time".to_string())
+    None
 }
 
 async fn test_health(client: &Client, base_url: &str) {
     let response = client
-        .get(&format!("{}/health", base_url))
+        .get(format!("{}/health", base_url))
         .send()
         .await
         .expect("Health check failed");
 
     assert!(response.status().is_success());
-    println!("✅ Health check passed");
 }
 
-async fn test_initialization(client: &Client, base_url: &str) -> String {
-    let request = json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "initialize",
-        "params": {
-            "protocolVersion": "2024-11-05",
-            "capabilities": {},
-            "clientInfo": {
-                "name": "comprehensive-test",
-                "version": "1.0.0"
-            }
-        }
-    });
-
-    let response: Value = client
-        .post(&format!("{}/mcp", base_url))
+async fn mcp_request(client: &Client, base_url: &str, request: Value) -> Value {
+    client
+        .post(format!("{}/mcp", base_url))
         .json(&request)
         .send()
         .await
-        .expect("Initialization request failed")
-        .json()
-        .await
-        .expect("Failed to parse initialization response");
-
-    assert!(response["result"].is_object());
-    println!("✅ MCP initialization successful");
-
-    response["result"]["sessionId"]
-        .as_str()
-        .unwrap_or("")
-        .to_string()
-}
-
-async fn test_tools_listing(client: &Client, base_url: &str, session: &str) -> Vec<String> {
-    let request = json!({
-        "jsonrpc": "2.0",
-        "id": 2,
-        "method": "tools/list",
-        "params": {}
-    });
-
-    let mut req_builder = client.post(&format!("{}/mcp", base_url)).json(&request);
-    if !session.is_empty() {
-        req_builder = req_builder.header("x-session-id", session);
-    }
-
-    let response: Value = req_builder
-        .send()
-        .await
-        .expect("Tools listing request failed")
+        .expect("MCP request failed")
         .json()
         .await
-        .expect("Failed to parse tools listing response");
-
-    let tools = response["result"]["tools"]
-        .as_array()
-        .expect("No tools array in response");
-
-    let tool_names: Vec<String> = tools
-        .iter()
-        .filter_map(|tool| tool["name"].as_str().map(|s| s.to_string()))
-        .collect();
-
-    println!("✅ Found {} tools", tool_names.len());
-    tool_names
+        .expect("Failed to parse MCP response")
 }
 
-async fn test_all_tools_comprehensive(
-    client: &Client,
-    base_url: &str,
-    session: &str,
-    tools: &[String],
-) {
-    println!("\n🧪 Testing All Tools with Appropriate Parameters");
-    println!("================================================");
-
-    let mut test_id = 100;
-
-    for tool_name in tools {
-        let tool_params = get_tool_parameters(tool_name);
-        test_tool_with_params(client, base_url, session, tool_name, &tool_params, test_id).await;
-        test_id += 1;
-        sleep(Duration::from_millis(300)).await; // Small delay between calls
-    }
-}
-
-fn get_tool_parameters(tool_name: &str) -> Value {
-    // Generate consistent UUIDs for testing
-    let project_id = generate_test_uuid(1000);
-    let task_id = generate_test_uuid(2000);
-    let user_id = generate_test_uuid(3000);
-    let comment_id = generate_test_uuid(4000);
-    let attachment_id = generate_test_uuid(5000);
-    let parent_comment_id = generate_test_uuid(6000);
-
-    match tool_name {
-        // Authentication endpoints
-        "post_auth_login" => json!({
-            "email": "test@example.com",
-            "password": "testpassword123"
-        }),
-        "post_auth_register" => json!({
-            "email": "newuser@example.com",
-            "password": "newpassword123",
-            "name": "Test User"
-        }),
-
-        // User management
-        "get_users_me" => json!({}),
-        "put_users_me" => json!({
-            "name": "Updated Test User",
-            "avatar": "https://example.com/avatar.jpg"
-        }),
-
-        // Project management
-        "get_projects" => json!({
-            "page": 1,
-            "per_page": 10,
-            "archived": false,
-            "search": "test"
-        }),
-        "post_projects" => json!({
-            "title": "Test Project",
-            "description": "Test project description",
-            "color": "#3B82F6"
-        }),
-        "get_projects___project_id__" => json!({
-            "project_id": project_id
-        }),
-        "put_projects___project_id__" => json!({
-            "project_id": project_id,
-            "title": "Updated Project",
-            "description": "Updated description",
-            "color": "#EF4444",
-            "is_archived": false
-        }),
-        "delete_projects___project_id__" => json!({
-            "project_id": project_id
+async fn test_initialization(client: &Client, base_url: &str) {
+    let response = mcp_request(
+        client,
+        base_url,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "comprehensive-test",
+                    "version": "1.0.0"
+                }
+            }
         }),
+    )
+    .await;
 
-        // Task management
-        "get_projects___project_id___tasks" => json!({
-            "project_id": project_id,
-            "page": 1,
-            "per_page": 20,
-            "status": "pending",
-            "priority": "medium",
-            "assignee": user_id,
-            "due_before": "2024-12-31",
-            "due_after": "2024-01-01",
-            "search": "important"
-        }),
-        "post_projects___project_id___tasks" => json!({
-            "project_id": project_id,
-            "title": "New Task",
-            "description": "Task description",
-            "status": "pending",
-            "priority": "high",
-            "due_date": "2024-12-31",
-            "estimated_hours": 5.0,
-            "assignee_id": user_id,
-            "tags": ["urgent", "backend"]
-        }),
-        "get_tasks___task_id__" => json!({
-            "task_id": task_id
-        }),
-        "put_tasks___task_id__" => json!({
-            "task_id": task_id,
-            "title": "Updated Task",
-            "description": "Updated description",
-            "status": "in_progress",
-            "priority": "critical",
-            "due_date": "2024-12-25",
-            "estimated_hours": 8.0,
-            "actual_hours": 2.0,
-            "assignee_id": user_id,
-            "tags": ["updated", "critical"]
-        }),
-        "delete_tasks___task_id__" => json!({
-            "task_id": task_id
-        }),
+    assert!(response["result"].is_object(), "{}", response);
+    assert!(response["result"]["capabilities"]["tools"].is_object());
+}
 
-        // Comments
-        "get_tasks___task_id___comments" => json!({
-            "task_id": task_id,
-            "page": 1,
-            "per_page": 50
-        }),
-        "post_tasks___task_id___comments" => json!({
-            "task_id": task_id,
-            "content": "This is a test comment for the task",
-            "parent_id": parent_comment_id
-        }),
-        "put_comments___comment_id__" => json!({
-            "comment_id": comment_id,
-            "content": "Updated comment content"
-        }),
-        "delete_comments___comment_id__" => json!({
-            "comment_id": comment_id
+async fn test_tools_listing(client: &Client, base_url: &str) -> Vec<Value> {
+    let response = mcp_request(
+        client,
+        base_url,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/list",
+            "params": {}
         }),
+    )
+    .await;
 
-        // Attachments
-        "post_tasks___task_id___attachments" => json!({
-            "task_id": task_id,
-            "description": "Test attachment description"
-        }),
-        "get_attachments___attachment_id__" => json!({
-            "attachment_id": attachment_id
-        }),
-        "delete_attachments___attachment_id__" => json!({
-            "attachment_id": attachment_id
-        }),
+    response["result"]["tools"]
+        .as_array()
+        .expect("No tools array in response")
+        .clone()
+}
 
-        // Analytics & Reports
-        "get_analytics_projects_stats" => json!({
-            "timeframe": "month"
-        }),
-        "post_reports_tasks_export" => json!({
-            "format": "pdf",
-            "project_ids": [project_id, generate_test_uuid(1001)],
-            "date_from": "2024-01-01",
-            "date_to": "2024-12-31",
-            "include_comments": true
+/// Call a tool and return its successful result; the echoed upstream request is the text
+async fn call_tool(client: &Client, base_url: &str, name: &str, arguments: Value) -> Value {
+    let response = mcp_request(
+        client,
+        base_url,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments }
         }),
-
-        // Root and health endpoints
-        "get_" => json!({}),
-        "get_health" => json!({}),
-
-        // Default fallback
-        _ => json!({}),
-    }
+    )
+    .await;
+
+    let result = &response["result"];
+    assert!(result.is_object(), "{} failed: {}", name, response);
+    assert_eq!(
+        result["isError"],
+        json!(false),
+        "{} failed: {}",
+        name,
+        result
+    );
+    let text = result["content"][0]["text"]
+        .as_str()
+        .expect("Tool result has text content");
+    serde_json::from_str(text).expect("Upstream echo is JSON")
 }
 
-async fn test_tool_with_params(
-    client: &Client,
-    base_url: &str,
-    session: &str,
-    tool_name: &str,
-    params: &Value,
-    test_id: i32,
-) {
-    println!("\n🔧 Testing: {}", tool_name);
-
-    let request = json!({
-        "jsonrpc": "2.0",
-        "id": test_id,
-        "method": "tools/call",
-        "params": {
-            "name": tool_name,
-            "arguments": params
-        }
-    });
-
-    let mut req_builder = client.post(&format!("{}/mcp", base_url)).json(&request);
-    if !session.is_empty() {
-        req_builder = req_builder.header("x-session-id", session);
-    }
+/// Every tool of the todo spec, called over HTTP with arguments generated from its schema,
+/// reaches the upstream with its path parameters filled
+#[tokio::test]
+async fn test_mcp_server_comprehensive() {
+    let harness = TestHarness::start().await;
+    let client = Client::new();
 
-    let response: Value = match req_builder.send().await {
-        Ok(resp) => resp.json().await.unwrap_or_default(),
-        Err(e) => {
-            println!("❌ Network error for {}: {}", tool_name, e);
-            return;
-        }
-    };
-
-    // Analyze response
-    if response["error"].is_object() {
-        let error_code = response["error"]["code"].as_i64().unwrap_or(0);
-        let error_msg = response["error"]["message"]
-            .as_str()
-            .unwrap_or("Unknown error");
-
-        match error_code {
-            -32601 => println!("   ⚠️  Tool not found (might be expected)"),
-            -32602 => println!("   ⚠️  Invalid parameters (needs specific values)"),
-            _ => println!(
-                "   ❌ Tool call failed: {} (code: {})",
-                error_msg, error_code
-            ),
-        }
-    } else if response["result"].is_object() {
-        println!("   ✅ Tool call successful");
-
-        // Show some result details for specific tools
-        if let Some(data) = response["result"]["content"][0]["raw"]["text"].as_str() {
-            match tool_name {
-                "get_projects"
-                | "get_projects___project_id___tasks"
-                | "get_tasks___task_id___comments" => {
-                    if let Ok(parsed) = serde_json::from_str::<Value>(data) {
-                        if let Some(items) = parsed["data"].as_array() {
-                            println!("      📊 Returned {} items", items.len());
-                        }
-                    }
-                }
-                "get_analytics_projects_stats" => {
-                    println!("      📈 Analytics data received");
-                }
-                _ => {
-                    // For other tools, just show that we got data
-                    if !data.is_empty() && data != "{}" {
-                        println!("      📄 Response data received");
-                    }
-                }
-            }
-        }
-    } else {
-        println!("   ⚠️  Tool call completed (empty or unexpected response)");
+    test_health(&client, &harness.mcp_url).await;
+    test_initialization(&client, &harness.mcp_url).await;
+    let tools = test_tools_listing(&client, &harness.mcp_url).await;
+    assert_eq!(tools.len(), harness.server.tool_count());
+    assert!(!tools.is_empty());
+
+    for tool in &tools {
+        let name = tool["name"].as_str().unwrap();
+        let arguments = generate_example(&tool["inputSchema"]);
+        let echo = call_tool(&client, &harness.mcp_url, name, arguments).await;
+        let path = echo["path"].as_str().unwrap();
+        assert!(
+            !path.contains('{') && !path.contains("%7B"),
+            "{} sent unresolved path {}",
+            name,
+            path
+        );
     }
+    assert_eq!(harness.received().len(), tools.len());
 }
 
-/// Quick smoke test for basic functionality
+/// Path and query arguments end up in the upstream URL
 #[tokio::test]
 async fn test_mcp_server_smoke() {
+    let harness = TestHarness::start().await;
     let client = Client::new();
-    let mcp_url = "http://127.0.0.1:3000";
-
-    println!("🚀 MCP Server Smoke Test");
-
-    if wait_for_server(&client, mcp_url).await.is_err() {
-        println!("❌ Server not available - skipping smoke test");
-        return;
-    }
-
-    // Test critical endpoints only
-    test_health(&client, mcp_url).await;
-    let session = test_initialization(&client, mcp_url).await;
-    let tools = test_tools_listing(&client, mcp_url, &session).await;
-
-    // Test a few critical tools
-    let critical_tools = vec![
-        "get_users_me",
-        "get_projects",
-        "get_projects___project_id___tasks",
-        "post_projects",
-        "get_health",
-        "get_",
-    ];
 
-    for tool in critical_tools {
-        if tools.contains(&tool.to_string()) {
-            let params = get_tool_parameters(tool);
-            test_tool_with_params(&client, mcp_url, &session, tool, &params, 999).await;
-        }
-    }
-
-    println!("✅ Smoke test completed!");
+    let echo = call_tool(
+        &client,
+        &harness.mcp_url,
+        "get__projects___project_id___tasks",
+        json!({ "project_id": "p-1", "status": "pending" }),
+    )
+    .await;
+    assert_eq!(echo["method"], "GET");
+    assert_eq!(echo["path"], "/projects/p-1/tasks");
+    assert_eq!(echo["query"], "status=pending");
+    assert_eq!(harness.received(), vec![echo]);
 }
 
-/// Test specific tools that should work with the current configuration
+/// Arguments left over after path and query parameters are sent upstream as a JSON body
 #[tokio::test]
 async fn test_working_endpoints() {
+    let harness = TestHarness::start().await;
     let client = Client::new();
-    let mcp_url = "http://127.0.0.1:3000";
 
-    println!("🎯 Testing Known Working Endpoints");
+    let echo = call_tool(
+        &client,
+        &harness.mcp_url,
+        "post__projects",
+        json!({ "title": "Test Project", "color": "#3B82F6" }),
+    )
+    .await;
+    assert_eq!(echo["method"], "POST");
+    assert_eq!(echo["path"], "/projects");
+    assert_eq!(echo["body"]["title"], "Test Project");
+    assert_eq!(echo["body"]["color"], "#3B82F6");
+}
 
-    if wait_for_server(&client, mcp_url).await.is_err() {
-        println!("❌ Server not available - skipping working endpoints test");
-        return;
+/// The built binary in stdio mode answers JSON-RPC lines and calls the upstream
+#[tokio::test]
+async fn test_stdio_binary_calls_upstream() {
+    let (upstream_url, received) = start_upstream().await;
+    let spec = std::fs::canonicalize(TODO_SPEC).unwrap();
+    // Run outside the repo so ./config.yaml is not picked up
+    let dir = tempfile::tempdir().unwrap();
+    let mut child = tokio::process::Command::new(env!("CARGO_BIN_EXE_yas-mcp"))
+        .args(["--mode", "stdio", "--endpoint", &upstream_url])
+        .arg("--swagger-file")
+        .arg(&spec)
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .expect("Failed to start yas-mcp");
+
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+    let requests = [
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "stdio-test", "version": "1.0.0" }
+            }
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "get__tasks___task_id__", "arguments": { "task_id": "t-7" } }
+        }),
+    ];
+    for request in &requests {
+        stdin
+            .write_all(format!("{}\n", request).as_bytes())
+            .await
+            .unwrap();
     }
+    stdin.flush().await.unwrap();
+
+    let mut responses = Vec::new();
+    while responses.len() < requests.len() {
+        let line = tokio::time::timeout(Duration::from_secs(10), stdout.next_line())
+            .await
+            .expect("yas-mcp answered in time")
+            .unwrap()
+            .expect("yas-mcp closed stdout early");
+        responses.push(serde_json::from_str::<Value>(&line).unwrap());
+    }
+    drop(stdin);
+
+    assert!(responses[0]["result"].is_object(), "{}", responses[0]);
+    assert_eq!(responses[1]["result"]["isError"], json!(false));
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0]["path"], "/tasks/t-7");
+    let _ = child.wait().await;
+}
 
-    let session = test_initialization(&client, mcp_url).await;
-
-    // These endpoints should work with our current config
-    let working_tools = vec![
-        "get_health",
-        "get_",
-        "get_users_me",
-        "get_projects",
-        "post_projects",
-        "get_analytics_projects_stats",
-        "post_auth_login",
-        "post_auth_register",
-    ];
+/// gzip request bodies are inflated and a large `tools/list` answer is gzipped
+#[tokio::test]
+async fn test_gzip_request_and_response_bodies() {
+    let harness = TestHarness::start().await;
+    let request = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list", "params": {} });
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(request.to_string().as_bytes()).unwrap();
+
+    let response = Client::new()
+        .post(format!("{}/mcp", harness.mcp_url))
+        .header("content-type", "application/json")
+        .header("content-encoding", "gzip")
+        .header("accept-encoding", "gzip")
+        .body(encoder.finish().unwrap())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    let mut body = String::new();
+    GzDecoder::new(&response.bytes().await.unwrap()[..])
+        .read_to_string(&mut body)
+        .unwrap();
+    let body: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(
+        body["result"]["tools"].as_array().unwrap().len(),
+        harness.server.tool_count()
+    );
+}
 
-    for tool in working_tools {
-        let params = get_tool_parameters(tool);
-        test_tool_with_params(&client, mcp_url, &session, tool, &params, 500).await;
-    }
+/// Request bodies in encodings other than gzip are refused with 415
+#[tokio::test]
+async fn test_unsupported_request_encoding_is_refused() {
+    let harness = TestHarness::start().await;
+    let response = Client::new()
+        .post(format!("{}/mcp", harness.mcp_url))
+        .header("content-type", "application/json")
+        .header("content-encoding", "br")
+        .body(vec![0x1b, 0x00])
+        .send()
+        .await
+        .unwrap();
 
-    println!("✅ Working endpoints test completed!");
+    assert_eq!(response.status(), 415);
+    assert!(harness.received().is_empty());
 }
@@ -0,0 +1,498 @@
+use yas_mcp::internal::auth::create_client;
+use yas_mcp::internal::auth::oauth2::{OAuth2Client, OAuth2ProviderConfig, OAuth2Token};
+use yas_mcp::internal::auth::token_manager::OAuth2TokenManager;
+use yas_mcp::internal::auth::token_store::{CachedToken, TokenStore};
+use yas_mcp::internal::config::OAuthConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::routing::post;
+    use axum::{Form, Json, Router};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Token endpoint answers, in order; the last one repeats
+    type Answers = Arc<(Vec<(u16, Value)>, AtomicUsize)>;
+
+    /// Start a provider with a device endpoint and a token endpoint giving `answers`
+    async fn start_provider(answers: Vec<(u16, Value)>) -> String {
+        async fn device(Form(form): Form<HashMap<String, String>>) -> Json<Value> {
+            assert_eq!(form["client_id"], "client");
+            assert_eq!(form["scope"], "read write");
+            Json(json!({
+                "device_code": "dev-123",
+                "user_code": "WDJB-MJHT",
+                "verification_uri": "https://example.com/device",
+                "expires_in": 900,
+                "interval": 5,
+            }))
+        }
+        async fn token(
+            State(answers): State<Answers>,
+            Form(form): Form<HashMap<String, String>>,
+        ) -> (axum::http::StatusCode, Json<Value>) {
+            assert_eq!(
+                form["grant_type"],
+                "urn:ietf:params:oauth:grant-type:device_code"
+            );
+            assert_eq!(form["device_code"], "dev-123");
+            let (list, calls) = &*answers;
+            let call = calls.fetch_add(1, Ordering::SeqCst).min(list.len() - 1);
+            let (status, body) = list[call].clone();
+            (
+                axum::http::StatusCode::from_u16(status).unwrap(),
+                Json(body),
+            )
+        }
+
+        let answers: Answers = Arc::new((answers, AtomicUsize::new(0)));
+        let app = Router::new()
+            .route("/device", post(device))
+            .route("/token", post(token))
+            .with_state(answers);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    fn client(base_url: &str) -> OAuth2Client {
+        OAuth2Client::new(OAuth2ProviderConfig {
+            provider: "test".to_string(),
+            client_id: "client".to_string(),
+            client_secret: String::new(),
+            auth_url: format!("{}/authorize", base_url),
+            token_url: format!("{}/token", base_url),
+            user_info_url: None,
+            device_authorization_url: Some(format!("{}/device", base_url)),
+            scopes: vec!["read".to_string(), "write".to_string()],
+            redirect_uri: None,
+            extra_params: None,
+        })
+        .unwrap()
+        .with_device_poll_interval(Duration::from_millis(10))
+    }
+
+    // ==================== device-code flow tests ====================
+
+    #[tokio::test]
+    async fn test_device_authorization_returns_user_code() {
+        let base_url = start_provider(vec![]).await;
+
+        let authorization = client(&base_url).device_authorization().await.unwrap();
+        assert_eq!(authorization.device_code, "dev-123");
+        assert_eq!(authorization.user_code, "WDJB-MJHT");
+        assert_eq!(authorization.verification_uri, "https://example.com/device");
+        assert_eq!(authorization.poll_interval(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_poll_device_token_waits_while_pending() {
+        let base_url = start_provider(vec![
+            (400, json!({ "error": "authorization_pending" })),
+            // GitHub reports pending with status 200
+            (200, json!({ "error": "authorization_pending" })),
+            (
+                200,
+                json!({ "access_token": "tok", "token_type": "bearer", "expires_in": 3600 }),
+            ),
+        ])
+        .await;
+
+        let token = client(&base_url)
+            .poll_device_token("dev-123")
+            .await
+            .unwrap();
+        assert_eq!(token.access_token, "tok");
+        assert_eq!(token.expires_in, Some(3600));
+    }
+
+    #[tokio::test]
+    async fn test_poll_device_token_fails_when_denied_or_expired() {
+        for code in ["access_denied", "expired_token"] {
+            let base_url = start_provider(vec![
+                (400, json!({ "error": "authorization_pending" })),
+                (400, json!({ "error": code })),
+            ])
+            .await;
+
+            let error = client(&base_url)
+                .poll_device_token("dev-123")
+                .await
+                .unwrap_err();
+            assert!(error.to_string().contains(code), "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_device_authorization_requires_an_endpoint() {
+        let client = OAuth2Client::new(OAuth2ProviderConfig {
+            provider: "test".to_string(),
+            client_id: "client".to_string(),
+            client_secret: String::new(),
+            auth_url: String::new(),
+            token_url: String::new(),
+            user_info_url: None,
+            device_authorization_url: None,
+            scopes: vec![],
+            redirect_uri: None,
+            extra_params: None,
+        })
+        .unwrap();
+
+        assert!(client.device_authorization().await.is_err());
+    }
+
+    // ==================== token response format tests ====================
+
+    /// Start a provider whose token endpoint answers with `content_type` and `body`,
+    /// or 406 when the request does not accept JSON
+    async fn start_token_provider(content_type: &'static str, body: &'static str) -> String {
+        let app = Router::new().route(
+            "/token",
+            post(move |headers: axum::http::HeaderMap| async move {
+                if headers.get("accept").and_then(|v| v.to_str().ok()) != Some("application/json") {
+                    return (
+                        axum::http::StatusCode::NOT_ACCEPTABLE,
+                        [("content-type", "")],
+                        "",
+                    );
+                }
+                (
+                    axum::http::StatusCode::OK,
+                    [("content-type", content_type)],
+                    body,
+                )
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_form_encoded_token_response_is_parsed() {
+        let base_url = start_token_provider(
+            "application/x-www-form-urlencoded; charset=utf-8",
+            "access_token=gho_abc&scope=repo%2Cuser&token_type=bearer&expires_in=28800",
+        )
+        .await;
+
+        let token = client(&base_url).exchange_code("code").await.unwrap();
+        assert_eq!(token.access_token, "gho_abc");
+        assert_eq!(token.scope.as_deref(), Some("repo,user"));
+        assert_eq!(token.expires_in, Some(28800));
+    }
+
+    #[tokio::test]
+    async fn test_json_token_response_is_parsed() {
+        let base_url = start_token_provider(
+            "application/json",
+            r#"{"access_token":"tok","token_type":"Bearer","refresh_token":"r2"}"#,
+        )
+        .await;
+
+        let token = client(&base_url).refresh_token("r1").await.unwrap();
+        assert_eq!(token.access_token, "tok");
+        assert_eq!(token.refresh_token.as_deref(), Some("r2"));
+    }
+
+    #[tokio::test]
+    async fn test_error_in_successful_token_response_is_an_error() {
+        let base_url = start_token_provider(
+            "application/x-www-form-urlencoded",
+            "error=bad_verification_code&error_description=The+code+is+incorrect",
+        )
+        .await;
+
+        let error = client(&base_url).exchange_code("code").await.unwrap_err();
+        assert!(
+            error.to_string().contains("bad_verification_code"),
+            "{}",
+            error
+        );
+    }
+
+    // ==================== token cache tests ====================
+
+    fn token(
+        access_token: &str,
+        expires_in: Option<u64>,
+        refresh_token: Option<&str>,
+    ) -> OAuth2Token {
+        OAuth2Token {
+            access_token: access_token.to_string(),
+            token_type: "bearer".to_string(),
+            expires_in,
+            refresh_token: refresh_token.map(str::to_string),
+            scope: None,
+            id_token: None,
+        }
+    }
+
+    /// Start a provider whose token endpoint answers refresh grants with `fresh`
+    async fn start_refresh_provider() -> String {
+        async fn token(Form(form): Form<HashMap<String, String>>) -> Json<Value> {
+            assert_eq!(form["grant_type"], "refresh_token");
+            assert_eq!(form["refresh_token"], "refresh-1");
+            Json(json!({ "access_token": "fresh", "token_type": "bearer", "expires_in": 3600 }))
+        }
+        let app = Router::new().route("/token", post(token));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_token_store_round_trip_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens").join("github.bin");
+        let store = TokenStore::new(&path, "correct horse");
+        assert!(store.load().unwrap().is_none());
+
+        store
+            .save(&CachedToken::new(token("secret-access", Some(3600), None)))
+            .unwrap();
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret-access"));
+
+        let cached = store.load().unwrap().unwrap();
+        assert_eq!(cached.token.access_token, "secret-access");
+        assert!(!cached.is_expired());
+        assert!(TokenStore::new(&path, "wrong").load().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_token_is_refreshed_when_expired() {
+        let base_url = start_refresh_provider().await;
+        let dir = tempfile::tempdir().unwrap();
+        let store = TokenStore::new(dir.path().join("token"), "pass");
+        store
+            .save(&CachedToken::new(token(
+                "stale",
+                Some(0),
+                Some("refresh-1"),
+            )))
+            .unwrap();
+
+        let client = client(&base_url).with_token_store(store.clone());
+        let token = client.cached_token().await.unwrap().unwrap();
+        assert_eq!(token.access_token, "fresh");
+
+        // The refreshed token is written back and keeps the refresh token
+        let cached = store.load().unwrap().unwrap();
+        assert_eq!(cached.token.access_token, "fresh");
+        assert_eq!(cached.token.refresh_token.as_deref(), Some("refresh-1"));
+        assert_eq!(
+            client.cached_token().await.unwrap().unwrap().access_token,
+            "fresh"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_without_refresh_token_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TokenStore::new(dir.path().join("token"), "pass");
+        store
+            .save(&CachedToken::new(token("stale", Some(0), None)))
+            .unwrap();
+
+        let client = client("http://127.0.0.1:9").with_token_store(store);
+        assert!(client.cached_token().await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_token_cache_requires_passphrase() {
+        let mut config = OAuthConfig {
+            enabled: true,
+            provider: "github".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            scopes: vec![],
+            allow_origins: vec![],
+            auth_url: None,
+            token_url: None,
+            user_info_url: None,
+            redirect_uri: None,
+            extra_params: None,
+            device_authorization_url: None,
+            token_cache_path: Some("/tmp/yas-mcp-token".to_string()),
+            token_cache_passphrase: None,
+        };
+        assert!(create_client(&config).is_err());
+
+        config.token_cache_passphrase = Some("pass".to_string());
+        assert!(create_client(&config).is_ok());
+    }
+
+    // ==================== token manager tests ====================
+
+    /// Start a provider that hands out `fresh-1`, `fresh-2`, ... for refresh grants,
+    /// and an upstream at `/echo` that answers with the Authorization header it got
+    /// (or 401 for the token `revoked`)
+    async fn start_refreshing_provider() -> (String, Arc<AtomicUsize>) {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let counter = refreshes.clone();
+        let app = Router::new()
+            .route(
+                "/token",
+                post(move |Form(form): Form<HashMap<String, String>>| {
+                    let counter = counter.clone();
+                    async move {
+                        assert_eq!(form["grant_type"], "refresh_token");
+                        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                        Json(json!({
+                            "access_token": format!("fresh-{}", n),
+                            "token_type": "bearer",
+                            "expires_in": 3600,
+                        }))
+                    }
+                }),
+            )
+            .route(
+                "/echo",
+                axum::routing::get(|headers: axum::http::HeaderMap| async move {
+                    let authorization = headers["authorization"].to_str().unwrap().to_string();
+                    if authorization == "Bearer revoked" {
+                        return (axum::http::StatusCode::UNAUTHORIZED, authorization);
+                    }
+                    (axum::http::StatusCode::OK, authorization)
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{}", addr), refreshes)
+    }
+
+    #[tokio::test]
+    async fn test_token_manager_refreshes_within_skew() {
+        let (base_url, refreshes) = start_refreshing_provider().await;
+        let manager =
+            OAuth2TokenManager::new(client(&base_url), token("old", Some(30), Some("refresh-1")))
+                .with_refresh_skew(Duration::from_secs(60));
+
+        assert_eq!(manager.access_token().await.unwrap(), "fresh-1");
+        // The refreshed token is good for an hour, well outside the skew
+        assert_eq!(manager.access_token().await.unwrap(), "fresh-1");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            manager.token().await.refresh_token.as_deref(),
+            Some("refresh-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_manager_keeps_valid_token() {
+        let (base_url, refreshes) = start_refreshing_provider().await;
+        let manager =
+            OAuth2TokenManager::new(client(&base_url), token("current", Some(3600), Some("r")));
+
+        assert_eq!(manager.access_token().await.unwrap(), "current");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 0);
+    }
+
+    fn managed_auth_config(base_url: &str, extra: &[(&str, &str)]) -> HashMap<String, String> {
+        let mut auth_config = HashMap::from([
+            ("token_url".to_string(), format!("{}/token", base_url)),
+            ("refresh_token".to_string(), "refresh-1".to_string()),
+        ]);
+        for (key, value) in extra {
+            auth_config.insert(key.to_string(), value.to_string());
+        }
+        auth_config
+    }
+
+    #[tokio::test]
+    async fn test_configured_token_expiry_is_honoured() {
+        let (base_url, refreshes) = start_refreshing_provider().await;
+        let manager = |extra: &[(&str, &str)]| {
+            OAuth2TokenManager::from_auth_config(&managed_auth_config(&base_url, extra), None)
+                .unwrap()
+                .unwrap()
+        };
+
+        let valid = manager(&[
+            ("token", "configured"),
+            ("token_expires_at", "2999-01-01T00:00:00Z"),
+        ]);
+        assert_eq!(valid.access_token().await.unwrap(), "configured");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 0);
+
+        let expired = manager(&[
+            ("token", "configured"),
+            ("token_expires_at", "2000-01-01T00:00:00Z"),
+        ]);
+        assert_eq!(expired.access_token().await.unwrap(), "fresh-1");
+
+        // An empty token is as good as none
+        let empty = manager(&[("token", "")]);
+        assert_eq!(empty.access_token().await.unwrap(), "fresh-2");
+
+        let invalid = OAuth2TokenManager::from_auth_config(
+            &managed_auth_config(&base_url, &[("token", "t"), ("token_expires_at", "soon")]),
+            None,
+        )
+        .unwrap();
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requester_sends_managed_token() {
+        use yas_mcp::internal::config::{AuthType, EndpointConfig};
+        use yas_mcp::internal::requester::{HttpRequester, RouteConfig};
+
+        let (base_url, refreshes) = start_refreshing_provider().await;
+        let endpoint = EndpointConfig {
+            base_url: base_url.clone(),
+            auth_type: AuthType::OAuth2,
+            auth_config: managed_auth_config(&base_url, &[]),
+            ..Default::default()
+        };
+        let route = RouteConfig::new("/echo".to_string(), "GET".to_string(), String::new());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .unwrap();
+
+        for _ in 0..2 {
+            let response = executor("{}").await.unwrap();
+            assert_eq!(response.body, b"Bearer fresh-1");
+        }
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requester_refreshes_and_retries_on_401() {
+        use yas_mcp::internal::config::{AuthType, EndpointConfig};
+        use yas_mcp::internal::requester::{HttpRequester, RouteConfig};
+
+        let (base_url, refreshes) = start_refreshing_provider().await;
+        // Configured as valid for hours, but the upstream has revoked it
+        let endpoint = EndpointConfig {
+            base_url: base_url.clone(),
+            auth_type: AuthType::OAuth2,
+            auth_config: managed_auth_config(&base_url, &[("token", "revoked")]),
+            ..Default::default()
+        };
+        let route = RouteConfig::new("/echo".to_string(), "GET".to_string(), String::new());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .unwrap();
+
+        let response = executor("{}").await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, b"Bearer fresh-1");
+        let response = executor("{}").await.unwrap();
+        assert_eq!(response.body, b"Bearer fresh-1");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,1942 @@
+use yas_mcp::internal::config::{AppConfig, ParserConfig, SwaggerSource};
+use yas_mcp::internal::gemini::validator::GeminiValidator;
+use yas_mcp::internal::parser::cache::ToolCache;
+use yas_mcp::internal::parser::diff::diff_tools;
+use yas_mcp::internal::parser::example::generate_example;
+use yas_mcp::internal::parser::sanitize::sanitize_description;
+use yas_mcp::internal::parser::{
+    merge_spec_tools, Adjuster, DiagnosticSeverity, ParseDiagnostic, Parser, RouteTool, SpecInfo,
+    SpecTools, SwaggerParser,
+};
+use yas_mcp::internal::requester::{ApiKeyLocation, AuthScheme, RouteAuth};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Helper to create a temp file with YAML content
+    fn create_temp_yaml(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().expect("Failed to create temp file");
+        file.write_all(content.as_bytes())
+            .expect("Failed to write temp file");
+        file
+    }
+
+    /// Helper to parse a spec with the given parser config
+    fn parse_spec(spec: &str, config: ParserConfig) -> Vec<RouteTool> {
+        let file = create_temp_yaml(spec);
+        let mut parser = SwaggerParser::with_config(Adjuster::new(), config);
+        parser
+            .init(file.path().to_str().unwrap(), None)
+            .expect("Failed to parse spec");
+        parser.get_route_tools().to_vec()
+    }
+
+    /// Helper to parse a spec with an adjustments file and the default config
+    fn parse_spec_with_adjustments(spec: &str, adjustments: &str) -> Vec<RouteTool> {
+        let spec_file = create_temp_yaml(spec);
+        let adjustments_file = create_temp_yaml(adjustments);
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser
+            .init(
+                spec_file.path().to_str().unwrap(),
+                adjustments_file.path().to_str(),
+            )
+            .expect("Failed to parse spec");
+        parser.get_route_tools().to_vec()
+    }
+
+    fn find_tool<'a>(tools: &'a [RouteTool], name: &str) -> &'a RouteTool {
+        tools
+            .iter()
+            .find(|t| t.tool.name == name)
+            .unwrap_or_else(|| panic!("tool {} not generated", name))
+    }
+
+    fn description(route_tool: &RouteTool) -> String {
+        route_tool.tool.description.as_deref().unwrap().to_string()
+    }
+
+    const EXTENSIONS_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /legacy:
+    get:
+      summary: List legacy items
+      deprecated: true
+      x-mcp-hint: Prefer list_items instead
+      x-rate-limit: 10
+      responses:
+        "200":
+          description: OK
+"#;
+
+    // ==================== description extension tests ====================
+
+    #[test]
+    fn test_default_config_appends_mcp_hint_and_deprecation() {
+        let tools = parse_spec(EXTENSIONS_SPEC, ParserConfig::default());
+
+        let description = description(&tools[0]);
+        assert!(description.contains("[DEPRECATED] List legacy items"));
+        assert!(description.ends_with("Prefer list_items instead"));
+        assert!(!description.contains("10"));
+    }
+
+    #[test]
+    fn test_configured_extensions_are_appended_in_order() {
+        let config = ParserConfig {
+            description_extensions: vec!["x-rate-limit".to_string(), "x-missing".to_string()],
+            ..Default::default()
+        };
+        let tools = parse_spec(EXTENSIONS_SPEC, config);
+
+        let description = description(&tools[0]);
+        assert!(description.ends_with("List legacy items 10"));
+        assert!(!description.contains("Prefer list_items"));
+    }
+
+    const CRUD_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /items:
+    get:
+      summary: List items
+      responses:
+        "200":
+          description: OK
+    post:
+      summary: Create item
+      responses:
+        "201":
+          description: Created
+  /items/{id}:
+    put:
+      summary: Replace item
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        "200":
+          description: OK
+    delete:
+      summary: Delete item
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        "204":
+          description: Deleted
+"#;
+
+    // ==================== annotation tests ====================
+
+    #[test]
+    fn test_annotations_inferred_from_method() {
+        let tools = parse_spec(CRUD_SPEC, ParserConfig::default());
+
+        let get = find_tool(&tools, "get__items")
+            .tool
+            .annotations
+            .clone()
+            .unwrap();
+        assert_eq!(get.read_only_hint, Some(true));
+        assert_eq!(get.idempotent_hint, Some(true));
+
+        let post = find_tool(&tools, "post__items")
+            .tool
+            .annotations
+            .clone()
+            .unwrap();
+        assert_eq!(post.read_only_hint, Some(false));
+        assert_eq!(post.idempotent_hint, Some(false));
+
+        let put = find_tool(&tools, "put__items___id__")
+            .tool
+            .annotations
+            .clone()
+            .unwrap();
+        assert_eq!(put.idempotent_hint, Some(true));
+        assert_eq!(put.destructive_hint, Some(false));
+
+        let delete = find_tool(&tools, "delete__items___id__")
+            .tool
+            .annotations
+            .clone()
+            .unwrap();
+        assert_eq!(delete.read_only_hint, Some(false));
+        assert_eq!(delete.destructive_hint, Some(true));
+    }
+
+    #[test]
+    fn test_annotations_overridden_by_adjustments() {
+        let adjustments = r#"
+tools:
+  - name: post__items
+    annotations:
+      idempotent: true
+      open_world: false
+"#;
+        let tools = parse_spec_with_adjustments(CRUD_SPEC, adjustments);
+
+        let post = find_tool(&tools, "post__items")
+            .tool
+            .annotations
+            .clone()
+            .unwrap();
+        assert_eq!(post.idempotent_hint, Some(true));
+        assert_eq!(post.open_world_hint, Some(false));
+        // Hints not mentioned in the override keep their inferred value
+        assert_eq!(post.read_only_hint, Some(false));
+    }
+
+    #[test]
+    fn test_excluded_tools_are_not_generated() {
+        let adjustments = r#"
+disabled_tools:
+  - delete__items___id__
+exclude_routes:
+  - path: /items
+    methods: [POST]
+"#;
+        let tools = parse_spec_with_adjustments(CRUD_SPEC, adjustments);
+
+        let names: Vec<&str> = tools.iter().map(|t| t.tool.name.as_ref()).collect();
+        assert_eq!(names.len(), 2, "{:?}", names);
+        assert!(names.contains(&"get__items"));
+        assert!(names.contains(&"put__items___id__"));
+    }
+
+    #[test]
+    fn test_read_only_mode_hides_mutating_operations() {
+        let config = ParserConfig {
+            read_only: true,
+            ..Default::default()
+        };
+        let tools = parse_spec(CRUD_SPEC, config);
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool.name, "get__items");
+        let annotations = tools[0].tool.annotations.clone().unwrap();
+        assert_eq!(annotations.read_only_hint, Some(true));
+    }
+
+    #[test]
+    fn test_path_prefixes_keep_matching_subtree_only() {
+        let config = ParserConfig {
+            path_prefixes: vec!["/items/".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(parse_spec(CRUD_SPEC, config).len(), 4);
+
+        // Prefixes match whole segments, so "/item" does not match "/items"
+        let config = ParserConfig {
+            path_prefixes: vec!["/item".to_string()],
+            ..Default::default()
+        };
+        assert!(parse_spec(CRUD_SPEC, config).is_empty());
+    }
+
+    // ==================== description composition tests ====================
+
+    const DESCRIPTION_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /notes:
+    get:
+      summary: "  List notes "
+      description: |
+        Returns every note,
+        newest first.
+      responses:
+        "200":
+          description: OK
+    post:
+      summary: Create note
+      description: Create note. The body must be JSON.
+      responses:
+        "201":
+          description: Created
+  /notes/{id}:
+    delete:
+      responses:
+        "204":
+          description: Deleted
+"#;
+
+    #[test]
+    fn test_description_combines_summary_and_description() {
+        let tools = parse_spec(DESCRIPTION_SPEC, ParserConfig::default());
+
+        assert_eq!(
+            description(find_tool(&tools, "get__notes")),
+            "List notes — Returns every note, newest first."
+        );
+        // A description that already opens with the summary is not repeated
+        assert_eq!(
+            description(find_tool(&tools, "post__notes")),
+            "Create note. The body must be JSON."
+        );
+    }
+
+    #[test]
+    fn test_summary_kept_when_description_only_shares_a_prefix() {
+        let spec = DESCRIPTION_SPEC
+            .replace("summary: Create note", "summary: Create")
+            .replace(
+                "description: Create note. The body must be JSON.",
+                "description: Creates a note from JSON.",
+            );
+        let tools = parse_spec(&spec, ParserConfig::default());
+
+        assert_eq!(
+            description(find_tool(&tools, "post__notes")),
+            "Create — Creates a note from JSON."
+        );
+    }
+
+    #[test]
+    fn test_description_falls_back_to_method_and_path() {
+        let tools = parse_spec(DESCRIPTION_SPEC, ParserConfig::default());
+
+        assert_eq!(
+            description(find_tool(&tools, "delete__notes___id__")),
+            "DELETE /notes/{id}\nRequired: id."
+        );
+    }
+
+    const ARGUMENTS_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Test, version: "1.0" }
+paths:
+  /tasks/{id}:
+    get:
+      summary: Get a task with all of its comments and history
+      parameters:
+        - { name: id, in: path, required: true, schema: { type: string } }
+        - { name: verbose, in: query, schema: { type: boolean } }
+        - { name: limit, in: query, schema: { type: integer } }
+      responses: { "200": { description: OK } }
+  /tasks:
+    get:
+      summary: List tasks
+      responses: { "200": { description: OK } }
+"#;
+
+    #[test]
+    fn test_description_lists_required_and_optional_arguments() {
+        let tools = parse_spec(ARGUMENTS_SPEC, ParserConfig::default());
+
+        assert_eq!(
+            description(find_tool(&tools, "get__tasks___id__")),
+            "Get a task with all of its comments and history\nRequired: id. Optional: limit, verbose."
+        );
+        // No parameters, no argument line
+        assert_eq!(description(find_tool(&tools, "get__tasks")), "List tasks");
+    }
+
+    #[test]
+    fn test_argument_line_kept_when_description_truncated() {
+        let config = ParserConfig {
+            max_description_length: 60,
+            ..Default::default()
+        };
+        let tools = parse_spec(ARGUMENTS_SPEC, config);
+
+        assert_eq!(
+            description(find_tool(&tools, "get__tasks___id__")),
+            "Get a task with all...\nRequired: id. Optional: limit, verbose."
+        );
+    }
+
+    #[test]
+    fn test_only_argument_line_kept_when_it_leaves_no_room() {
+        // The argument line is 39 characters, leaving none for the description
+        let config = ParserConfig {
+            max_description_length: 40,
+            ..Default::default()
+        };
+        let tools = parse_spec(ARGUMENTS_SPEC, config);
+
+        assert_eq!(
+            description(find_tool(&tools, "get__tasks___id__")),
+            "Required: id. Optional: limit, verbose."
+        );
+    }
+
+    #[test]
+    fn test_description_truncated_on_char_boundary() {
+        let spec = DESCRIPTION_SPEC.replace("List notes", "Liste der Notizen über alles");
+        let config = ParserConfig {
+            max_description_length: 20,
+            ..Default::default()
+        };
+        let tools = parse_spec(&spec, config);
+
+        assert_eq!(
+            description(find_tool(&tools, "get__notes")),
+            "Liste der Notizen üb..."
+        );
+    }
+
+    // ==================== sanitize_description() tests ====================
+
+    #[test]
+    fn test_sanitize_strips_html_and_markdown() {
+        let text = "## Overview\n<p>Lists <b>all</b> items&nbsp;&amp; **archived** ones.</p>\n- See [the docs](https://example.com/docs) for details";
+
+        assert_eq!(
+            sanitize_description(text),
+            "Overview Lists all items & archived ones. See the docs (https://example.com/docs) for details"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_flattens_tables() {
+        let text = "Statuses:\n| Value | Meaning |\n|---|:---:|\n| open | Not started |";
+
+        assert_eq!(
+            sanitize_description(text),
+            "Statuses: Value; Meaning open; Not started"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_keeps_code_spans_and_urls() {
+        let text = "Call `GET /items/<id>?page=**2**` or see <https://example.com/a_b__c>";
+
+        assert_eq!(
+            sanitize_description(text),
+            "Call `GET /items/<id>?page=**2**` or see https://example.com/a_b__c"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_descriptions_off_by_default() {
+        let spec = DESCRIPTION_SPEC.replace("newest first.", "**newest** first.");
+
+        let tools = parse_spec(&spec, ParserConfig::default());
+        assert!(description(find_tool(&tools, "get__notes")).contains("**newest**"));
+
+        let config = ParserConfig {
+            sanitize_descriptions: true,
+            ..Default::default()
+        };
+        let tools = parse_spec(&spec, config);
+        assert!(description(find_tool(&tools, "get__notes")).ends_with("newest first."));
+    }
+
+    // ==================== title tests ====================
+
+    const TITLE_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /tasks:
+    get:
+      operationId: listTasksByProject
+      responses:
+        "200":
+          description: OK
+    post:
+      summary: Create a task
+      operationId: create_task
+      responses:
+        "201":
+          description: Created
+  /health:
+    get:
+      responses:
+        "200":
+          description: OK
+"#;
+
+    #[test]
+    fn test_title_from_summary_or_operation_id() {
+        let tools = parse_spec(TITLE_SPEC, ParserConfig::default());
+
+        let post = find_tool(&tools, "post__tasks");
+        assert_eq!(post.tool.title.as_deref(), Some("Create a task"));
+
+        let get = find_tool(&tools, "get__tasks");
+        assert_eq!(get.tool.title.as_deref(), Some("List tasks by project"));
+        // The machine name is unchanged
+        assert_eq!(get.tool.name, "get__tasks");
+
+        let health = find_tool(&tools, "get__health");
+        assert_eq!(health.tool.title, None);
+    }
+
+    #[test]
+    fn test_title_overridden_by_adjustments() {
+        let adjustments = r#"
+tools:
+  - name: get__health
+    title: "Health check (liveness)"
+"#;
+        let tools = parse_spec_with_adjustments(TITLE_SPEC, adjustments);
+
+        let health = find_tool(&tools, "get__health");
+        assert_eq!(
+            health.tool.title.as_deref(),
+            Some("Health check (liveness)")
+        );
+    }
+
+    // ==================== get_diagnostics() tests ====================
+
+    const DIAGNOSTICS_SPEC: &str = r##"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /pets:
+    post:
+      summary: Create pet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                owner:
+                  $ref: "#/components/schemas/Owner"
+                tags:
+                  type: array
+      responses:
+        "201":
+          description: Created
+    head:
+      responses:
+        "200":
+          description: OK
+  /pets/{id}:
+    get:
+      parameters:
+        - $ref: "#/components/parameters/PetId"
+      responses:
+        "200":
+          description: OK
+components:
+  schemas:
+    Owner:
+      type: string
+  parameters:
+    PetId:
+      name: id
+      in: path
+      required: true
+      schema:
+        type: string
+"##;
+
+    /// Helper to parse a spec and return its diagnostics
+    fn diagnostics_for(spec: &str) -> Vec<ParseDiagnostic> {
+        let file = create_temp_yaml(spec);
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser
+            .init(file.path().to_str().unwrap(), None)
+            .expect("Failed to parse spec");
+        parser.get_diagnostics().to_vec()
+    }
+
+    #[test]
+    fn test_diagnostics_empty_for_clean_spec() {
+        assert!(diagnostics_for(CRUD_SPEC).is_empty());
+    }
+
+    const COLLIDING_NAMES_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Collisions, version: '1.0' }
+paths:
+  /a-b:
+    get:
+      responses: { '200': { description: OK } }
+  /a_b:
+    get:
+      responses: { '200': { description: OK } }
+  /tasks:
+    get:
+      x-mcp-name: list_things
+      responses: { '200': { description: OK } }
+  /projects:
+    get:
+      x-mcp-name: list_things
+      responses: { '200': { description: OK } }
+"#;
+
+    #[test]
+    fn test_duplicate_tool_names_keep_first_and_are_reported() {
+        let file = create_temp_yaml(COLLIDING_NAMES_SPEC);
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser.init(file.path().to_str().unwrap(), None).unwrap();
+
+        let tools = parser.get_route_tools();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(find_tool(tools, "get__a_b").route_config.path, "/a-b");
+        // Paths are processed in sorted order
+        assert_eq!(
+            find_tool(tools, "list_things").route_config.path,
+            "/projects"
+        );
+
+        let diagnostics = parser.get_diagnostics();
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity == DiagnosticSeverity::Error));
+        assert_eq!(
+            (diagnostics[0].method.as_str(), diagnostics[0].path.as_str()),
+            ("GET", "/a_b")
+        );
+        assert!(diagnostics[0].message.contains("'get__a_b'"));
+        assert!(diagnostics[0].message.contains("GET /a-b"));
+        assert_eq!(diagnostics[1].path, "/tasks");
+        assert!(diagnostics[1].message.contains("GET /projects"));
+    }
+
+    #[test]
+    fn test_diagnostics_report_unrepresentable_schemas() {
+        let diagnostics = diagnostics_for(DIAGNOSTICS_SPEC);
+
+        let post: Vec<&ParseDiagnostic> = diagnostics
+            .iter()
+            .filter(|d| d.path == "/pets" && d.method == "POST")
+            .collect();
+        assert_eq!(post.len(), 2, "{:?}", post);
+        assert!(post
+            .iter()
+            .all(|d| d.severity == DiagnosticSeverity::Warning));
+        assert!(post.iter().any(|d| d
+            .message
+            .contains("'#/components/schemas/Owner' at body.owner")));
+        assert!(post
+            .iter()
+            .any(|d| d.message.contains("array without items at body.tags")));
+
+        let get = diagnostics
+            .iter()
+            .find(|d| d.path == "/pets/{id}" && d.method == "GET")
+            .expect("unresolved parameter should be reported");
+        assert!(get.message.contains("#/components/parameters/PetId"));
+    }
+
+    #[test]
+    fn test_diagnostics_report_skipped_operations() {
+        let diagnostics = diagnostics_for(DIAGNOSTICS_SPEC);
+
+        let head = diagnostics
+            .iter()
+            .find(|d| d.method == "HEAD")
+            .expect("skipped HEAD should be reported");
+        assert_eq!(head.severity, DiagnosticSeverity::Info);
+        assert_eq!(head.path, "/pets");
+    }
+
+    // ==================== unsupported parameter tests ====================
+
+    const COOKIE_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /session:
+    get:
+      parameters:
+        - name: session_id
+          in: cookie
+          schema:
+            type: string
+        - name: verbose
+          in: query
+          schema:
+            type: boolean
+      responses:
+        "200":
+          description: OK
+"#;
+
+    #[test]
+    fn test_unsupported_parameter_reported_and_tool_kept_by_default() {
+        let tools = parse_spec(COOKIE_SPEC, ParserConfig::default());
+        assert_eq!(tools.len(), 1);
+
+        let diagnostics = diagnostics_for(COOKIE_SPEC);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert!(diagnostics[0]
+            .message
+            .contains("cookie parameter 'session_id'"));
+    }
+
+    #[test]
+    fn test_strict_mode_skips_lossy_tools() {
+        let file = create_temp_yaml(COOKIE_SPEC);
+        let config = ParserConfig {
+            strict: true,
+            ..Default::default()
+        };
+        let mut parser = SwaggerParser::with_config(Adjuster::new(), config);
+        parser.init(file.path().to_str().unwrap(), None).unwrap();
+
+        assert!(parser.get_route_tools().is_empty());
+        let error = parser
+            .get_diagnostics()
+            .iter()
+            .find(|d| d.severity == DiagnosticSeverity::Error)
+            .expect("strict skip should be reported");
+        assert_eq!(error.path, "/session");
+        assert_eq!(error.method, "GET");
+    }
+
+    // ==================== per-route server tests ====================
+
+    const SERVERS_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+servers:
+  - url: https://api.example.com
+paths:
+  /token:
+    servers:
+      - url: https://{region}.auth.example.com
+        variables:
+          region:
+            default: eu
+    post:
+      responses:
+        "200":
+          description: OK
+    get:
+      servers:
+        - url: https://sso.example.com/v2
+      responses:
+        "200":
+          description: OK
+  /users:
+    get:
+      responses:
+        "200":
+          description: OK
+  /legacy:
+    get:
+      servers:
+        - url: /legacy-api
+      responses:
+        "200":
+          description: OK
+"#;
+
+    #[test]
+    fn test_operation_and_path_servers_set_route_base_url() {
+        let tools = parse_spec(SERVERS_SPEC, ParserConfig::default());
+        let base_url = |name: &str| find_tool(&tools, name).route_config.base_url.clone();
+
+        assert_eq!(
+            base_url("get__token"),
+            Some("https://sso.example.com/v2".to_string())
+        );
+        assert_eq!(
+            base_url("post__token"),
+            Some("https://eu.auth.example.com".to_string())
+        );
+        // Document-level servers never override the configured endpoint
+        assert_eq!(base_url("get__users"), None);
+    }
+
+    #[test]
+    fn test_relative_operation_server_is_ignored() {
+        let tools = parse_spec(SERVERS_SPEC, ParserConfig::default());
+
+        assert_eq!(find_tool(&tools, "get__legacy").route_config.base_url, None);
+        let diagnostics = diagnostics_for(SERVERS_SPEC);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "/legacy");
+        assert!(diagnostics[0].message.contains("/legacy-api"));
+    }
+
+    // ==================== free-form object tests ====================
+
+    const MAP_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /labels:
+    put:
+      summary: Replace labels
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                counts:
+                  type: object
+                  additionalProperties:
+                    type: integer
+                metadata:
+                  additionalProperties: true
+      responses:
+        "200":
+          description: OK
+"#;
+
+    fn body_schema(route_tool: &RouteTool) -> serde_json::Value {
+        route_tool.tool.input_schema["properties"]["body"].clone()
+    }
+
+    #[test]
+    fn test_free_form_objects_keep_additional_properties() {
+        let tools = parse_spec(MAP_SPEC, ParserConfig::default());
+        let body = body_schema(&tools[0]);
+
+        assert_eq!(
+            body["properties"]["counts"]["additionalProperties"]["type"],
+            "number"
+        );
+        assert_eq!(body["properties"]["metadata"]["type"], "object");
+        assert_eq!(
+            body["properties"]["metadata"]["additionalProperties"],
+            serde_json::json!(true)
+        );
+        assert!(diagnostics_for(MAP_SPEC).is_empty());
+    }
+
+    #[test]
+    fn test_gemini_compatible_strips_additional_properties() {
+        let config = ParserConfig {
+            gemini_compatible: true,
+            ..Default::default()
+        };
+        let tools = parse_spec(MAP_SPEC, config);
+        let body = body_schema(&tools[0]);
+
+        assert!(body["properties"]["counts"]
+            .get("additionalProperties")
+            .is_none());
+        assert!(body["properties"]["metadata"]
+            .get("additionalProperties")
+            .is_none());
+        assert!(GeminiValidator::validate_tool(&tools[0].tool).is_valid);
+    }
+
+    #[test]
+    fn test_default_profile_overrides_gemini_compatible() {
+        let config = ParserConfig {
+            gemini_compatible: true,
+            profile: Some("default".to_string()),
+            ..Default::default()
+        };
+        let tools = parse_spec(MAP_SPEC, config);
+        let body = body_schema(&tools[0]);
+
+        assert!(body["properties"]["counts"]
+            .get("additionalProperties")
+            .is_some());
+    }
+
+    #[test]
+    fn test_unknown_profile_is_rejected() {
+        let file = create_temp_yaml(MAP_SPEC);
+        let config = ParserConfig {
+            profile: Some("netscape".to_string()),
+            ..Default::default()
+        };
+        let mut parser = SwaggerParser::with_config(Adjuster::new(), config);
+
+        let err = parser
+            .init(file.path().to_str().unwrap(), None)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown compatibility profile 'netscape'"));
+    }
+
+    // ==================== external $ref tests ====================
+
+    const REF_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Refs, version: '1.0' }
+paths:
+  /tasks:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: './models/task.yaml#/Task'
+      responses: { '201': { description: Created } }
+  /leak:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '../secret.yaml#/Secret'
+      responses: { '201': { description: Created } }
+  /remote:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: 'http://127.0.0.1:9/models.yaml#/Task'
+      responses: { '201': { description: Created } }
+"#;
+
+    const TASK_MODEL: &str = r#"
+Task:
+  type: object
+  properties:
+    title: { type: string }
+    status: { $ref: '#/Status' }
+Status:
+  type: string
+  enum: [open, done]
+"#;
+
+    /// Parse a spec written next to a models directory and a file outside its directory
+    fn parse_with_models(spec: &str) -> SwaggerParser {
+        let dir = tempfile::tempdir().unwrap();
+        let spec_dir = dir.path().join("spec");
+        std::fs::create_dir_all(spec_dir.join("models")).unwrap();
+        std::fs::write(spec_dir.join("openapi.yaml"), spec).unwrap();
+        std::fs::write(spec_dir.join("models/task.yaml"), TASK_MODEL).unwrap();
+        std::fs::write(dir.path().join("secret.yaml"), "Secret: { type: object }").unwrap();
+
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser
+            .init(spec_dir.join("openapi.yaml").to_str().unwrap(), None)
+            .expect("Failed to parse spec");
+        parser
+    }
+
+    #[test]
+    fn test_external_file_refs_are_inlined() {
+        let parser = parse_with_models(REF_SPEC);
+
+        let body = body_schema(find_tool(parser.get_route_tools(), "post__tasks"));
+        assert_eq!(body["properties"]["title"]["type"], "string");
+        assert_eq!(
+            body["properties"]["status"]["enum"],
+            serde_json::json!(["open", "done"])
+        );
+    }
+
+    #[test]
+    fn test_refs_outside_spec_dir_and_remote_refs_are_not_loaded() {
+        let parser = parse_with_models(REF_SPEC);
+
+        let messages: Vec<_> = parser
+            .get_diagnostics()
+            .iter()
+            .map(|d| d.message.clone())
+            .collect();
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("../secret.yaml") && m.contains("outside the spec directory")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("127.0.0.1:9") && m.contains("remote refs are disabled")));
+        // The tools are still generated, with the unresolved body degraded as before
+        assert_eq!(parser.get_route_tools().len(), 3);
+    }
+
+    #[test]
+    fn test_remote_refs_fetched_when_enabled() {
+        use std::io::{Read, Write};
+
+        // Minimal HTTP server answering every request with the task model
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    TASK_MODEL.len(),
+                    TASK_MODEL
+                );
+            }
+        });
+
+        let spec = format!(
+            "openapi: 3.0.0\ninfo: {{ title: Remote, version: '1.0' }}\npaths:\n  /tasks:\n    post:\n      requestBody:\n        content:\n          application/json:\n            schema:\n              $ref: 'http://{}/models.yaml#/Task'\n      responses: {{ '201': {{ description: Created }} }}\n",
+            addr
+        );
+        let config = ParserConfig {
+            remote_refs: true,
+            ..Default::default()
+        };
+        let tools = parse_spec(&spec, config);
+
+        let body = body_schema(find_tool(&tools, "post__tasks"));
+        assert_eq!(
+            body["properties"]["status"]["enum"],
+            serde_json::json!(["open", "done"])
+        );
+    }
+
+    // ==================== path item $ref tests ====================
+
+    const PATH_ITEM_REF_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Path refs, version: '1.0' }
+paths:
+  /tasks:
+    $ref: '#/components/pathItems/Tasks'
+  /tasks/{id}:
+    $ref: './paths/task.yaml'
+  /missing:
+    $ref: '#/components/pathItems/Missing'
+components:
+  pathItems:
+    Tasks:
+      get:
+        summary: List tasks
+        responses: { '200': { description: OK } }
+      post:
+        summary: Create a task
+        responses: { '201': { description: Created } }
+"#;
+
+    #[test]
+    fn test_path_item_refs_contribute_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("paths")).unwrap();
+        let spec_path = dir.path().join("openapi.yaml");
+        std::fs::write(&spec_path, PATH_ITEM_REF_SPEC).unwrap();
+        std::fs::write(
+            dir.path().join("paths/task.yaml"),
+            "get:\n  parameters:\n    - { name: id, in: path, required: true, schema: { type: string } }\n  responses: { '200': { description: OK } }\n",
+        )
+        .unwrap();
+
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser.init(spec_path.to_str().unwrap(), None).unwrap();
+
+        let mut names: Vec<_> = parser
+            .get_route_tools()
+            .iter()
+            .map(|t| t.tool.name.to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["get__tasks", "get__tasks___id__", "post__tasks"]);
+        assert!(parser
+            .get_diagnostics()
+            .iter()
+            .any(|d| d.message.contains("#/components/pathItems/Missing")));
+    }
+
+    // ==================== security scheme tests ====================
+
+    const SECURITY_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Secure, version: '1.0' }
+security:
+  - session: []
+  - token: []
+components:
+  securitySchemes:
+    session: { type: apiKey, in: cookie, name: SESSION }
+    token: { type: apiKey, in: header, name: X-Token }
+    admin: { type: http, scheme: basic }
+paths:
+  /tasks:
+    get:
+      responses: { '200': { description: OK } }
+  /login:
+    post:
+      security: []
+      responses: { '200': { description: OK } }
+  /users:
+    delete:
+      security:
+        - admin: []
+      responses: { '204': { description: Deleted } }
+"#;
+
+    fn route_auth(tools: &[RouteTool], path: &str) -> RouteAuth {
+        tools
+            .iter()
+            .find(|t| t.route_config.path == path)
+            .map(|t| t.route_config.auth.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_global_security_sets_route_auth() {
+        let tools = parse_spec(SECURITY_SPEC, ParserConfig::default());
+
+        assert_eq!(
+            route_auth(&tools, "/tasks"),
+            RouteAuth::Global(AuthScheme::ApiKey {
+                name: "X-Token".to_string(),
+                location: ApiKeyLocation::Header,
+            })
+        );
+    }
+
+    #[test]
+    fn test_operation_security_overrides_global() {
+        let tools = parse_spec(SECURITY_SPEC, ParserConfig::default());
+
+        assert_eq!(route_auth(&tools, "/login"), RouteAuth::Public);
+        assert_eq!(
+            route_auth(&tools, "/users"),
+            RouteAuth::Operation(AuthScheme::Basic)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_security_scheme_is_reported() {
+        let spec = SECURITY_SPEC.replace("  - token: []\n", "");
+        let diagnostics = diagnostics_for(&spec);
+
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.message.contains("session") && d.message.contains("auth_type")));
+        assert_eq!(
+            route_auth(&parse_spec(&spec, ParserConfig::default()), "/tasks"),
+            RouteAuth::Endpoint
+        );
+    }
+
+    const SCOPES_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Scoped, version: '1.0' }
+security:
+  - oauth: [tasks:read]
+components:
+  securitySchemes:
+    key: { type: apiKey, in: header, name: X-Key }
+    oauth:
+      type: oauth2
+      flows:
+        clientCredentials:
+          tokenUrl: https://auth.example.com/token
+          scopes: { tasks:read: Read, tasks:write: Write, admin: Admin }
+paths:
+  /tasks:
+    get:
+      responses: { '200': { description: OK } }
+    post:
+      security:
+        - key: []
+        - oauth: [tasks:write, tasks:read, tasks:write]
+      responses: { '201': { description: Created } }
+  /health:
+    get:
+      security: []
+      responses: { '200': { description: OK } }
+"#;
+
+    fn required_scopes(tools: &[RouteTool], method: &str, path: &str) -> Vec<String> {
+        tools
+            .iter()
+            .find(|t| t.route_config.method == method && t.route_config.path == path)
+            .map(|t| t.route_config.method_config.required_scopes.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_required_scopes_come_from_operation_or_global_security() {
+        let tools = parse_spec(SCOPES_SPEC, ParserConfig::default());
+
+        assert_eq!(required_scopes(&tools, "GET", "/tasks"), vec!["tasks:read"]);
+        // The first OAuth2 requirement counts, even after one with another scheme
+        assert_eq!(
+            required_scopes(&tools, "POST", "/tasks"),
+            vec!["tasks:read", "tasks:write"]
+        );
+        assert!(required_scopes(&tools, "GET", "/health").is_empty());
+    }
+
+    // ==================== request content type tests ====================
+
+    const CONTENT_TYPE_SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Test, version: "1.0" }
+paths:
+  /tasks:
+    post:
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema:
+              type: object
+              properties: { title: { type: string } }
+      responses: { "201": { description: Created } }
+    patch:
+      requestBody:
+        content:
+          text/plain: { schema: { type: string } }
+          application/merge-patch+json:
+            schema:
+              type: object
+              properties: { done: { type: boolean } }
+      responses: { "200": { description: OK } }
+    put:
+      requestBody:
+        content:
+          text/csv: { schema: { type: string } }
+      responses: { "200": { description: OK } }
+"#;
+
+    fn content_type(route_tool: &RouteTool) -> Option<&str> {
+        route_tool
+            .route_config
+            .method_config
+            .content_type
+            .as_deref()
+    }
+
+    #[test]
+    fn test_content_type_from_request_body_media_type() {
+        let tools = parse_spec(CONTENT_TYPE_SPEC, ParserConfig::default());
+
+        let post = find_tool(&tools, "post__tasks");
+        assert_eq!(
+            content_type(post),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert!(post.tool.input_schema["properties"]["body"]["properties"]
+            .get("title")
+            .is_some());
+        // JSON-based media types are preferred over the first declared one
+        let patch = find_tool(&tools, "patch__tasks");
+        assert_eq!(content_type(patch), Some("application/merge-patch+json"));
+        assert!(patch.tool.input_schema["properties"]["body"]["properties"]
+            .get("done")
+            .is_some());
+        assert_eq!(
+            content_type(find_tool(&tools, "put__tasks")),
+            Some("text/csv")
+        );
+
+        let diagnostics = diagnostics_for(CONTENT_TYPE_SPEC);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.method == "PUT" && d.message.contains("text/csv")));
+    }
+
+    #[test]
+    fn test_content_type_overridden_by_adjustments() {
+        let adjustments = r#"
+tools:
+  - name: put__tasks
+    content_type: text/csv; charset=utf-8
+"#;
+        let tools = parse_spec_with_adjustments(CONTENT_TYPE_SPEC, adjustments);
+
+        assert_eq!(
+            content_type(find_tool(&tools, "put__tasks")),
+            Some("text/csv; charset=utf-8")
+        );
+        assert_eq!(
+            content_type(find_tool(&tools, "patch__tasks")),
+            Some("application/merge-patch+json")
+        );
+    }
+
+    // ==================== body example tests ====================
+
+    const EXAMPLE_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /tasks:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { type: object, properties: { title: { type: string } } }
+            examples:
+              simple: { value: { title: "Write docs" } }
+      responses: { "200": { description: OK } }
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties: { title: { type: string } }
+              example: { title: "From schema" }
+      responses: { "200": { description: OK } }
+    patch:
+      requestBody:
+        content:
+          application/json:
+            schema: { type: object, properties: { title: { type: string } } }
+      responses: { "200": { description: OK } }
+"#;
+
+    #[test]
+    fn test_body_example_taken_from_media_type_or_schema() {
+        let tools = parse_spec(EXAMPLE_SPEC, ParserConfig::default());
+        let example = |name: &str| {
+            find_tool(&tools, name)
+                .route_config
+                .method_config
+                .body_example
+                .clone()
+        };
+
+        assert_eq!(
+            example("post__tasks"),
+            Some(serde_json::json!({ "title": "Write docs" }))
+        );
+        assert_eq!(
+            example("put__tasks"),
+            Some(serde_json::json!({ "title": "From schema" }))
+        );
+        assert_eq!(example("patch__tasks"), None);
+    }
+
+    // ==================== example generation tests ====================
+
+    #[test]
+    fn test_example_generated_from_schema_types() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "due": { "type": "string", "format": "date-time" },
+                "priority": { "type": "string", "enum": ["low", "high"] },
+                "estimate": { "type": "integer" },
+                "done": { "type": "boolean", "default": true },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "owner": { "type": "object", "properties": { "email": { "type": "string", "format": "email" } } },
+                "score": { "type": "number", "example": 4.5 }
+            }
+        });
+
+        assert_eq!(
+            generate_example(&schema),
+            serde_json::json!({
+                "title": "string",
+                "due": "2024-01-01T00:00:00Z",
+                "priority": "low",
+                "estimate": 0,
+                "done": true,
+                "tags": ["string"],
+                "owner": { "email": "user@example.com" },
+                "score": 4.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_example_uses_first_variant_and_merges_all_of() {
+        let one_of = serde_json::json!({ "oneOf": [{ "type": "integer" }, { "type": "string" }] });
+        let all_of = serde_json::json!({ "allOf": [
+            { "type": "object", "properties": { "id": { "type": "integer" } } },
+            { "type": "object", "properties": { "name": { "type": "string" } } }
+        ] });
+
+        assert_eq!(generate_example(&one_of), serde_json::json!(0));
+        assert_eq!(
+            generate_example(&all_of),
+            serde_json::json!({ "id": 0, "name": "string" })
+        );
+    }
+
+    #[test]
+    fn test_example_keeps_only_required_properties() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["title"],
+            "properties": { "title": { "type": "string" }, "notes": { "type": "string" } }
+        });
+
+        assert_eq!(
+            generate_example(&schema),
+            serde_json::json!({ "title": "string" })
+        );
+    }
+
+    #[test]
+    fn test_body_example_added_to_body_description() {
+        let config = ParserConfig {
+            body_examples: true,
+            ..Default::default()
+        };
+        let tools = parse_spec(EXAMPLE_SPEC, config);
+        let body_description = |name: &str| {
+            find_tool(&tools, name).tool.input_schema["properties"]["body"]["description"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        assert!(body_description("post__tasks").ends_with(r#"Example: {"title":"Write docs"}"#));
+        assert!(body_description("patch__tasks").ends_with(r#"Example: {"title":"string"}"#));
+
+        let tools = parse_spec(EXAMPLE_SPEC, ParserConfig::default());
+        assert!(
+            !find_tool(&tools, "post__tasks").tool.input_schema["properties"]["body"]
+                .to_string()
+                .contains("Example:")
+        );
+    }
+
+    // ==================== x-mcp-exclude tests ====================
+
+    #[test]
+    fn test_x_mcp_exclude_skips_operation() {
+        let spec = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /admin/reset:
+    post:
+      x-mcp-exclude: true
+      responses: { "200": { description: OK } }
+  /items:
+    get:
+      x-mcp-exclude: false
+      responses: { "200": { description: OK } }
+"#;
+        let adjustments = r#"
+routes:
+  - path: /admin/reset
+    methods: [POST]
+  - path: /items
+    methods: [GET]
+"#;
+        let tools = parse_spec_with_adjustments(spec, adjustments);
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool.name, "get__items");
+        assert!(diagnostics_for(spec)
+            .iter()
+            .any(|d| d.path == "/admin/reset" && d.message.contains("x-mcp-exclude")));
+    }
+
+    // ==================== x-mcp-name / x-mcp-description tests ====================
+
+    const VENDOR_NAME_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "1.0"
+paths:
+  /tasks:
+    get:
+      summary: List tasks
+      x-mcp-name: list_tasks
+      x-mcp-description: List the caller's open tasks
+      responses: { "200": { description: OK } }
+    post:
+      summary: Create task
+      x-mcp-name: "create tasks!"
+      x-mcp-description: Create a task for the caller
+      responses: { "200": { description: OK } }
+"#;
+
+    #[test]
+    fn test_vendor_extensions_override_name_and_description() {
+        let tools = parse_spec(VENDOR_NAME_SPEC, ParserConfig::default());
+
+        let list = find_tool(&tools, "list_tasks");
+        assert!(list
+            .tool
+            .description
+            .as_deref()
+            .unwrap()
+            .starts_with("List the caller's open tasks"));
+        // An invalid name falls back to the generated one with a warning
+        find_tool(&tools, "post__tasks");
+        assert!(diagnostics_for(VENDOR_NAME_SPEC)
+            .iter()
+            .any(|d| d.method == "POST" && d.message.contains("x-mcp-name")));
+    }
+
+    #[test]
+    fn test_adjustments_win_over_vendor_extensions() {
+        let adjustments = r#"
+descriptions:
+  - path: /tasks
+    updates:
+      - method: GET
+        new_description: Tasks from adjustments
+tools:
+  - name: list_tasks
+    title: Task list
+"#;
+        let tools = parse_spec_with_adjustments(VENDOR_NAME_SPEC, adjustments);
+
+        let list = find_tool(&tools, "list_tasks");
+        assert!(list
+            .tool
+            .description
+            .as_deref()
+            .unwrap()
+            .starts_with("Tasks from adjustments"));
+        assert_eq!(list.tool.title.as_deref(), Some("Task list"));
+    }
+
+    // ==================== tool diff tests ====================
+
+    #[test]
+    fn test_diff_tools_reports_added_removed_and_changed() {
+        let new_spec = r#"
+openapi: 3.0.0
+info:
+  title: Test
+  version: "2.0"
+paths:
+  /items:
+    get:
+      summary: List items
+      parameters:
+        - name: page
+          in: query
+          required: true
+          schema: { type: integer }
+      responses: { "200": { description: OK } }
+  /items/{id}:
+    put:
+      summary: Replace item
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema: { type: integer }
+      responses: { "200": { description: OK } }
+  /items/{id}/archive:
+    post:
+      responses: { "200": { description: OK } }
+"#;
+        let old = parse_spec(CRUD_SPEC, ParserConfig::default());
+        let new = parse_spec(new_spec, ParserConfig::default());
+
+        let diff = diff_tools(&old, &new);
+
+        assert_eq!(diff.added, vec!["post__items___id___archive"]);
+        assert_eq!(diff.removed, vec!["delete__items___id__", "post__items"]);
+        let changes: Vec<(&str, &Vec<String>)> = diff
+            .changed
+            .iter()
+            .map(|c| (c.tool.as_str(), &c.changes))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "get__items",
+                    &vec!["argument 'page' added (required)".to_string()]
+                ),
+                (
+                    "put__items___id__",
+                    &vec!["argument 'id' schema changed".to_string()]
+                ),
+            ]
+        );
+        assert!(diff.report().ends_with("1 added, 2 removed, 2 changed\n"));
+        assert!(diff_tools(&old, &old).is_empty());
+    }
+
+    // ==================== argument alias tests ====================
+
+    #[test]
+    fn test_argument_aliases_rename_schema_properties() {
+        let adjustments = r#"
+tools:
+  - name: put__items___id__
+    argument_aliases:
+      id: itemId
+  - name: post__tasks
+    argument_aliases:
+      title: name
+"#;
+        let tools = parse_spec_with_adjustments(CRUD_SPEC, adjustments);
+        let put = find_tool(&tools, "put__items___id__");
+        let schema = &put.tool.input_schema;
+
+        assert!(schema["properties"].get("itemId").is_some());
+        assert!(schema["properties"].get("id").is_none());
+        assert_eq!(schema["required"], serde_json::json!(["itemId"]));
+        assert_eq!(
+            put.route_config
+                .method_config
+                .argument_aliases
+                .get("itemId"),
+            Some(&"id".to_string())
+        );
+
+        let tools = parse_spec_with_adjustments(CONTENT_TYPE_SPEC, adjustments);
+        let post = find_tool(&tools, "post__tasks");
+        let body = &post.tool.input_schema["properties"]["body"]["properties"];
+        assert!(body.get("name").is_some());
+        assert!(body.get("title").is_none());
+    }
+
+    // ==================== remote spec tests ====================
+
+    /// Serve the given statuses in order (repeating the last) with `body`, counting requests
+    fn serve_statuses(
+        statuses: Vec<u16>,
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::{Read, Write};
+        use std::sync::atomic::Ordering;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/openapi.yaml", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[n.min(statuses.len() - 1)];
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_remote_spec_fetch_retries_transient_failures() {
+        let (url, requests) = serve_statuses(vec![503, 200], CRUD_SPEC);
+        let mut parser = SwaggerParser::new(Adjuster::new());
+
+        parser
+            .init(&url, None)
+            .expect("second attempt should succeed");
+
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(!parser.get_route_tools().is_empty());
+    }
+
+    #[test]
+    fn test_remote_spec_not_found_is_not_retried() {
+        let (url, requests) = serve_statuses(vec![404], "missing");
+        let mut parser = SwaggerParser::new(Adjuster::new());
+
+        let err = parser.init(&url, None).unwrap_err();
+
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(
+            format!("{:#}", err).contains("after 1 attempt"),
+            "{:#}",
+            err
+        );
+    }
+
+    // ==================== reader tests ====================
+
+    #[test]
+    fn test_parse_reader_matches_file_parse() {
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser
+            .parse_reader(Box::new(std::io::Cursor::new(
+                CRUD_SPEC.as_bytes().to_vec(),
+            )))
+            .expect("Failed to parse spec from reader");
+
+        let names: Vec<_> = parser
+            .get_route_tools()
+            .iter()
+            .map(|t| t.tool.name.to_string())
+            .collect();
+        let expected: Vec<_> = parse_spec(CRUD_SPEC, ParserConfig::default())
+            .iter()
+            .map(|t| t.tool.name.to_string())
+            .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_reparse_replaces_previous_tools() {
+        let spec_file = create_temp_yaml(CRUD_SPEC);
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser
+            .init(spec_file.path().to_str().unwrap(), None)
+            .unwrap();
+        parser
+            .parse_reader(Box::new(std::io::Cursor::new(MAP_SPEC.as_bytes().to_vec())))
+            .unwrap();
+
+        assert_eq!(parser.get_route_tools().len(), 1);
+    }
+
+    #[test]
+    fn test_large_spec_keeps_spec_order() {
+        // Enough operations to take the multi-threaded path on multi-core machines
+        let mut spec = String::from("openapi: 3.0.0\ninfo: {title: Big, version: '1.0'}\npaths:\n");
+        for i in 0..100 {
+            spec.push_str(&format!(
+                "  /items{i:03}/{{id}}:\n    get:\n      parameters:\n        - {{name: id, in: path, required: true, schema: {{type: integer}}}}\n      responses: {{'200': {{description: OK}}}}\n    delete:\n      responses: {{'204': {{description: Gone}}}}\n"
+            ));
+        }
+
+        let tools = parse_spec(&spec, ParserConfig::default());
+
+        assert_eq!(tools.len(), 200);
+        for (i, pair) in tools.chunks(2).enumerate() {
+            let path = format!("/items{:03}/{{id}}", i);
+            assert_eq!(pair[0].route_config.path, path);
+            assert_eq!(pair[0].route_config.method, "GET");
+            assert_eq!(pair[1].route_config.method, "DELETE");
+            assert!(pair[0]
+                .route_config
+                .method_config
+                .param_schemas
+                .contains_key("id"));
+        }
+    }
+
+    // ==================== tool cache tests ====================
+
+    #[test]
+    fn test_cache_key_covers_spec_adjustments_and_config() {
+        let config = ParserConfig::default();
+        let key = ToolCache::key(CRUD_SPEC.as_bytes(), None, &config);
+
+        assert_eq!(key, ToolCache::key(CRUD_SPEC.as_bytes(), None, &config));
+        assert_ne!(key, ToolCache::key(MAP_SPEC.as_bytes(), None, &config));
+        assert_ne!(
+            key,
+            ToolCache::key(CRUD_SPEC.as_bytes(), Some(b"mcp: []"), &config)
+        );
+        let read_only = ParserConfig {
+            read_only: true,
+            ..Default::default()
+        };
+        assert_ne!(key, ToolCache::key(CRUD_SPEC.as_bytes(), None, &read_only));
+    }
+
+    #[test]
+    fn test_cache_round_trip_and_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ToolCache::new(dir.path());
+        let tools = parse_spec(CRUD_SPEC, ParserConfig::default());
+        let key = ToolCache::key(CRUD_SPEC.as_bytes(), None, &ParserConfig::default());
+
+        assert!(cache.load(&key).is_none());
+        let info = SpecInfo {
+            title: "Tasks".to_string(),
+            version: "1.0".to_string(),
+            description: None,
+        };
+        cache.store(&key, &tools, &[], Some(&info), &[]).unwrap();
+
+        let cached = cache.load(&key).expect("stored entry should load");
+        assert_eq!(cached.info, Some(info));
+        assert_eq!(cached.route_tools.len(), tools.len());
+        assert_eq!(cached.route_tools[0].tool, tools[0].tool);
+        assert_eq!(
+            cached.route_tools[0].route_config.path,
+            tools[0].route_config.path
+        );
+
+        // A corrupt entry is a miss, not an error
+        std::fs::write(dir.path().join(format!("{}.json", key)), b"{not json").unwrap();
+        assert!(cache.load(&key).is_none());
+    }
+
+    #[test]
+    fn test_cache_entry_stale_when_ref_document_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("models")).unwrap();
+        let spec_path = dir.path().join("openapi.yaml");
+        std::fs::write(&spec_path, REF_SPEC).unwrap();
+        std::fs::write(dir.path().join("models/task.yaml"), TASK_MODEL).unwrap();
+
+        let mut parser = SwaggerParser::new(Adjuster::new());
+        parser.init(spec_path.to_str().unwrap(), None).unwrap();
+        let documents = parser.get_external_documents().to_vec();
+        assert_eq!(documents.len(), 1);
+        assert!(documents[0].ends_with("task.yaml"), "{:?}", documents);
+
+        let cache = ToolCache::new(dir.path().join("cache"));
+        let key = ToolCache::key(REF_SPEC.as_bytes(), None, &ParserConfig::default());
+        let tools = parser.get_route_tools();
+        cache.store(&key, tools, &[], None, &documents).unwrap();
+        assert!(cache.load(&key).is_some());
+
+        // The main spec is unchanged, so only the recorded document can tell the entry is stale
+        std::fs::write(
+            dir.path().join("models/task.yaml"),
+            TASK_MODEL.replace("title", "name"),
+        )
+        .unwrap();
+        assert!(cache.load(&key).is_none());
+
+        // Results built from remote documents are never stored
+        let remote = ["http://127.0.0.1:9/models.yaml".to_string()];
+        cache.store("remote", tools, &[], None, &remote).unwrap();
+        assert!(cache.load("remote").is_none());
+    }
+
+    // ==================== multi-file tests ====================
+
+    fn spec_tools(file: &str, prefix: &str, spec: &str) -> SpecTools {
+        SpecTools {
+            file: file.to_string(),
+            tool_prefix: Some(prefix.to_string()),
+            route_tools: parse_spec(spec, ParserConfig::default()),
+        }
+    }
+
+    #[test]
+    fn test_swagger_sources_prefix_only_multiple_files() {
+        let single = AppConfig {
+            swagger_file: "core.yaml".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            single.swagger_sources(),
+            vec![SwaggerSource {
+                path: "core.yaml".to_string(),
+                tool_prefix: None,
+            }]
+        );
+
+        let multiple = AppConfig {
+            swagger_file: "specs/core.yaml, bill=billing.yaml".to_string(),
+            ..Default::default()
+        };
+        let prefixes: Vec<_> = multiple
+            .swagger_sources()
+            .into_iter()
+            .map(|s| (s.path, s.tool_prefix.unwrap()))
+            .collect();
+        assert_eq!(
+            prefixes,
+            vec![
+                ("specs/core.yaml".to_string(), "core".to_string()),
+                ("billing.yaml".to_string(), "bill".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swagger_sources_keep_url_query_strings_whole() {
+        let config = AppConfig {
+            swagger_file:
+                "https://host/openapi.json?version=2&a=b, bill=https://host/billing.yaml?v=1"
+                    .to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.swagger_sources(),
+            vec![
+                SwaggerSource {
+                    path: "https://host/openapi.json?version=2&a=b".to_string(),
+                    tool_prefix: Some("openapi".to_string()),
+                },
+                SwaggerSource {
+                    path: "https://host/billing.yaml?v=1".to_string(),
+                    tool_prefix: Some("bill".to_string()),
+                },
+            ]
+        );
+        // Only a plain name before `=` is a prefix
+        assert_eq!(
+            SwaggerSource::parse("specs/v=2/core.yaml").path,
+            "specs/v=2/core.yaml"
+        );
+    }
+
+    #[test]
+    fn test_merge_prefixes_tool_names() {
+        let (tools, diagnostics) = merge_spec_tools(vec![
+            spec_tools("core.yaml", "core", CRUD_SPEC),
+            spec_tools("billing.yaml", "billing", MAP_SPEC),
+        ]);
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(tools.len(), 5);
+        find_tool(&tools, "core_get__items");
+        assert!(tools
+            .iter()
+            .filter(|t| t.route_config.path == "/labels")
+            .all(|t| t.tool.name.starts_with("billing_")));
+    }
+
+    #[test]
+    fn test_merge_reports_conflicting_operations() {
+        let (tools, diagnostics) = merge_spec_tools(vec![
+            spec_tools("core.yaml", "core", CRUD_SPEC),
+            spec_tools("copy.yaml", "copy", CRUD_SPEC),
+        ]);
+
+        assert_eq!(tools.len(), 4);
+        assert!(tools.iter().all(|t| t.tool.name.starts_with("core_")));
+        assert_eq!(diagnostics.len(), 4);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity == DiagnosticSeverity::Warning && d.message.contains("copy.yaml")));
+    }
+
+    #[test]
+    fn test_merge_reports_tool_name_collisions_between_operations() {
+        let renamed = r#"
+openapi: 3.0.0
+info: { title: Things, version: '1.0' }
+paths:
+  /things:
+    get:
+      x-mcp-name: get__items
+      responses: { '200': { description: OK } }
+"#;
+        let (tools, diagnostics) = merge_spec_tools(vec![
+            spec_tools("core.yaml", "api", CRUD_SPEC),
+            spec_tools("things.yaml", "api", renamed),
+        ]);
+
+        assert_eq!(tools.len(), 4);
+        assert_eq!(
+            find_tool(&tools, "api_get__items").route_config.path,
+            "/items"
+        );
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].path, "/things");
+        assert!(diagnostics[0].message.contains(
+            "'api_get__items' in things.yaml is also generated by GET /items in core.yaml"
+        ));
+    }
+}
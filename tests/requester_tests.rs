@@ -0,0 +1,1065 @@
+use yas_mcp::internal::config::_config::{AuthType, EndpointConfig};
+use yas_mcp::internal::requester::http_requester::DEFAULT_USER_AGENT;
+use yas_mcp::internal::requester::limiter::{host_key, ConcurrencyLimiter};
+use yas_mcp::internal::requester::signing::hmac_sha256;
+use yas_mcp::internal::requester::{
+    build_url, coerce_arguments, join_url, missing_scopes, ApiKeyLocation, AuthScheme,
+    HttpRequester, HttpResponse, Interceptor, RequestParts, RouteAuth, RouteConfig, TraceContext,
+};
+use yas_mcp::internal::server::tool::ToolCallError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Map, Value};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Helper to build the per-argument schema map the parser stores on MethodConfig
+    fn schemas(value: Value) -> HashMap<String, Value> {
+        value.as_object().unwrap().clone().into_iter().collect()
+    }
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    // ==================== coerce_arguments() tests ====================
+
+    #[test]
+    fn test_coerce_string_to_number() {
+        let schemas = schemas(json!({
+            "page": { "type": "number" },
+            "ratio": { "type": "number" }
+        }));
+        let mut arguments = args(json!({ "page": "1", "ratio": "0.5" }));
+
+        coerce_arguments(&mut arguments, &schemas);
+
+        assert_eq!(arguments["page"], json!(1));
+        assert_eq!(arguments["ratio"], json!(0.5));
+    }
+
+    #[test]
+    fn test_coerce_string_to_boolean() {
+        let schemas = schemas(json!({ "archived": { "type": "boolean" } }));
+        let mut arguments = args(json!({ "archived": "false" }));
+
+        coerce_arguments(&mut arguments, &schemas);
+
+        assert_eq!(arguments["archived"], json!(false));
+    }
+
+    #[test]
+    fn test_coerce_leaves_strings_and_unknown_untouched() {
+        let schemas = schemas(json!({
+            "search": { "type": "string" },
+            "page": { "type": "number" }
+        }));
+        let mut arguments = args(json!({
+            "search": "42",
+            "page": "not-a-number",
+            "extra": "true"
+        }));
+
+        coerce_arguments(&mut arguments, &schemas);
+
+        assert_eq!(arguments["search"], json!("42"));
+        assert_eq!(arguments["page"], json!("not-a-number"));
+        assert_eq!(arguments["extra"], json!("true"));
+    }
+
+    #[test]
+    fn test_coerce_nested_body_fields() {
+        let schemas = schemas(json!({
+            "body": {
+                "type": "object",
+                "properties": {
+                    "estimated_hours": { "type": "number" },
+                    "flags": { "type": "array", "items": { "type": "boolean" } }
+                }
+            }
+        }));
+        let mut arguments = args(json!({
+            "body": { "estimated_hours": "5", "flags": ["true", "false"] }
+        }));
+
+        coerce_arguments(&mut arguments, &schemas);
+
+        assert_eq!(arguments["body"]["estimated_hours"], json!(5));
+        assert_eq!(arguments["body"]["flags"], json!([true, false]));
+    }
+
+    // ==================== dry-run tests ====================
+
+    /// Route whose `id` is a path param and `verbose` a query param
+    fn task_route(method: &str) -> RouteConfig {
+        let mut route =
+            RouteConfig::new("/tasks/{id}".to_string(), method.to_string(), String::new());
+        route.method_config.query_params = vec!["verbose".to_string()];
+        route
+    }
+
+    fn requester(dry_run: bool) -> HttpRequester {
+        // Port 9 (discard) is never listened on, so a real send would fail
+        let endpoint = EndpointConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            headers: HashMap::from([("x-api-key".to_string(), "secret".to_string())]),
+            dry_run,
+            ..Default::default()
+        };
+        HttpRequester::new(&endpoint).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_config_returns_composed_request() {
+        let executor = requester(true)
+            .build_route_executor(&task_route("PUT"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "verbose": true, "title": "x"}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(request["dry_run"], json!(true));
+        assert_eq!(request["method"], json!("PUT"));
+        assert_eq!(
+            request["url"],
+            json!("http://127.0.0.1:9/tasks/7?verbose=true")
+        );
+        // Credential headers are sent but not shown to the client
+        assert_eq!(request["headers"]["x-api-key"], json!("<redacted>"));
+        assert_eq!(request["body"], json!({ "title": "x" }));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_argument_is_consumed() {
+        let executor = requester(false)
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "__dry_run": true}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+        assert_eq!(request["body"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_argument_becomes_header() {
+        let executor = requester(true)
+            .with_trace_context(true)
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+        let response = executor(&json!({ "id": "7", "__traceparent": traceparent }).to_string())
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+        assert_eq!(request["headers"]["traceparent"], json!(traceparent));
+
+        // An invalid value is dropped rather than forwarded
+        let response = executor(r#"{"id": "7", "__traceparent": "not-a-trace"}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+        assert!(request["headers"].get("traceparent").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_argument_dropped_without_trace_context() {
+        let executor = requester(true)
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+        let response = executor(&json!({ "id": "7", "__traceparent": traceparent }).to_string())
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+        assert!(request["headers"].get("traceparent").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_if_none_match_argument_becomes_header() {
+        let executor = requester(true)
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "__if_none_match": "\"v1\""}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+        assert_eq!(request["headers"]["if-none-match"], json!("\"v1\""));
+    }
+
+    #[test]
+    fn test_trace_context_parse_and_format() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let trace = TraceContext::parse(header).unwrap();
+        assert_eq!(trace.to_header(), header);
+        assert!(trace.sampled);
+
+        let child = trace.child();
+        assert_eq!(child.trace_id, trace.trace_id);
+        assert_eq!(child.span_id.len(), 16);
+        assert_ne!(child.span_id, trace.span_id);
+        assert!(TraceContext::parse(&TraceContext::new_root().to_header()).is_some());
+
+        for invalid in [
+            "",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ] {
+            assert!(TraceContext::parse(invalid).is_none(), "{}", invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_argument_overrides_accept_header() {
+        let mut route = task_route("GET");
+        route
+            .headers
+            .insert("Accept".to_string(), "application/json".to_string());
+        let executor = requester(true).build_route_executor(&route).unwrap();
+
+        let response = executor(r#"{"id": "7", "__accept": "text/csv"}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(request["headers"]["accept"], json!("text/csv"));
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+    }
+
+    #[tokio::test]
+    async fn test_body_sent_in_route_content_type() {
+        let mut route = task_route("POST");
+        route.method_config.content_type = Some("application/x-www-form-urlencoded".to_string());
+        let executor = requester(true).build_route_executor(&route).unwrap();
+
+        let response = executor(r#"{"id": "7", "body": {"title": "a b", "done": false}}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(
+            request["headers"]["content-type"],
+            json!("application/x-www-form-urlencoded")
+        );
+        assert_eq!(request["body"], json!("done=false&title=a+b"));
+
+        route.method_config.content_type = Some("application/merge-patch+json".to_string());
+        let executor = requester(true).build_route_executor(&route).unwrap();
+        let response = executor(r#"{"id": "7", "title": "x"}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(
+            request["headers"]["content-type"],
+            json!("application/merge-patch+json")
+        );
+        assert_eq!(request["body"], json!({ "title": "x" }));
+
+        // Arguments are never sent as JSON under a media type that is not JSON
+        route.method_config.content_type = Some("application/xml".to_string());
+        let executor = requester(true).build_route_executor(&route).unwrap();
+        let error = executor(r#"{"id": "7", "title": "x"}"#).await.unwrap_err();
+        match error.downcast_ref::<ToolCallError>() {
+            Some(ToolCallError::InvalidArguments(violations)) => {
+                assert!(
+                    violations[0].contains("application/xml"),
+                    "{:?}",
+                    violations
+                );
+                assert!(violations[0].contains("__raw_body"), "{:?}", violations);
+            }
+            None => panic!("expected an argument error, got {:#}", error),
+        }
+        let response = executor(r#"{"id": "7", "__raw_body": "<task/>"}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(request["body"], json!("<task/>"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_body_sent_verbatim() {
+        let mut route = task_route("POST");
+        route.method_config.content_type = Some("application/x-ndjson".to_string());
+        let executor = requester(true).build_route_executor(&route).unwrap();
+
+        let response =
+            executor(r#"{"id": "7", "title": "ignored", "__raw_body": "{\"a\":1}\n{\"a\":2}\n"}"#)
+                .await
+                .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(
+            request["headers"]["content-type"],
+            json!("application/x-ndjson")
+        );
+        assert_eq!(request["body"], json!("{\"a\":1}\n{\"a\":2}\n"));
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7"));
+    }
+
+    #[tokio::test]
+    async fn test_argument_aliases_renamed_to_upstream_names() {
+        let mut route = task_route("POST");
+        route.method_config.argument_aliases = HashMap::from([
+            ("taskId".to_string(), "id".to_string()),
+            ("detailed".to_string(), "verbose".to_string()),
+            ("name".to_string(), "title".to_string()),
+        ]);
+        let executor = requester(true).build_route_executor(&route).unwrap();
+
+        let response = executor(r#"{"taskId": "7", "detailed": true, "body": {"name": "x"}}"#)
+            .await
+            .unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(
+            request["url"],
+            json!("http://127.0.0.1:9/tasks/7?verbose=true")
+        );
+        assert_eq!(request["body"], json!({ "body": { "title": "x" } }));
+    }
+
+    #[tokio::test]
+    async fn test_default_query_added_unless_provided() {
+        let endpoint = EndpointConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            dry_run: true,
+            default_query: HashMap::from([
+                ("api_version".to_string(), "1".to_string()),
+                ("format".to_string(), "json".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let mut route = task_route("GET");
+        route.method_config.default_query =
+            HashMap::from([("api_version".to_string(), "2".to_string())]);
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .unwrap();
+
+        let call = |args: &'static str| {
+            let executor = executor.clone();
+            async move {
+                let response = executor(args).await.unwrap();
+                let request: Value = serde_json::from_slice(&response.body).unwrap();
+                request["url"].clone()
+            }
+        };
+
+        assert_eq!(
+            call(r#"{"id": "7"}"#).await,
+            json!("http://127.0.0.1:9/tasks/7?api_version=2&format=json")
+        );
+        assert_eq!(
+            call(r#"{"id": "7", "verbose": true, "format": "csv"}"#).await,
+            json!("http://127.0.0.1:9/tasks/7?verbose=true&format=csv&api_version=2")
+        );
+    }
+
+    // ==================== interceptor tests ====================
+
+    /// Signs requests, rewrites the host and records the last status seen
+    #[derive(Default)]
+    struct RecordingInterceptor {
+        last_status: AtomicU16,
+    }
+
+    impl Interceptor for RecordingInterceptor {
+        fn before_request(&self, request: &mut RequestParts) {
+            request
+                .headers
+                .insert("x-signature".to_string(), "signed".to_string());
+            request.url = request.url.replace("127.0.0.1:9", "upstream.test");
+        }
+
+        fn after_response(&self, response: &HttpResponse) {
+            self.last_status
+                .store(response.status_code, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_modifies_request_before_send() {
+        let interceptor = Arc::new(RecordingInterceptor::default());
+        let executor = requester(true)
+            .with_interceptor(interceptor.clone())
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7"}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(request["url"], json!("http://upstream.test/tasks/7"));
+        assert_eq!(request["headers"]["x-signature"], json!("signed"));
+        // Dry runs never reach upstream, so there is no response to observe
+        assert_eq!(interceptor.last_status.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_observes_response() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/tasks/{id}",
+            axum::routing::get(|| async { (axum::http::StatusCode::ACCEPTED, "ok") }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let endpoint = EndpointConfig {
+            base_url: format!("http://{}", addr),
+            ..Default::default()
+        };
+        let interceptor = Arc::new(RecordingInterceptor::default());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .with_interceptor(interceptor.clone())
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7"}"#).await.unwrap();
+
+        assert_eq!(response.status_code, 202);
+        assert_eq!(interceptor.last_status.load(Ordering::SeqCst), 202);
+    }
+
+    // ==================== timeout tests ====================
+
+    #[tokio::test]
+    async fn test_set_timeout_applies_per_request() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/tasks/{id}",
+            axum::routing::get(|| async {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                "late"
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let endpoint = EndpointConfig {
+            base_url: format!("http://{}", addr),
+            ..Default::default()
+        };
+        let mut requester = HttpRequester::new(&endpoint).unwrap();
+        requester.set_timeout(std::time::Duration::from_millis(100));
+        let executor = requester.build_route_executor(&task_route("GET")).unwrap();
+
+        let started = std::time::Instant::now();
+        let err = executor(r#"{"id": "7"}"#).await.unwrap_err();
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+        let timed_out = err
+            .chain()
+            .filter_map(|e| e.downcast_ref::<reqwest::Error>())
+            .any(|e| e.is_timeout());
+        assert!(timed_out, "expected a timeout, got {:#}", err);
+    }
+
+    #[tokio::test]
+    async fn test_pool_options_keep_requests_working() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route("/tasks/{id}", axum::routing::get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let endpoint = EndpointConfig {
+            base_url: format!("http://{}", addr),
+            pool_max_idle_per_host: Some(1),
+            pool_idle_timeout: Some(5),
+            ..Default::default()
+        };
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        for _ in 0..3 {
+            let response = executor(r#"{"id": "7"}"#).await.unwrap();
+            assert_eq!(response.status_code, 200);
+        }
+    }
+
+    /// Serve `/tasks/{id}` and answer with the HTTP version each request arrived with
+    async fn version_echo_endpoint() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/tasks/{id}",
+            axum::routing::get(
+                |version: axum::http::Version| async move { format!("{:?}", version) },
+            ),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_http2_prior_knowledge() {
+        let base_url = version_echo_endpoint().await;
+        let call = |endpoint: EndpointConfig| async move {
+            let executor = HttpRequester::new(&endpoint)
+                .unwrap()
+                .build_route_executor(&task_route("GET"))
+                .unwrap();
+            let response = executor(r#"{"id": "7"}"#).await.unwrap();
+            String::from_utf8(response.body).unwrap()
+        };
+
+        let negotiated = call(EndpointConfig {
+            base_url: base_url.clone(),
+            ..Default::default()
+        })
+        .await;
+        let forced = call(EndpointConfig {
+            base_url,
+            http2_prior_knowledge: true,
+            http2_keep_alive_interval: Some(10),
+            ..Default::default()
+        })
+        .await;
+
+        assert_eq!(negotiated, "HTTP/1.1");
+        assert_eq!(forced, "HTTP/2.0");
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_default_and_override() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/tasks/{id}",
+            axum::routing::get(|headers: axum::http::HeaderMap| async move {
+                headers["user-agent"].to_str().unwrap().to_string()
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let call = |user_agent: Option<&str>| {
+            let endpoint = EndpointConfig {
+                base_url: format!("http://{}", addr),
+                user_agent: user_agent.map(str::to_string),
+                ..Default::default()
+            };
+            async move {
+                let executor = HttpRequester::new(&endpoint)
+                    .unwrap()
+                    .build_route_executor(&task_route("GET"))
+                    .unwrap();
+                let response = executor(r#"{"id": "7"}"#).await.unwrap();
+                String::from_utf8(response.body).unwrap()
+            }
+        };
+
+        assert_eq!(call(None).await, DEFAULT_USER_AGENT);
+        assert!(DEFAULT_USER_AGENT.starts_with("yas-mcp/"));
+        assert_eq!(call(Some("my-agent/1.0")).await, "my-agent/1.0");
+    }
+
+    // ==================== concurrency limit tests ====================
+
+    #[tokio::test]
+    async fn test_max_concurrent_requests_queues_excess_requests() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (active_in_handler, peak_in_handler) = (active.clone(), peak.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/tasks/{id}",
+            axum::routing::get(move || {
+                let (active, peak) = (active_in_handler.clone(), peak_in_handler.clone());
+                async move {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    "ok"
+                }
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let endpoint = EndpointConfig {
+            base_url: format!("http://{}", addr),
+            max_concurrent_requests: Some(2),
+            ..Default::default()
+        };
+        let requester = HttpRequester::new(&endpoint).unwrap();
+        let limiter = requester.limiter();
+        let executor = requester.build_route_executor(&task_route("GET")).unwrap();
+
+        let calls: Vec<_> = (0..5)
+            .map(|_| tokio::spawn(executor(r#"{"id": "7"}"#)))
+            .collect();
+        for call in calls {
+            assert_eq!(call.await.unwrap().unwrap().status_code, 200);
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_counts_and_blocks_per_host() {
+        let limiter = ConcurrencyLimiter::new(Some(1));
+        let first = limiter.acquire("api.test:80").await;
+        // Other hosts have their own slots
+        let other = limiter.acquire("other.test:80").await;
+        assert_eq!(limiter.in_flight(), 2);
+        assert_eq!(limiter.in_flight_for("api.test:80"), 1);
+
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            limiter.acquire("api.test:80"),
+        )
+        .await;
+        assert!(blocked.is_err(), "second request to a full host must wait");
+
+        drop(first);
+        drop(other);
+        let _second = limiter.acquire("api.test:80").await;
+        assert_eq!(limiter.in_flight(), 1);
+
+        assert_eq!(host_key("https://api.test/v1/tasks"), "api.test:443");
+        assert_eq!(host_key("http://127.0.0.1:8080/x"), "127.0.0.1:8080");
+    }
+
+    // ==================== auth tests ====================
+
+    /// Keeps the last request as it would be sent, before dry-run redaction
+    #[derive(Default)]
+    struct CapturingInterceptor {
+        last_request: std::sync::Mutex<Option<RequestParts>>,
+    }
+
+    impl Interceptor for CapturingInterceptor {
+        fn before_request(&self, request: &mut RequestParts) {
+            *self.last_request.lock().unwrap() = Some(request.clone());
+        }
+    }
+
+    /// URL and headers of the request a dry run composes, with credentials unredacted
+    async fn dry_run_request(endpoint: EndpointConfig, route: RouteConfig) -> Value {
+        let interceptor = Arc::new(CapturingInterceptor::default());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .with_interceptor(interceptor.clone())
+            .build_route_executor(&route)
+            .unwrap();
+        executor(r#"{"id": "7"}"#).await.unwrap();
+        let parts = interceptor.last_request.lock().unwrap().clone().unwrap();
+        json!({ "url": parts.url, "headers": parts.headers })
+    }
+
+    fn auth_endpoint(auth_type: AuthType, auth_config: &[(&str, &str)]) -> EndpointConfig {
+        EndpointConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            dry_run: true,
+            auth_type,
+            auth_config: auth_config
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spec_auth_scheme_applied_with_configured_credentials() {
+        let mut route = task_route("GET");
+        route.auth = RouteAuth::Global(AuthScheme::ApiKey {
+            name: "X-Token".to_string(),
+            location: ApiKeyLocation::Header,
+        });
+
+        let request =
+            dry_run_request(auth_endpoint(AuthType::None, &[("api_key", "k3y")]), route).await;
+
+        assert_eq!(request["headers"]["x-token"], json!("k3y"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_auth_type_overrides_spec_scheme() {
+        let mut route = task_route("GET");
+        route.auth = RouteAuth::Global(AuthScheme::Bearer);
+
+        let request = dry_run_request(
+            auth_endpoint(
+                AuthType::ApiKey,
+                &[("api_key", "k3y"), ("name", "key"), ("in", "query")],
+            ),
+            route,
+        )
+        .await;
+
+        assert_eq!(request["url"], json!("http://127.0.0.1:9/tasks/7?key=k3y"));
+        assert_eq!(request["headers"].get("authorization"), None);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_from_config() {
+        let request = dry_run_request(
+            auth_endpoint(AuthType::Bearer, &[("token", "t0ken")]),
+            task_route("GET"),
+        )
+        .await;
+
+        assert_eq!(request["headers"]["authorization"], json!("Bearer t0ken"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_response_redacts_bearer_token() {
+        let executor = HttpRequester::new(&auth_endpoint(AuthType::Bearer, &[("token", "t0ken")]))
+            .unwrap()
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "__dry_run": true}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(request["headers"]["authorization"], json!("<redacted>"));
+        assert!(!String::from_utf8_lossy(&response.body).contains("t0ken"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_response_redacts_query_api_key() {
+        let endpoint = auth_endpoint(
+            AuthType::ApiKey,
+            &[("api_key", "k3y"), ("name", "key"), ("in", "query")],
+        );
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&task_route("GET"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7"}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        let url = request["url"].as_str().unwrap();
+        assert!(url.starts_with("http://127.0.0.1:9/tasks/7?"), "{}", url);
+        assert!(url.contains("key=<redacted>"), "{}", url);
+        assert!(!String::from_utf8_lossy(&response.body).contains("k3y"));
+    }
+
+    #[tokio::test]
+    async fn test_public_operation_sends_no_credentials() {
+        let mut route = task_route("POST");
+        route.auth = RouteAuth::Public;
+
+        let request = dry_run_request(
+            auth_endpoint(AuthType::Bearer, &[("token", "t0ken")]),
+            route,
+        )
+        .await;
+
+        assert_eq!(request["headers"].get("authorization"), None);
+    }
+
+    #[tokio::test]
+    async fn test_operation_scheme_overrides_configured_auth_type() {
+        let mut route = task_route("GET");
+        route.auth = RouteAuth::Operation(AuthScheme::ApiKey {
+            name: "X-Token".to_string(),
+            location: ApiKeyLocation::Header,
+        });
+
+        let request = dry_run_request(
+            auth_endpoint(AuthType::Bearer, &[("token", "t0ken"), ("api_key", "k3y")]),
+            route,
+        )
+        .await;
+
+        assert_eq!(request["headers"]["x-token"], json!("k3y"));
+        assert_eq!(request["headers"].get("authorization"), None);
+    }
+
+    #[test]
+    fn test_missing_scopes_compares_with_granted_scope() {
+        let required = vec!["tasks:read".to_string(), "tasks:write".to_string()];
+        let mut endpoint = EndpointConfig {
+            auth_type: AuthType::OAuth2,
+            ..Default::default()
+        };
+        // Without a configured grant nothing can be said to be missing
+        assert!(missing_scopes(&endpoint, &required).is_empty());
+
+        endpoint
+            .auth_config
+            .insert("scope".to_string(), "tasks:read profile".to_string());
+        assert_eq!(missing_scopes(&endpoint, &required), vec!["tasks:write"]);
+    }
+
+    // ==================== signing tests ====================
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231_vectors() {
+        assert_eq!(
+            hex(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        // Keys longer than the block size are hashed first
+        assert_eq!(
+            hex(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requests_signed_over_final_body() {
+        let endpoint = EndpointConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            dry_run: true,
+            hmac_secret: Some("k3y".to_string()),
+            hmac_header: Some("X-Sig".to_string()),
+            ..Default::default()
+        };
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&task_route("POST"))
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "title": "x"}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        let timestamp = request["headers"]["x-timestamp"].as_str().unwrap();
+        let message = format!("POST\n/tasks/7\n{}\n{{\"title\":\"x\"}}", timestamp);
+        assert_eq!(
+            request["headers"]["x-sig"],
+            json!(hex(&hmac_sha256(b"k3y", message.as_bytes())))
+        );
+    }
+
+    // ==================== join_url() tests ====================
+
+    #[test]
+    fn test_join_url_single_slash_at_boundary() {
+        assert_eq!(join_url("http://host", "/users"), "http://host/users");
+        assert_eq!(join_url("http://host/", "/users"), "http://host/users");
+        assert_eq!(join_url("http://host/", "users"), "http://host/users");
+        assert_eq!(join_url("http://host", "users"), "http://host/users");
+    }
+
+    #[test]
+    fn test_join_url_keeps_base_path() {
+        assert_eq!(
+            join_url("http://host/api/", "/users"),
+            "http://host/api/users"
+        );
+        assert_eq!(
+            join_url("http://host/api", "users/{id}"),
+            "http://host/api/users/{id}"
+        );
+    }
+
+    #[test]
+    fn test_join_url_root_and_empty_paths() {
+        assert_eq!(join_url("http://host/api", "/"), "http://host/api/");
+        assert_eq!(join_url("http://host/api/", ""), "http://host/api");
+    }
+
+    #[test]
+    fn test_build_url_preserves_base_query() {
+        let url = build_url("https://host/api?key=abc", "/users").unwrap();
+
+        assert_eq!(url.as_str(), "https://host/api/users?key=abc");
+    }
+
+    #[tokio::test]
+    async fn test_route_query_merged_onto_base_query() {
+        let endpoint = EndpointConfig {
+            base_url: "https://host/api/?key=abc".to_string(),
+            dry_run: true,
+            ..Default::default()
+        };
+        let mut route =
+            RouteConfig::new("/tasks/{id}".to_string(), "GET".to_string(), String::new());
+        route.method_config.query_params = vec!["page".to_string()];
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .unwrap();
+
+        let response = executor(r#"{"id": "7", "page": 2}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(
+            request["url"],
+            json!("https://host/api/tasks/7?key=abc&page=2")
+        );
+    }
+
+    // ==================== base URL validation tests ====================
+
+    fn build_error(base_url: &str) -> String {
+        let endpoint = EndpointConfig {
+            base_url: base_url.to_string(),
+            ..Default::default()
+        };
+        match HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&task_route("GET"))
+        {
+            Ok(_) => panic!("base URL '{}' should be rejected", base_url),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_missing_base_url_is_rejected() {
+        assert!(build_error("").contains("no API endpoint configured"));
+    }
+
+    #[test]
+    fn test_relative_base_url_is_rejected() {
+        assert!(build_error("/api").contains("not an absolute URL"));
+    }
+
+    #[tokio::test]
+    async fn test_route_base_url_overrides_endpoint() {
+        let endpoint = EndpointConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+        let mut route = task_route("GET");
+        route.base_url = Some("https://auth.example.com/".to_string());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .expect("route base URL should satisfy validation");
+
+        let response = executor(r#"{"id": "7"}"#).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(request["url"], json!("https://auth.example.com/tasks/7"));
+    }
+
+    // ==================== path prefix tests ====================
+
+    async fn dry_run_url(endpoint: EndpointConfig, path: &str) -> Value {
+        let route = RouteConfig::new(path.to_string(), "GET".to_string(), String::new());
+        let executor = HttpRequester::new(&endpoint)
+            .unwrap()
+            .build_route_executor(&route)
+            .unwrap();
+
+        let args = if path.contains("{id}") {
+            r#"{"id": "7"}"#
+        } else {
+            "{}"
+        };
+        let response = executor(args).await.unwrap();
+        let request: Value = serde_json::from_slice(&response.body).unwrap();
+        request["url"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_path_prefix_strip_and_add() {
+        let endpoint = EndpointConfig {
+            base_url: "http://api.test".to_string(),
+            dry_run: true,
+            path_prefix_strip: Some("/v1/".to_string()),
+            path_prefix_add: Some("internal".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dry_run_url(endpoint.clone(), "/v1/users/{id}").await,
+            json!("http://api.test/internal/users/7")
+        );
+        // Only whole segments are stripped
+        assert_eq!(
+            dry_run_url(endpoint, "/v10/users").await,
+            json!("http://api.test/internal/v10/users")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_prefix_unset_keeps_spec_path() {
+        let endpoint = EndpointConfig {
+            base_url: "http://api.test".to_string(),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dry_run_url(endpoint, "/v1/users").await,
+            json!("http://api.test/v1/users")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_template_expands_environment_variables() {
+        std::env::set_var("YAS_MCP_TEST_API_VERSION", "v2");
+        let endpoint = EndpointConfig {
+            base_url: "http://api.test".to_string(),
+            dry_run: true,
+            path_prefix_strip: Some("/v1".to_string()),
+            path_template: Some("/${YAS_MCP_TEST_API_VERSION}${path}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dry_run_url(endpoint, "/v1/users/{id}").await,
+            json!("http://api.test/v2/users/7")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_template_collapses_empty_segments() {
+        std::env::set_var("YAS_MCP_TEST_EMPTY_SEGMENT", "");
+        let endpoint = EndpointConfig {
+            base_url: "http://api.test".to_string(),
+            dry_run: true,
+            path_template: Some("/${YAS_MCP_TEST_EMPTY_SEGMENT}${path}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            dry_run_url(endpoint, "/users").await,
+            json!("http://api.test/users")
+        );
+    }
+
+    #[test]
+    fn test_path_template_rejects_unset_variables_and_missing_path() {
+        let route = RouteConfig::new("/users".to_string(), "GET".to_string(), String::new());
+        for template in ["/${YAS_MCP_TEST_UNSET_VARIABLE}${path}", "/v2"] {
+            let endpoint = EndpointConfig {
+                base_url: "http://api.test".to_string(),
+                path_template: Some(template.to_string()),
+                ..Default::default()
+            };
+            let error = HttpRequester::new(&endpoint)
+                .unwrap()
+                .build_route_executor(&route)
+                .err()
+                .expect("template should be rejected");
+            assert!(error.to_string().contains(template), "{}", error);
+        }
+    }
+}
@@ -1,13 +1,20 @@
 //! STDIO protocol tests - tests MCP protocol without real I/O
 
 use std::sync::Arc;
-use yas_mcp::internal::config::AppConfig;
+use yas_mcp::internal::config::{AppConfig, EndpointConfig, ServerConfig, ServerMode};
 use yas_mcp::internal::mcp::processor::McpProcessor;
 use yas_mcp::internal::mcp::protocol::JsonRpcRequest;
 use yas_mcp::internal::mcp::registry::ToolRegistry;
-use yas_mcp::internal::server::_server::create_server;
+use yas_mcp::internal::mcp::session::{McpSession, SessionError};
+use yas_mcp::internal::parser::types::{Parser, RouteTool};
+use yas_mcp::internal::requester::mock::MockRequester;
+use yas_mcp::internal::requester::{HttpRequester, Requester, RouteConfig};
+use yas_mcp::internal::server::_server::{create_and_setup_server, create_server};
+use yas_mcp::internal::server::{Server, ServerBuilder};
+use yas_mcp::internal::transport::duplex::DuplexTransport;
 use yas_mcp::internal::transport::mock::MockTransport;
 use yas_mcp::internal::transport::runner::TransportRunner;
+use yas_mcp::internal::transport::Transport;
 
 mod fixtures;
 
@@ -117,6 +124,29 @@ async fn test_list_tools() {
     assert_eq!(tools.len(), registry.count(), "Tool count mismatch");
 }
 
+/// Test: wait_for_responses resolves once the runner has written the expected responses
+#[tokio::test]
+async fn test_mock_transport_waits_for_responses() {
+    let (processor, _registry) = create_test_processor().await;
+    let transport = MockTransport::new();
+    transport
+        .queue_request(&serde_json::from_value(fixtures::requests::list_tools_request(1)).unwrap());
+    transport
+        .queue_request(&serde_json::from_value(fixtures::requests::list_tools_request(2)).unwrap());
+
+    let mut runner = TransportRunner::new(transport.clone(), Arc::new(processor));
+    let run = tokio::spawn(async move { runner.run().await });
+
+    let responses = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        transport.wait_for_responses(2),
+    )
+    .await
+    .expect("both responses are captured");
+    assert_eq!(responses.len(), 2);
+    let _ = run.await;
+}
+
 /// Test: Call tool with valid arguments
 #[tokio::test]
 async fn test_call_tool_valid() {
@@ -237,10 +267,1070 @@ async fn test_notification_no_response() {
     assert_eq!(responses.len(), 0, "Notifications should not get responses");
 }
 
+/// Test: Call tool through an injected parser and dry-run requester returns the real output
+#[tokio::test]
+async fn test_call_tool_with_injected_dependencies() {
+    let endpoint = EndpointConfig {
+        base_url: "http://api.test".to_string(),
+        dry_run: true,
+        ..Default::default()
+    };
+    let requester = HttpRequester::new(&endpoint).unwrap();
+    let (processor, _) = create_stub_processor(Box::new(requester)).await;
+    let transport = MockTransport::new();
+
+    transport.queue_request(
+        &serde_json::from_value(fixtures::requests::call_tool_request(
+            1,
+            "get_task",
+            serde_json::json!({ "task_id": "42" }),
+        ))
+        .unwrap(),
+    );
+
+    let mut runner = TransportRunner::new(transport.clone(), Arc::new(processor));
+    let _ = runner.run().await;
+
+    let responses = transport.get_responses();
+    assert_eq!(responses.len(), 1, "Expected 1 response");
+
+    let result = responses[0].result.as_ref().expect("Should have result");
+    assert_eq!(result["isError"], serde_json::json!(false));
+
+    let text = result["content"][0]["text"]
+        .as_str()
+        .expect("Should have text content");
+    let request: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(request["method"], "GET");
+    assert_eq!(request["url"], "http://api.test/tasks/42");
+}
+
+/// Test: Call tool dispatches to the requester and returns its canned response
+#[tokio::test]
+async fn test_call_tool_returns_mock_response() {
+    let requester = MockRequester::new();
+    requester.respond_with(
+        "GET",
+        "/tasks/{task_id}",
+        200,
+        serde_json::json!({ "id": "42", "title": "Write tests" }),
+    );
+    let (processor, _) = create_stub_processor(Box::new(requester.clone())).await;
+    let transport = MockTransport::new();
+
+    transport.queue_request(
+        &serde_json::from_value(fixtures::requests::call_tool_request(
+            1,
+            "get_task",
+            serde_json::json!({ "task_id": "42" }),
+        ))
+        .unwrap(),
+    );
+
+    let mut runner = TransportRunner::new(transport.clone(), Arc::new(processor));
+    let _ = runner.run().await;
+
+    let responses = transport.get_responses();
+    let result = responses[0].result.as_ref().expect("Should have result");
+    assert_eq!(result["isError"], serde_json::json!(false));
+    assert_eq!(
+        result["content"][0]["text"],
+        r#"{"id":"42","title":"Write tests"}"#
+    );
+
+    let calls = requester.get_calls();
+    assert_eq!(calls.len(), 1, "Expected exactly one upstream call");
+    assert_eq!(calls[0].params, serde_json::json!({ "task_id": "42" }));
+}
+
+/// Test: Upstream error status is surfaced as a tool error result
+#[tokio::test]
+async fn test_call_tool_upstream_error_is_tool_error() {
+    let requester = MockRequester::new();
+    requester.respond_with(
+        "GET",
+        "/tasks/{task_id}",
+        404,
+        serde_json::json!({ "detail": "Task not found" }),
+    );
+    let (processor, _) = create_stub_processor(Box::new(requester)).await;
+    let transport = MockTransport::new();
+
+    transport.queue_request(
+        &serde_json::from_value(fixtures::requests::call_tool_request(
+            1,
+            "get_task",
+            serde_json::json!({ "task_id": "missing" }),
+        ))
+        .unwrap(),
+    );
+
+    let mut runner = TransportRunner::new(transport.clone(), Arc::new(processor));
+    let _ = runner.run().await;
+
+    let responses = transport.get_responses();
+    let result = responses[0].result.as_ref().expect("Should have result");
+    assert_eq!(result["isError"], serde_json::json!(true));
+    assert_eq!(
+        result["content"][0]["text"],
+        r#"{"detail":"Task not found"}"#
+    );
+}
+
+/// Test: A slow route answers after its delay and a broken connection fails the call
+#[tokio::test]
+async fn test_call_tool_with_injected_latency_and_failure() {
+    let requester = MockRequester::new();
+    requester.respond_with(
+        "GET",
+        "/tasks/{task_id}",
+        200,
+        serde_json::json!({ "id": "42" }),
+    );
+    requester.delay(
+        "GET",
+        "/tasks/{task_id}",
+        std::time::Duration::from_millis(50),
+    );
+    let server = create_stub_server(Box::new(requester.clone())).await;
+    let session = McpSession::from_server(&server).await;
+
+    let started = std::time::Instant::now();
+    let result = session
+        .call_tool("get_task", serde_json::json!({ "task_id": "42" }))
+        .await
+        .unwrap();
+    assert!(started.elapsed() >= std::time::Duration::from_millis(50));
+    assert_eq!(result["isError"], serde_json::json!(false));
+
+    requester.fail_with("GET", "/tasks/{task_id}", "connection refused");
+    let result = session
+        .call_tool("get_task", serde_json::json!({ "task_id": "42" }))
+        .await;
+    match result {
+        Err(SessionError::Rpc { message, .. }) => {
+            assert!(
+                message.contains("Failed to execute HTTP request"),
+                "{}",
+                message
+            )
+        }
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+    assert_eq!(requester.get_calls().len(), 2);
+}
+
+/// Test: In-process session drives the handshake, listing and calls
+#[tokio::test]
+async fn test_session_lists_and_calls_tools() {
+    let requester = MockRequester::new();
+    requester.respond_with(
+        "GET",
+        "/tasks/{task_id}",
+        200,
+        serde_json::json!({ "id": "42" }),
+    );
+    let server = create_stub_server(Box::new(requester)).await;
+    let session = McpSession::from_server(&server).await;
+
+    let info = session
+        .initialize()
+        .await
+        .expect("initialize should succeed");
+    assert_eq!(info.server_info.name, server.config.server.name);
+
+    let tools = session
+        .list_tools()
+        .await
+        .expect("tools/list should succeed");
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "get_task");
+
+    let result = session
+        .call_tool("get_task", serde_json::json!({ "task_id": "42" }))
+        .await
+        .expect("tools/call should succeed");
+    assert_eq!(result["content"][0]["text"], r#"{"id":"42"}"#);
+}
+
+/// Test: the tool count is known right after creation, even on a current-thread runtime
+#[tokio::test(flavor = "current_thread")]
+async fn test_create_and_setup_server_registers_tools_once() {
+    let server = create_and_setup_server(AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let count = server.tool_count();
+    assert!(count > 0);
+    // start() calls setup_tools again; it must not parse or register twice
+    server.setup_tools().await.unwrap();
+    assert_eq!(server.tool_count(), count);
+}
+
+/// Test: the tool allowlist keeps only the named tools
+#[tokio::test]
+async fn test_tool_allowlist_drops_other_tools() {
+    let config = AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        server: ServerConfig {
+            tools: vec![
+                "get__projects".to_string(),
+                "post__projects".to_string(),
+                "no_such_tool".to_string(),
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = create_and_setup_server(config).await.unwrap();
+
+    assert_eq!(
+        server.tool_names().await,
+        vec!["get__projects", "post__projects"]
+    );
+    let session = McpSession::from_server(&server).await;
+    match session
+        .call_tool("get__health", serde_json::json!({}))
+        .await
+    {
+        Err(SessionError::Rpc { code, .. }) => assert_eq!(code, -32601),
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+}
+
+/// Test: tools/list narrows to tools whose name or description contains the filter
+#[tokio::test]
+async fn test_list_tools_filter() {
+    let server = create_server(AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    server.setup_tools().await.unwrap();
+    let session = McpSession::from_server(&server).await;
+
+    let all = session.list_tools().await.unwrap();
+    let comments = session.search_tools("COMMENT").await.unwrap();
+    let by_description = session.search_tools("health check").await.unwrap();
+
+    assert!(!comments.is_empty() && comments.len() < all.len());
+    assert!(comments.iter().all(|t| {
+        t.name.to_lowercase().contains("comment")
+            || t.description
+                .as_deref()
+                .unwrap_or_default()
+                .to_lowercase()
+                .contains("comment")
+    }));
+    assert_eq!(by_description.len(), 1);
+    assert_eq!(session.search_tools("  ").await.unwrap().len(), all.len());
+}
+
+/// Test: tools/describe returns one tool with the method, path and base URL it calls
+#[tokio::test]
+async fn test_describe_tool_returns_tool_and_route() {
+    let server = create_server(AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    server.setup_tools().await.unwrap();
+    let session = McpSession::from_server(&server).await;
+
+    let tool = session
+        .list_tools()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.name == "get__tasks___task_id__")
+        .expect("todo spec has a get task tool");
+    let description = session.describe_tool(&tool.name).await.unwrap();
+
+    assert_eq!(description.tool, tool);
+    let route = description.route.expect("route tools have a route");
+    assert_eq!(route.method, "GET");
+    assert_eq!(route.path, "/tasks/{task_id}");
+    assert_eq!(route.base_url.as_deref(), Some("http://localhost:8080"));
+
+    match session.describe_tool("nonexistent_tool").await {
+        Err(SessionError::Rpc { code, .. }) => assert_eq!(code, -32601),
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+}
+
+/// Test: a spec (or allowlist) without tools fails setup in http mode but only warns in stdio mode
+#[tokio::test]
+async fn test_empty_tool_set_fails_in_http_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("empty.yaml");
+    std::fs::write(
+        &spec,
+        "openapi: 3.0.0\ninfo:\n  title: Empty\n  version: \"1.0\"\npaths: {}\n",
+    )
+    .unwrap();
+    let config = |mode: ServerMode, fail_on_empty: Option<bool>| AppConfig {
+        swagger_file: spec.to_string_lossy().to_string(),
+        server: ServerConfig {
+            mode,
+            fail_on_empty,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let server = create_server(config(ServerMode::Http, None)).await.unwrap();
+    let err = server
+        .setup_tools()
+        .await
+        .expect_err("http mode refuses to serve no tools");
+    assert!(err.to_string().contains("No tools were generated"));
+    assert!(err.to_string().contains("--fail-on-empty false"));
+
+    let server = create_server(config(ServerMode::Stdio, None))
+        .await
+        .unwrap();
+    server.setup_tools().await.unwrap();
+    assert_eq!(server.tool_count(), 0);
+
+    let server = create_server(config(ServerMode::Http, Some(false)))
+        .await
+        .unwrap();
+    server.setup_tools().await.unwrap();
+    let server = create_server(config(ServerMode::Stdio, Some(true)))
+        .await
+        .unwrap();
+    assert!(server.setup_tools().await.is_err());
+
+    // An allowlist that matches none of the generated tools leaves nothing to serve either
+    let server = create_server(AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        server: ServerConfig {
+            mode: ServerMode::Http,
+            tools: vec!["no_such_tool".to_string()],
+            ..Default::default()
+        },
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let err = server
+        .setup_tools()
+        .await
+        .expect_err("an allowlist dropping every tool is an empty tool set");
+    assert!(err.to_string().contains("--tools allowlist"), "{:#}", err);
+}
+
+/// Test: the example tool returns a generated request body for a tool
+#[tokio::test]
+async fn test_example_tool_returns_request_body() {
+    let config = AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        server: ServerConfig {
+            example_tool: true,
+            ..Default::default()
+        },
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = create_server(config).await.unwrap();
+    server.setup_tools().await.unwrap();
+    let session = McpSession::from_server(&server).await;
+
+    let result = session
+        .call_tool(
+            "__example_for",
+            serde_json::json!({ "tool": "post__projects" }),
+        )
+        .await
+        .unwrap();
+    let payload: serde_json::Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(payload["source"], "generated");
+    assert_eq!(payload["example"]["title"], "string");
+
+    let result = session
+        .call_tool(
+            "__example_for",
+            serde_json::json!({ "tool": "get__projects" }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result["isError"], true);
+}
+
+/// Test: self-test passes every todo tool and flags a tool whose example request cannot be built
+#[tokio::test]
+async fn test_self_test_reports_malformed_requests() {
+    let config = |swagger_file: String| AppConfig {
+        swagger_file,
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = create_and_setup_server(config("examples/todo-app/openapi.yaml".to_string()))
+        .await
+        .unwrap();
+    let report = server.self_test().await;
+    assert!(report.is_ok(), "{}", report.report());
+    assert_eq!(report.passed.len(), server.tool_count());
+
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("object-path.yaml");
+    std::fs::write(
+        &spec,
+        r#"openapi: 3.0.0
+info:
+  title: Object path parameter
+  version: "1.0"
+paths:
+  /items/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: object
+      responses:
+        "200":
+          description: OK
+  /items:
+    get:
+      responses:
+        "200":
+          description: OK
+"#,
+    )
+    .unwrap();
+    let server = create_and_setup_server(config(spec.to_string_lossy().to_string()))
+        .await
+        .unwrap();
+    let report = server.self_test().await;
+    assert_eq!(report.passed, vec!["get__items".to_string()]);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].tool, "get__items___id__");
+    assert!(
+        report.failures[0].reason.contains("request failed"),
+        "{}",
+        report.report()
+    );
+    assert!(report.report().contains("1 passed, 1 failed"));
+}
+
+/// Test: initialize returns the spec's info.description as instructions, cut to the limit
+#[tokio::test]
+async fn test_instructions_from_spec_description() {
+    let config = |max_instructions_length: usize| AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        server: ServerConfig {
+            max_instructions_length,
+            ..Default::default()
+        },
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let server = create_and_setup_server(config(0)).await.unwrap();
+    let info = McpSession::from_server(&server)
+        .await
+        .initialize()
+        .await
+        .unwrap();
+    let instructions = info.instructions.unwrap();
+    assert!(instructions.starts_with("A comprehensive Todo management API"));
+    assert!(instructions.ends_with("file uploads."));
+
+    let server = create_and_setup_server(config(20)).await.unwrap();
+    let info = McpSession::from_server(&server)
+        .await
+        .initialize()
+        .await
+        .unwrap();
+    assert_eq!(info.instructions.unwrap(), "A comprehensive T...");
+
+    // Without a description the default stays
+    let server = create_stub_server(Box::new(MockRequester::new())).await;
+    let info = McpSession::from_server(&server)
+        .await
+        .initialize()
+        .await
+        .unwrap();
+    assert_eq!(info.instructions.as_deref(), Some("OpenAPI MCP Server"));
+}
+
+/// Test: tool name and schema snapshots reflect the registered tools
+#[tokio::test]
+async fn test_tool_names_and_schemas_snapshot() {
+    let server = create_stub_server(Box::new(MockRequester::new())).await;
+
+    assert_eq!(server.tool_names().await, vec!["get_task".to_string()]);
+    let schemas = server.tool_schemas().await;
+    assert_eq!(schemas.len(), 1);
+    assert_eq!(schemas[0].name, "get_task");
+    assert_eq!(
+        schemas[0].input_schema["required"],
+        serde_json::json!(["task_id"])
+    );
+}
+
+/// Test: Session surfaces JSON-RPC errors as typed errors
+#[tokio::test]
+async fn test_session_call_unknown_tool_returns_rpc_error() {
+    let server = create_stub_server(Box::new(MockRequester::new())).await;
+    let session = McpSession::from_server(&server).await;
+
+    let err = session
+        .call_tool("nonexistent_tool", serde_json::json!({}))
+        .await
+        .expect_err("unknown tool should fail");
+
+    match err {
+        SessionError::Rpc { code, .. } => assert_eq!(code, -32601),
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+}
+
+/// Test: HTTP mode serves JSON-RPC over a Unix socket, replacing a stale socket file
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_mode_over_unix_socket() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let socket_path = dir.path().join("mcp.sock");
+    // A socket file nobody listens on, as left behind by a crashed server
+    drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+
+    let config = AppConfig {
+        swagger_file: "stub.yaml".to_string(),
+        server: ServerConfig {
+            mode: ServerMode::Http,
+            uds: Some(socket_path.to_string_lossy().to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = ServerBuilder::new(config)
+        .parser(Box::new(StubParser::new()))
+        .requester(Box::new(MockRequester::new()))
+        .build()
+        .await
+        .unwrap();
+    let shutdown_handle = server.clone();
+    let handle = tokio::spawn(async move { server.start().await });
+
+    let mut stream = None;
+    for _ in 0..50 {
+        if let Ok(s) = tokio::net::UnixStream::connect(&socket_path).await {
+            stream = Some(s);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let mut stream = stream.expect("server should listen on the socket");
+
+    let body = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+    let request = format!(
+        "POST /mcp HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.contains("get_task"));
+
+    // Shutdown drains the server and removes the socket file
+    shutdown_handle.shutdown();
+    let result = tokio::time::timeout(std::time::Duration::from_secs(5), handle)
+        .await
+        .expect("server should stop after shutdown");
+    assert!(result.unwrap().is_ok());
+    assert!(!socket_path.exists());
+}
+
+/// Test: HTTP mode serves HTTPS when a certificate and key are configured
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_mode_over_tls() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let config = AppConfig {
+        swagger_file: "stub.yaml".to_string(),
+        server: ServerConfig {
+            mode: ServerMode::Http,
+            host: "127.0.0.1".to_string(),
+            port,
+            tls_cert_path: Some("tests/fixtures/tls/cert.pem".to_string()),
+            tls_key_path: Some("tests/fixtures/tls/key.pem".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = ServerBuilder::new(config)
+        .parser(Box::new(StubParser::new()))
+        .requester(Box::new(MockRequester::new()))
+        .build()
+        .await
+        .unwrap();
+    let shutdown_handle = server.clone();
+    let handle = tokio::spawn(async move { server.start().await });
+
+    // The fixture certificate is self-signed
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let mut response = None;
+    for _ in 0..50 {
+        let sent = client
+            .post(format!("https://127.0.0.1:{}/mcp", port))
+            .json(&serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+            .send()
+            .await;
+        if let Ok(r) = sent {
+            response = Some(r);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let body: serde_json::Value = response
+        .expect("server should accept HTTPS")
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(body["result"]["tools"][0]["name"], "get_task");
+
+    // A client that connects but never starts its handshake does not hold up others
+    let _idle = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let fresh_client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let second = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        fresh_client
+            .post(format!("https://127.0.0.1:{}/mcp", port))
+            .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+            .send(),
+    )
+    .await
+    .expect("handshake should not wait on the idle client")
+    .unwrap();
+    assert!(second.status().is_success());
+
+    shutdown_handle.shutdown();
+    assert!(handle.await.unwrap().is_ok());
+}
+
+/// Test: A live initialize -> list -> call conversation over an in-memory duplex pipe
+#[tokio::test]
+async fn test_duplex_conversation() {
+    let requester = MockRequester::new();
+    requester.respond_with(
+        "GET",
+        "/tasks/{task_id}",
+        200,
+        serde_json::json!({"id": "7"}),
+    );
+    let (processor, _) = create_stub_processor(Box::new(requester)).await;
+
+    let (server_end, mut client) = DuplexTransport::pair(64 * 1024);
+    let runner = tokio::spawn(async move {
+        TransportRunner::new(server_end, Arc::new(processor))
+            .run()
+            .await
+    });
+
+    async fn exchange(
+        client: &mut DuplexTransport,
+        request: serde_json::Value,
+    ) -> serde_json::Value {
+        client
+            .write_message(&serde_json::to_vec(&request).unwrap())
+            .await
+            .unwrap();
+        client.flush().await.unwrap();
+        serde_json::from_slice(&client.read_message().await.unwrap()).unwrap()
+    }
+
+    let init = exchange(&mut client, fixtures::requests::initialize_request(1)).await;
+    assert_eq!(init["id"], 1);
+    assert!(init["result"]["serverInfo"].is_object());
+
+    // The next request is built from the previous response
+    let list = exchange(&mut client, fixtures::requests::list_tools_request(2)).await;
+    let tool_name = list["result"]["tools"][0]["name"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let call =
+        fixtures::requests::call_tool_request(3, &tool_name, serde_json::json!({ "task_id": "7" }));
+    let result = exchange(&mut client, call).await;
+    assert_eq!(result["id"], 3);
+    assert_eq!(result["result"]["content"][0]["text"], r#"{"id":"7"}"#);
+
+    // Closing the client ends the runner loop cleanly
+    drop(client);
+    assert!(runner.await.unwrap().is_ok());
+}
+
+/// Test: Removing a tool after initialize sends tools/list_changed to the client
+#[tokio::test]
+async fn test_duplex_tool_list_changed_notification() {
+    let (processor, registry) = create_stub_processor(Box::new(MockRequester::new())).await;
+    let (server_end, mut client) = DuplexTransport::pair(64 * 1024);
+    let runner = tokio::spawn(async move {
+        TransportRunner::new(server_end, Arc::new(processor))
+            .run()
+            .await
+    });
+
+    client
+        .write_message(&serde_json::to_vec(&fixtures::requests::initialize_request(1)).unwrap())
+        .await
+        .unwrap();
+    client.flush().await.unwrap();
+    let init: serde_json::Value =
+        serde_json::from_slice(&client.read_message().await.unwrap()).unwrap();
+    assert_eq!(
+        init["result"]["capabilities"]["tools"]["listChanged"],
+        serde_json::json!(true)
+    );
+
+    assert!(registry.remove("get_task"));
+    let notification: serde_json::Value = serde_json::from_slice(
+        &tokio::time::timeout(std::time::Duration::from_secs(5), client.read_message())
+            .await
+            .expect("notification sent")
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(notification["method"], "notifications/tools/list_changed");
+    assert!(notification.get("id").is_none());
+
+    drop(client);
+    assert!(runner.await.unwrap().is_ok());
+}
+
+/// Test: A tool change notification sent while a line is half-read does not drop its bytes
+#[tokio::test]
+async fn test_duplex_tool_change_mid_line_keeps_partial_read() {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (processor, registry) = create_stub_processor(Box::new(MockRequester::new())).await;
+    let (server_stream, client_stream) = tokio::io::duplex(64 * 1024);
+    let runner = tokio::spawn(async move {
+        TransportRunner::new(
+            DuplexTransport::from_stream(server_stream),
+            Arc::new(processor),
+        )
+        .run()
+        .await
+    });
+    let (client_read, mut client_write) = tokio::io::split(client_stream);
+    let mut lines = BufReader::new(client_read).lines();
+
+    let mut init = serde_json::to_vec(&fixtures::requests::initialize_request(1)).unwrap();
+    init.push(b'\n');
+    client_write.write_all(&init).await.unwrap();
+    let init: serde_json::Value =
+        serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+    assert_eq!(init["id"], 1);
+
+    let list = serde_json::to_vec(&fixtures::requests::list_tools_request(2)).unwrap();
+    let (head, tail) = list.split_at(list.len() / 2);
+    client_write.write_all(head).await.unwrap();
+    client_write.flush().await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    // The runner abandons the pending read to send the notification
+    assert!(registry.remove("get_task"));
+    let notification: serde_json::Value = serde_json::from_str(
+        &tokio::time::timeout(std::time::Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("notification sent")
+            .unwrap()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(notification["method"], "notifications/tools/list_changed");
+
+    client_write.write_all(tail).await.unwrap();
+    client_write.write_all(b"\n").await.unwrap();
+    let response: serde_json::Value = serde_json::from_str(
+        &tokio::time::timeout(std::time::Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("response sent")
+            .unwrap()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response["id"], 2);
+    assert!(response["error"].is_null(), "{}", response);
+    assert!(response["result"]["tools"].is_array());
+
+    drop(client_write);
+    drop(lines);
+    assert!(runner.await.unwrap().is_ok());
+}
+
+/// Test: In stdio mode the binary's stdout carries nothing but JSON-RPC messages, even with debug logging
+#[tokio::test]
+async fn test_stdio_binary_stdout_is_only_json_rpc() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let spec = std::fs::canonicalize("examples/todo-app/openapi.yaml").unwrap();
+    // Run outside the repo so ./config.yaml is not picked up
+    let dir = tempfile::tempdir().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yas-mcp"))
+        .args(["--mode", "stdio", "--endpoint", "http://127.0.0.1:9"])
+        .arg("--swagger-file")
+        .arg(&spec)
+        .current_dir(dir.path())
+        .env("RUST_LOG", "debug")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start yas-mcp");
+
+    let messages = [
+        fixtures::requests::initialize_request(1),
+        fixtures::requests::initialized_notification(),
+        fixtures::requests::list_tools_request(2),
+        fixtures::requests::unknown_method_request(3),
+    ];
+    let mut stdin = child.stdin.take().unwrap();
+    for message in &messages {
+        writeln!(stdin, "{}", message).unwrap();
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    assert_eq!(lines.len(), 3, "one response per request: {}", stdout);
+    for line in lines {
+        let message: serde_json::Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("non JSON-RPC line on stdout ({}): {}", e, line));
+        assert_eq!(message["jsonrpc"], "2.0", "{}", line);
+    }
+    // The debug logs went to stderr instead
+    assert!(!output.stderr.is_empty());
+}
+
+/// Test: Reading the spec from stdin is refused in stdio mode, which owns stdin
+#[tokio::test]
+async fn test_stdin_spec_rejected_in_stdio_mode() {
+    let config = AppConfig {
+        swagger_file: "-".to_string(),
+        ..Default::default()
+    };
+    let server = create_server(config).await.unwrap();
+
+    let err = server
+        .setup_tools()
+        .await
+        .expect_err("stdin spec needs http mode");
+
+    assert!(err.to_string().contains("--mode http"));
+}
+
+/// Test: With a cache dir, the second start loads tools from the cache instead of parsing
+#[tokio::test]
+async fn test_tool_cache_reused_on_next_start() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let config = AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        cache_dir: Some(cache_dir.path().to_string_lossy().to_string()),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let first = create_server(config.clone()).await.unwrap();
+    first.setup_tools().await.unwrap();
+    let entries: Vec<_> = std::fs::read_dir(cache_dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    assert_eq!(entries.len(), 1, "one cache entry per spec");
+
+    // Mark the cached tools so a cache hit is observable
+    let mut cached: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&entries[0]).unwrap()).unwrap();
+    cached["route_tools"][0]["tool"]["name"] = serde_json::json!("from_cache");
+    std::fs::write(&entries[0], serde_json::to_vec(&cached).unwrap()).unwrap();
+
+    let second = create_server(config).await.unwrap();
+    second.setup_tools().await.unwrap();
+    let registry = second.get_tool_registry().await;
+    let processor = McpProcessor::new(&second, registry);
+    let tools = McpSession::new(processor).list_tools().await.unwrap();
+    assert!(tools.iter().any(|t| t.name == "from_cache"));
+}
+
+/// Test: The opt-in info tool describes the spec and lists every operation
+#[tokio::test]
+async fn test_info_tool_describes_api() {
+    let config = AppConfig {
+        swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        server: ServerConfig {
+            info_tool: true,
+            ..Default::default()
+        },
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let server = create_server(config).await.unwrap();
+    server.setup_tools().await.unwrap();
+    let session = McpSession::from_server(&server).await;
+
+    let tools = session.list_tools().await.unwrap();
+    assert!(tools.iter().any(|t| t.name == "__openapi_info"));
+
+    let result = session
+        .call_tool("__openapi_info", serde_json::json!({}))
+        .await
+        .unwrap();
+    let payload: serde_json::Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(payload["apis"][0]["title"], "Todo Management API");
+    assert_eq!(
+        payload["operations"].as_array().unwrap().len(),
+        tools.len() - 1
+    );
+}
+
+/// Parser stub that serves a single fixed route instead of reading a spec
+struct StubParser {
+    route_tools: Vec<RouteTool>,
+}
+
+impl StubParser {
+    fn new() -> Self {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "task_id": { "type": "string" } },
+            "required": ["task_id"]
+        });
+        let tool = rmcp::model::Tool {
+            name: "get_task".into(),
+            title: None,
+            description: Some("Get a task".into()),
+            input_schema: Arc::new(schema.as_object().unwrap().clone()),
+            output_schema: None,
+            annotations: None,
+            icons: None,
+            meta: None,
+        };
+        let route_config = RouteConfig::new(
+            "/tasks/{task_id}".to_string(),
+            "GET".to_string(),
+            "Get a task".to_string(),
+        );
+
+        Self {
+            route_tools: vec![RouteTool { route_config, tool }],
+        }
+    }
+}
+
+impl Parser for StubParser {
+    fn init(&mut self, _openapi_spec: &str, _adjustments_file: Option<&str>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn parse_reader(&mut self, _reader: Box<dyn std::io::Read>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn get_route_tools(&self) -> &[RouteTool] {
+        &self.route_tools
+    }
+}
+
+// Helper to create a server backed by the stub parser and the given requester
+async fn create_stub_server(requester: Box<dyn Requester>) -> Server {
+    let config = AppConfig {
+        swagger_file: "stub.yaml".to_string(),
+        ..Default::default()
+    };
+
+    let server = ServerBuilder::new(config)
+        .parser(Box::new(StubParser::new()))
+        .requester(requester)
+        .build()
+        .await
+        .expect("Failed to build server");
+    server.setup_tools().await.expect("Failed to setup tools");
+    server
+}
+
+// Helper to create a processor backed by the stub parser and the given requester
+async fn create_stub_processor(requester: Box<dyn Requester>) -> (McpProcessor, Arc<ToolRegistry>) {
+    let server = create_stub_server(requester).await;
+    let registry = server.get_tool_registry().await;
+    (McpProcessor::new(&server, registry.clone()), registry)
+}
+
 // Helper to create test processor with tools loaded
 async fn create_test_processor() -> (McpProcessor, Arc<ToolRegistry>) {
     let config = AppConfig {
         swagger_file: "examples/todo-app/openapi.yaml".to_string(),
+        endpoint: EndpointConfig {
+            base_url: "http://localhost:8080".to_string(),
+            ..Default::default()
+        },
         ..Default::default()
     };
 
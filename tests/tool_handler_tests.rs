@@ -0,0 +1,496 @@
+use yas_mcp::internal::gemini::validator::GeminiValidator;
+use yas_mcp::internal::mcp::registry::ToolRegistry;
+use yas_mcp::internal::requester::{
+    HttpResponse, RouteConfig, RouteExecutor, StatusRange, TraceContext,
+};
+use yas_mcp::internal::server::tool::audit::{redact_arguments, AuditLog};
+use yas_mcp::internal::server::tool::handler::ToolExecutor;
+use yas_mcp::internal::server::tool::{ToolCallError, ToolHandler};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{CallToolRequest, CallToolRequestMethod, CallToolRequestParam, Tool};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// Tool whose schema requires `id` and restricts `status` to an enum
+    fn test_tool() -> Tool {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "status": { "type": "string", "enum": ["pending", "done"] }
+            },
+            "required": ["id"]
+        });
+        Tool {
+            name: "get_tasks".into(),
+            title: None,
+            description: None,
+            input_schema: Arc::new(schema.as_object().unwrap().clone()),
+            output_schema: None,
+            annotations: None,
+            icons: None,
+            meta: None,
+        }
+    }
+
+    /// Executor that always succeeds without touching the network
+    fn ok_executor() -> ToolExecutor {
+        Arc::new(|_request: CallToolRequest| {
+            Box::pin(async { Ok(rmcp::model::CallToolResult::success(vec![])) })
+        })
+    }
+
+    fn call_request(arguments: Value) -> CallToolRequest {
+        CallToolRequest {
+            method: CallToolRequestMethod,
+            params: CallToolRequestParam {
+                name: "get_tasks".into(),
+                arguments: arguments.as_object().cloned(),
+            },
+            extensions: Default::default(),
+        }
+    }
+
+    fn handler(validate: bool) -> ToolHandler {
+        let mut handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()))
+            .with_argument_validation(validate);
+        handler.register_tool("get_tasks", test_tool(), ok_executor());
+        handler
+    }
+
+    // ==================== output schema tests ====================
+
+    fn registered_output_schema(output_schema: Value) -> Tool {
+        let mut tool = test_tool();
+        tool.output_schema = Some(Arc::new(output_schema.as_object().unwrap().clone()));
+        let mut handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        handler.register_tool("get_tasks", tool, ok_executor());
+        handler.list_tool_metadata().remove(0)
+    }
+
+    #[test]
+    fn test_array_output_schema_wrapped_in_object() {
+        let item = json!({ "type": "object", "properties": { "id": { "type": "string" } } });
+        let tool = registered_output_schema(json!({ "type": "array", "items": item }));
+
+        let schema = Value::Object(tool.output_schema.as_deref().unwrap().clone());
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["properties"]["result"]["type"], json!("array"));
+        assert_eq!(schema["required"], json!(["result"]));
+        assert!(GeminiValidator::validate_tool(&tool).is_valid);
+    }
+
+    #[test]
+    fn test_object_output_schema_kept() {
+        let output = json!({ "type": "object", "properties": { "id": { "type": "string" } } });
+        let tool = registered_output_schema(output.clone());
+
+        assert_eq!(
+            Value::Object(tool.output_schema.as_deref().unwrap().clone()),
+            output
+        );
+    }
+
+    // ==================== registry tests ====================
+
+    #[test]
+    fn test_registry_exposes_route_of_route_tools() {
+        let registry = Arc::new(ToolRegistry::new());
+        let mut handler = ToolHandler::new(false, registry.clone());
+        let mut route = RouteConfig::new("/tasks".to_string(), "GET".to_string(), String::new());
+        route.base_url = Some("https://tasks.example.com".to_string());
+        handler.register_route_tool("get_tasks", test_tool(), ok_executor(), route);
+        handler.register_tool("api_info", test_tool(), ok_executor());
+
+        let route = registry.get_route("get_tasks").unwrap();
+        assert_eq!(route.method, "GET");
+        assert_eq!(route.path, "/tasks");
+        assert_eq!(route.base_url.as_deref(), Some("https://tasks.example.com"));
+        assert!(registry.get_route("api_info").is_none());
+        assert!(registry.get_route("missing").is_none());
+    }
+
+    // ==================== argument validation tests ====================
+
+    #[tokio::test]
+    async fn test_validation_disabled_passes_invalid_arguments() {
+        let executor = handler(false).get_executor("get_tasks").unwrap();
+
+        let result = executor(call_request(json!({}))).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validation_accepts_valid_arguments() {
+        let executor = handler(true).get_executor("get_tasks").unwrap();
+
+        let result = executor(call_request(json!({ "id": "1", "status": "done" }))).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validation_reports_all_violations() {
+        let executor = handler(true).get_executor("get_tasks").unwrap();
+
+        let err = executor(call_request(json!({ "status": "archived" })))
+            .await
+            .expect_err("invalid arguments should be rejected");
+
+        let tool_error = err
+            .downcast_ref::<ToolCallError>()
+            .expect("should be a ToolCallError");
+        assert_eq!(tool_error.code(), -32602);
+
+        let ToolCallError::InvalidArguments(violations) = tool_error;
+        assert_eq!(
+            violations.len(),
+            2,
+            "missing id and bad enum: {:?}",
+            violations
+        );
+        assert!(violations.iter().any(|v| v.contains("id")));
+        assert!(violations.iter().any(|v| v.contains("/status")));
+    }
+
+    #[tokio::test]
+    async fn test_validation_accepts_arguments_the_executor_would_coerce() {
+        let mut tool = test_tool();
+        tool.input_schema = Arc::new(
+            json!({
+                "type": "object",
+                "properties": {
+                    "page": { "type": "integer" },
+                    "verbose": { "type": "boolean" }
+                },
+                "required": ["page"]
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        );
+        let received = Arc::new(std::sync::Mutex::new(None));
+        let seen = received.clone();
+        let executor: ToolExecutor = Arc::new(move |request: CallToolRequest| {
+            *seen.lock().unwrap() = request.params.arguments.clone();
+            Box::pin(async { Ok(rmcp::model::CallToolResult::success(vec![])) })
+        });
+        let mut handler =
+            ToolHandler::new(false, Arc::new(ToolRegistry::new())).with_argument_validation(true);
+        handler.register_tool("get_tasks", tool, executor);
+        let executor = handler.get_executor("get_tasks").unwrap();
+
+        executor(call_request(json!({ "page": "1", "verbose": "true" })))
+            .await
+            .expect("coercible strings should pass validation");
+        assert_eq!(
+            received.lock().unwrap().clone().map(Value::Object),
+            Some(json!({ "page": 1, "verbose": true }))
+        );
+
+        let err = executor(call_request(json!({ "page": "one" })))
+            .await
+            .expect_err("unparseable values are still rejected");
+        assert!(err.downcast_ref::<ToolCallError>().is_some());
+    }
+
+    // ==================== audit log tests ====================
+
+    /// Route executor answering every call with the given status
+    fn status_executor(status_code: u16) -> RouteExecutor {
+        Arc::new(move |_params: &str| {
+            Box::pin(async move {
+                Ok(HttpResponse {
+                    status_code,
+                    body: b"{}".to_vec(),
+                    headers: Default::default(),
+                })
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_records_calls_with_correlation_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()))
+            .with_audit_log(Some(Arc::new(AuditLog::open(&path).unwrap())));
+
+        let ok = handler.create_handler("get_tasks", status_executor(200));
+        let failing = handler.create_handler("get_tasks", status_executor(503));
+        let result = ok(call_request(json!({ "id": "1", "api_key": "s3cret" })))
+            .await
+            .unwrap();
+        failing(call_request(json!({ "id": "2" }))).await.unwrap();
+
+        let records: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+
+        let correlation_id = &result.meta.as_ref().unwrap().0["correlation_id"];
+        assert_eq!(&records[0]["correlation_id"], correlation_id);
+        assert_eq!(records[0]["tool"], "get_tasks");
+        assert_eq!(records[0]["outcome"], "success");
+        assert_eq!(records[0]["status"], 200);
+        assert_eq!(records[0]["arguments"]["id"], "1");
+        assert_eq!(records[0]["arguments"]["api_key"], "<redacted>");
+        assert_eq!(records[1]["outcome"], "upstream_error");
+        assert_ne!(records[1]["correlation_id"], records[0]["correlation_id"]);
+    }
+
+    #[tokio::test]
+    async fn test_no_correlation_id_without_audit_log() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let executor = handler.create_handler("get_tasks", status_executor(200));
+
+        let result = executor(call_request(json!({ "id": "1" }))).await.unwrap();
+
+        assert!(!result.meta.unwrap().0.contains_key("correlation_id"));
+    }
+
+    // ==================== trace context tests ====================
+
+    /// Route executor that records the parameters of every call
+    fn capturing_executor(calls: Arc<std::sync::Mutex<Vec<Value>>>) -> RouteExecutor {
+        Arc::new(move |params: &str| {
+            calls
+                .lock()
+                .unwrap()
+                .push(serde_json::from_str(params).unwrap());
+            Box::pin(async move {
+                Ok(HttpResponse {
+                    status_code: 200,
+                    body: b"{}".to_vec(),
+                    headers: Default::default(),
+                })
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_continues_caller_trace_or_starts_one() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler =
+            ToolHandler::new(false, Arc::new(ToolRegistry::new())).with_trace_context(true);
+        let executor = handler.create_handler("get_tasks", capturing_executor(calls.clone()));
+
+        let parent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        executor(call_request(json!({ "id": "1", "__traceparent": parent })))
+            .await
+            .unwrap();
+        executor(call_request(json!({ "id": "2" }))).await.unwrap();
+
+        let calls = calls.lock().unwrap();
+        let child = TraceContext::parse(calls[0]["__traceparent"].as_str().unwrap()).unwrap();
+        assert_eq!(child.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(child.span_id, "00f067aa0ba902b7");
+        let root = TraceContext::parse(calls[1]["__traceparent"].as_str().unwrap()).unwrap();
+        assert_ne!(root.trace_id, child.trace_id);
+        assert!(root.sampled);
+    }
+
+    #[tokio::test]
+    async fn test_no_trace_context_by_default() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let executor = handler.create_handler("get_tasks", capturing_executor(calls.clone()));
+
+        executor(call_request(json!({ "id": "1" }))).await.unwrap();
+
+        assert_eq!(calls.lock().unwrap()[0], json!({ "id": "1" }));
+    }
+
+    // ==================== result tests ====================
+
+    /// Route executor answering with the given status, content type and body
+    fn body_executor(status_code: u16, content_type: &str, body: &str) -> RouteExecutor {
+        let headers = HashMap::from([("content-type".to_string(), content_type.to_string())]);
+        let body = body.as_bytes().to_vec();
+        Arc::new(move |_params: &str| {
+            let response = HttpResponse {
+                status_code,
+                body: body.clone(),
+                headers: headers.clone(),
+            };
+            Box::pin(async move { Ok(response) })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_problem_json_error_is_structured() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let executor = body_executor(
+            404,
+            "application/problem+json; charset=utf-8",
+            r#"{"type": "https://example.com/not-found", "title": "Not Found", "detail": "Task 7 does not exist", "trace": "x"}"#,
+        );
+
+        let result = handler.create_handler("get_tasks", executor)(call_request(json!({})))
+            .await
+            .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            result.structured_content,
+            Some(json!({
+                "type": "https://example.com/not-found",
+                "title": "Not Found",
+                "detail": "Task 7 does not exist",
+                "status": 404
+            }))
+        );
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Not Found: Task 7 does not exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_problem_json_falls_back_to_text() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let executor = body_executor(500, "application/problem+json", "upstream exploded");
+
+        let result = handler.create_handler("get_tasks", executor)(call_request(json!({})))
+            .await
+            .unwrap();
+
+        assert_eq!(result.structured_content, None);
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "upstream exploded"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_returns_no_content_result() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let executor: RouteExecutor = Arc::new(|_params: &str| {
+            Box::pin(async {
+                Ok(HttpResponse {
+                    status_code: 204,
+                    body: Vec::new(),
+                    headers: Default::default(),
+                })
+            })
+        });
+
+        let result = handler.create_handler("get_tasks", executor)(call_request(json!({})))
+            .await
+            .unwrap();
+
+        let expected = json!({ "status": 204, "body": null });
+        assert_eq!(result.is_error, Some(false));
+        assert_eq!(result.structured_content, Some(expected.clone()));
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert_eq!(serde_json::from_str::<Value>(&text).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_success_statuses_decide_is_error() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let mut route = RouteConfig::default();
+        route.method_config.success_statuses = vec!["201".parse::<StatusRange>().unwrap()];
+
+        for (status, is_error) in [(201, false), (200, true), (404, true)] {
+            let executor =
+                handler.create_route_handler("post_tasks", status_executor(status), &route);
+            let result = executor(call_request(json!({}))).await.unwrap();
+
+            assert_eq!(result.is_error, Some(is_error), "status {}", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_field_flags_successful_body_as_error() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let mut route = RouteConfig::default();
+        route.method_config.error_field = Some("$.error.message".parse().unwrap());
+        let call = |body: &'static str| {
+            let executor = handler.create_route_handler(
+                "get_tasks",
+                body_executor(200, "application/json", body),
+                &route,
+            );
+            async move { executor(call_request(json!({}))).await.unwrap() }
+        };
+
+        let result = call(r#"{"error": {"message": "quota exceeded"}}"#).await;
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(result.content[0].as_text().unwrap().text, "quota exceeded");
+        assert_eq!(result.meta.unwrap().0["http_status"], json!(200));
+
+        for body in [
+            r#"{"error": {"message": ""}}"#,
+            r#"{"items": []}"#,
+            "not json",
+        ] {
+            assert_eq!(call(body).await.is_error, Some(false), "{}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_result_meta_carries_http_status() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+
+        for status in [200, 201, 503] {
+            let executor = handler.create_handler("get_tasks", status_executor(status));
+            let result = executor(call_request(json!({ "id": "1" }))).await.unwrap();
+
+            assert_eq!(result.meta.unwrap().0["http_status"], json!(status));
+            assert_eq!(result.is_error, Some(status >= 400));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_is_a_success_carrying_the_etag() {
+        let handler = ToolHandler::new(false, Arc::new(ToolRegistry::new()));
+        let route: RouteExecutor = Arc::new(|_params: &str| {
+            Box::pin(async {
+                Ok(HttpResponse {
+                    status_code: 304,
+                    body: Vec::new(),
+                    headers: HashMap::from([("ETag".to_string(), "\"v1\"".to_string())]),
+                })
+            })
+        });
+        let executor = handler.create_handler("get_tasks", route);
+
+        let result = executor(call_request(json!({ "id": "1" }))).await.unwrap();
+        assert_eq!(result.is_error, Some(false));
+        assert_eq!(
+            result.structured_content,
+            Some(json!({ "status": 304, "not_modified": true, "etag": "\"v1\"" }))
+        );
+        let meta = result.meta.unwrap();
+        assert_eq!(meta.0["http_status"], json!(304));
+        assert_eq!(meta.0["etag"], json!("\"v1\""));
+    }
+
+    #[test]
+    fn test_redact_arguments_masks_nested_credentials() {
+        let arguments = json!({
+            "name": "x",
+            "credentials": { "password": "p", "user": "u" },
+            "headers": [{ "X-Auth-Token": "t" }]
+        });
+
+        let redacted = redact_arguments(arguments.as_object().unwrap());
+
+        assert_eq!(
+            redacted,
+            json!({
+                "name": "x",
+                "credentials": { "password": "<redacted>", "user": "u" },
+                "headers": [{ "X-Auth-Token": "<redacted>" }]
+            })
+        );
+    }
+}